@@ -1,8 +1,16 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use makepad_router_core::Router;
-use makepad_router_perf::{build_mixed_route_table, router_from_table};
+use makepad_router_perf::{
+    build_dynamic_first_route_table, build_mixed_route_table, router_from_table,
+};
 use std::collections::VecDeque;
 
+fn route_register_bulk_1k(c: &mut Criterion) {
+    c.bench_function("route_register_bulk_1k", |b| {
+        b.iter(|| black_box(build_mixed_route_table(black_box(1_000))));
+    });
+}
+
 fn route_lookup_exact_static_1k(c: &mut Criterion) {
     let table = build_mixed_route_table(1_000);
     let registry = table.registry;
@@ -33,6 +41,36 @@ fn route_lookup_dynamic_1k(c: &mut Criterion) {
     });
 }
 
+fn route_lookup_dynamic_first_1k(c: &mut Criterion) {
+    let table = build_dynamic_first_route_table(1_000);
+    let registry = table.registry;
+    let paths = table.dynamic_paths;
+
+    c.bench_function("route_lookup_dynamic_first_1k", |b| {
+        let mut i = 0usize;
+        b.iter(|| {
+            let path = &paths[i % paths.len()];
+            i = i.wrapping_add(1);
+            black_box(registry.resolve_path(black_box(path)));
+        });
+    });
+}
+
+fn route_lookup_miss_1k(c: &mut Criterion) {
+    let table = build_mixed_route_table(1_000);
+    let registry = table.registry;
+    let paths = table.miss_paths;
+
+    c.bench_function("route_lookup_miss_1k", |b| {
+        let mut i = 0usize;
+        b.iter(|| {
+            let path = &paths[i % paths.len()];
+            i = i.wrapping_add(1);
+            black_box(registry.resolve_path(black_box(path)));
+        });
+    });
+}
+
 fn route_lookup_mixed_1k(c: &mut Criterion) {
     let table = build_mixed_route_table(1_000);
     let registry = table.registry;
@@ -208,8 +246,11 @@ fn dispatch_with_async_guard_pending(c: &mut Criterion) {
 
 criterion_group!(
     router_perf,
+    route_register_bulk_1k,
     route_lookup_exact_static_1k,
     route_lookup_dynamic_1k,
+    route_lookup_dynamic_first_1k,
+    route_lookup_miss_1k,
     route_lookup_mixed_1k,
     dispatch_go_to_path_1k,
     dispatch_go_to_path_nested_1k,
@@ -1,6 +1,7 @@
 use makepad_router_perf::{
-    build_mixed_route_table, router_from_table, run_dispatch_go_to_path_samples,
-    run_lookup_samples, run_stack_ops_samples, stats_to_json, summarize_us,
+    build_dynamic_first_route_table, build_mixed_route_table, router_from_table,
+    run_dispatch_go_to_path_samples, run_lookup_samples, run_stack_ops_samples, stats_to_json,
+    summarize_us,
 };
 use std::collections::HashMap;
 use std::env;
@@ -11,6 +12,7 @@ fn main() {
     let args = Args::parse();
 
     let table = build_mixed_route_table(1_000);
+    let dynamic_first = build_dynamic_first_route_table(1_000);
     let router = router_from_table(&table);
 
     let mut stats = Vec::new();
@@ -22,6 +24,18 @@ fn main() {
         "route_lookup_dynamic_1k",
         &run_lookup_samples(&table.registry, &table.dynamic_paths, args.iterations),
     ));
+    stats.push(summarize_us(
+        "route_lookup_dynamic_first_1k",
+        &run_lookup_samples(
+            &dynamic_first.registry,
+            &dynamic_first.dynamic_paths,
+            args.iterations,
+        ),
+    ));
+    stats.push(summarize_us(
+        "route_lookup_miss_1k",
+        &run_lookup_samples(&table.registry, &table.miss_paths, args.iterations),
+    ));
     stats.push(summarize_us(
         "route_lookup_mixed_1k",
         &run_lookup_samples(&table.registry, &table.mixed_paths, args.iterations),
@@ -0,0 +1,240 @@
+//! Typed route arguments.
+//!
+//! `#[derive(RouteArgs)]` (from `makepad-router-derive`) implements [`RouteArgs`] for a struct, so a
+//! screen can read its params/query as one typed value instead of individual lookups:
+//!
+//! ```ignore
+//! #[derive(RouteArgs)]
+//! struct UserArgs {
+//!     id: u64,                     // path param `:id`
+//!     #[route(query)]
+//!     tab: Option<String>,         // `?tab=...`
+//!     #[route(query = "tag")]
+//!     tags: Vec<String>,           // `?tag=a&tag=b`
+//! }
+//!
+//! registry.register_args::<UserArgs>("/user/:id", live_id!(user_profile))?;
+//! let args: UserArgs = route.args()?;
+//! ```
+
+use crate::error::RouteArgsError;
+use crate::pattern::{RouteParams, RoutePattern, RouteSegment};
+use crate::route::{Route, RouteQuery};
+use makepad_live_id::LiveId;
+
+/// A value that can be read from and written to a path segment or query value.
+pub trait RouteArgValue: Sized {
+    /// Parse from the (decoded) text of a param or query value.
+    fn parse_arg(raw: &str) -> Option<Self>;
+    /// Format as text for a param or query value.
+    fn format_arg(&self) -> String;
+}
+
+impl RouteArgValue for String {
+    fn parse_arg(raw: &str) -> Option<Self> {
+        Some(raw.to_string())
+    }
+
+    fn format_arg(&self) -> String {
+        self.clone()
+    }
+}
+
+impl RouteArgValue for bool {
+    /// Accepts 1/0, true/false, yes/no, on/off (like `Route::get_param_bool`).
+    fn parse_arg(raw: &str) -> Option<Self> {
+        match raw.to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => Some(true),
+            "0" | "false" | "no" | "off" => Some(false),
+            _ => None,
+        }
+    }
+
+    fn format_arg(&self) -> String {
+        self.to_string()
+    }
+}
+
+macro_rules! route_arg_value_from_str {
+    ($($t:ty),*) => {$(
+        impl RouteArgValue for $t {
+            fn parse_arg(raw: &str) -> Option<Self> {
+                raw.parse().ok()
+            }
+
+            fn format_arg(&self) -> String {
+                self.to_string()
+            }
+        }
+    )*};
+}
+
+route_arg_value_from_str!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+/// A path param read by a [`RouteArgs`] type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RouteArgParam {
+    pub name: &'static str,
+    /// `false` for `Option<T>` fields.
+    pub required: bool,
+}
+
+/// Typed view of a route's params and query; usually implemented with `#[derive(RouteArgs)]`.
+pub trait RouteArgs: Sized {
+    /// Path params read by this type.
+    fn route_params() -> &'static [RouteArgParam];
+
+    /// Read the arguments from a resolved route.
+    fn from_route(route: &Route) -> Result<Self, RouteArgsError>;
+
+    /// Path params for building a path from the arguments.
+    fn to_params(&self) -> RouteParams;
+
+    /// Query values for building a URL from the arguments.
+    fn to_query(&self) -> RouteQuery;
+
+    /// Build a route carrying these arguments.
+    fn to_route(&self, route_id: LiveId) -> Route {
+        let mut route = Route::with_params(route_id, self.to_params());
+        route.query = self.to_query();
+        route
+    }
+
+    /// Check that `pattern` captures every param this type reads.
+    ///
+    /// A required field must map to a segment that is always captured: a required `:param`, an
+    /// optional one with a default, or a named `*wildcard`.
+    fn validate_pattern(pattern: &RoutePattern) -> Result<(), RouteArgsError> {
+        for param in Self::route_params() {
+            let segment = pattern.segments.iter().find(|segment| match segment {
+                RouteSegment::Dynamic { name, .. } => name == param.name,
+                RouteSegment::WildcardSingle { name, .. }
+                | RouteSegment::WildcardMulti { name, .. } => name.as_deref() == Some(param.name),
+                RouteSegment::Static(_) => false,
+            });
+            let always_captured = match segment {
+                None => {
+                    return Err(RouteArgsError::UnknownParam {
+                        name: param.name.to_string(),
+                    })
+                }
+                Some(RouteSegment::Dynamic {
+                    optional, default, ..
+                }) => !optional || default.is_some(),
+                Some(RouteSegment::WildcardSingle { .. }) => true,
+                Some(_) => false,
+            };
+            if param.required && !always_captured {
+                return Err(RouteArgsError::OptionalParam {
+                    name: param.name.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Everything the generated `RouteArgs` impls name, re-exported at the crate root as
+/// `makepad_router_core::__private` so the derive needs a single path.
+#[doc(hidden)]
+pub mod __private {
+    use super::*;
+
+    pub use super::{RouteArgParam, RouteArgs};
+    pub use crate::error::RouteArgsError;
+    pub use crate::pattern::RouteParams;
+    pub use crate::route::{Route, RouteQuery};
+
+    fn parse<T: RouteArgValue>(raw: &str) -> Option<T> {
+        T::parse_arg(raw)
+    }
+
+    pub fn param_opt<T: RouteArgValue>(
+        route: &Route,
+        name: &str,
+    ) -> Result<Option<T>, RouteArgsError> {
+        let Some(raw) = route.get_param_str(LiveId::from_str(name)) else {
+            return Ok(None);
+        };
+        parse(&raw)
+            .map(Some)
+            .ok_or_else(|| RouteArgsError::InvalidParam {
+                name: name.to_string(),
+                value: raw.into_owned(),
+            })
+    }
+
+    pub fn param<T: RouteArgValue>(route: &Route, name: &str) -> Result<T, RouteArgsError> {
+        param_opt(route, name)?.ok_or_else(|| RouteArgsError::MissingParam {
+            name: name.to_string(),
+        })
+    }
+
+    pub fn query_opt<T: RouteArgValue>(
+        route: &Route,
+        key: &str,
+    ) -> Result<Option<T>, RouteArgsError> {
+        let Some(raw) = route.query_get(key) else {
+            return Ok(None);
+        };
+        parse(raw)
+            .map(Some)
+            .ok_or_else(|| RouteArgsError::InvalidQuery {
+                key: key.to_string(),
+                value: raw.to_string(),
+            })
+    }
+
+    pub fn query<T: RouteArgValue>(route: &Route, key: &str) -> Result<T, RouteArgsError> {
+        query_opt(route, key)?.ok_or_else(|| RouteArgsError::MissingQuery {
+            key: key.to_string(),
+        })
+    }
+
+    pub fn query_all<T: RouteArgValue>(route: &Route, key: &str) -> Result<Vec<T>, RouteArgsError> {
+        route
+            .query_get_all(key)
+            .into_iter()
+            .map(|raw| {
+                parse(raw).ok_or_else(|| RouteArgsError::InvalidQuery {
+                    key: key.to_string(),
+                    value: raw.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn add_param<T: RouteArgValue>(params: &mut RouteParams, name: &str, value: &T) {
+        params.add_string(LiveId::from_str(name), value.format_arg());
+    }
+
+    pub fn add_param_opt<T: RouteArgValue>(
+        params: &mut RouteParams,
+        name: &str,
+        value: &Option<T>,
+    ) {
+        if let Some(value) = value {
+            add_param(params, name, value);
+        }
+    }
+
+    pub fn append_query<T: RouteArgValue>(query: &mut RouteQuery, key: &str, value: &T) {
+        query.append(key, value.format_arg());
+    }
+
+    pub fn append_query_opt<T: RouteArgValue>(
+        query: &mut RouteQuery,
+        key: &str,
+        value: &Option<T>,
+    ) {
+        if let Some(value) = value {
+            append_query(query, key, value);
+        }
+    }
+
+    pub fn append_query_all<T: RouteArgValue>(query: &mut RouteQuery, key: &str, values: &[T]) {
+        for value in values {
+            append_query(query, key, value);
+        }
+    }
+}
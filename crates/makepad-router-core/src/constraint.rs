@@ -0,0 +1,340 @@
+//! Value constraints for dynamic route segments.
+//!
+//! Constraints are declared inline in a pattern (`/user/:id<u64>`) and checked by
+//! `RoutePattern::matches` before a segment is accepted. Supported forms:
+//! - Built-in types: `u64`, `i64`, `f64`, `bool`, `uuid`
+//! - Alternations: `(info|posts)`
+//! - A single bracket character class with an optional quantifier: `[a-z-]+`, `[0-9]{4}`,
+//!   `[^.]*` (a small regex subset; the whole segment must match)
+
+use crate::error::PatternError;
+use makepad_micro_serde::*;
+use std::fmt;
+
+/// Inclusive range of Unicode scalar values in a character class.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, SerBin, DeBin, SerRon, DeRon)]
+pub struct RouteCharRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Bracket character class with repetition bounds (e.g., `[a-z-]+`).
+#[derive(Clone, Debug, PartialEq, Eq, Hash, SerBin, DeBin, SerRon, DeRon)]
+pub struct RouteCharClass {
+    pub ranges: Vec<RouteCharRange>,
+    pub negated: bool,
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+/// Constraint on the value of a dynamic segment (e.g., `:id<u64>`).
+#[derive(Clone, Debug, PartialEq, Eq, Hash, SerBin, DeBin, SerRon, DeRon)]
+pub enum RouteParamConstraint {
+    /// Unsigned integer (`u64`).
+    U64,
+    /// Signed integer (`i64`).
+    I64,
+    /// Floating point number (`f64`).
+    F64,
+    /// Boolean (`bool`, accepts 1/0, true/false, yes/no, on/off).
+    Bool,
+    /// Hyphenated UUID (`uuid`, e.g. `123e4567-e89b-12d3-a456-426614174000`).
+    Uuid,
+    /// One of a fixed set of values (`(info|posts)`).
+    OneOf(Vec<String>),
+    /// Character class with repetition (`[a-z-]+`).
+    CharClass(RouteCharClass),
+}
+
+/// Constraint errors are reported relative to the start of the constraint text.
+fn invalid(reason: String) -> PatternError {
+    PatternError::InvalidConstraint {
+        position: 0,
+        reason,
+    }
+}
+
+impl RouteCharClass {
+    fn contains(&self, c: char) -> bool {
+        let c = c as u32;
+        let hit = self.ranges.iter().any(|r| r.start <= c && c <= r.end);
+        hit != self.negated
+    }
+
+    fn matches(&self, value: &str) -> bool {
+        let mut count = 0usize;
+        for c in value.chars() {
+            if !self.contains(c) {
+                return false;
+            }
+            count += 1;
+        }
+        count >= self.min && self.max.is_none_or(|max| count <= max)
+    }
+
+    fn parse(source: &str) -> Result<Self, PatternError> {
+        let body = source
+            .strip_prefix('[')
+            .ok_or_else(|| invalid(format!("Invalid constraint <{}>", source)))?;
+        let mut chars = body.chars().peekable();
+        let mut negated = false;
+        if chars.peek() == Some(&'^') {
+            negated = true;
+            chars.next();
+        }
+
+        let mut members = Vec::<char>::new();
+        let mut ranges = Vec::<RouteCharRange>::new();
+        let mut closed = false;
+        while let Some(c) = chars.next() {
+            match c {
+                ']' => {
+                    closed = true;
+                    break;
+                }
+                '\\' => {
+                    let escaped = chars.next().ok_or_else(|| {
+                        invalid(format!("Unterminated escape in constraint <{}>", source))
+                    })?;
+                    members.push(escaped);
+                }
+                '-' if !members.is_empty() && chars.peek().is_some_and(|n| *n != ']') => {
+                    let start = members.pop().unwrap_or('-');
+                    let mut end = chars.next().unwrap_or('-');
+                    if end == '\\' {
+                        end = chars.next().unwrap_or('\\');
+                    }
+                    if (end as u32) < (start as u32) {
+                        return Err(invalid(format!(
+                            "Invalid range {}-{} in constraint <{}>",
+                            start, end, source
+                        )));
+                    }
+                    ranges.push(RouteCharRange {
+                        start: start as u32,
+                        end: end as u32,
+                    });
+                }
+                c => members.push(c),
+            }
+        }
+        if !closed {
+            return Err(invalid(format!(
+                "Unterminated character class in constraint <{}>",
+                source
+            )));
+        }
+        ranges.extend(members.into_iter().map(|c| RouteCharRange {
+            start: c as u32,
+            end: c as u32,
+        }));
+        if ranges.is_empty() {
+            return Err(invalid(format!(
+                "Empty character class in constraint <{}>",
+                source
+            )));
+        }
+
+        let quantifier: String = chars.collect();
+        let (min, max) = match quantifier.as_str() {
+            "" => (1, Some(1)),
+            "+" => (1, None),
+            "*" => (0, None),
+            "?" => (0, Some(1)),
+            q => Self::parse_repetition(q).ok_or_else(|| {
+                invalid(format!(
+                    "Invalid quantifier '{}' in constraint <{}>",
+                    q, source
+                ))
+            })?,
+        };
+
+        Ok(Self {
+            ranges,
+            negated,
+            min,
+            max,
+        })
+    }
+
+    fn parse_repetition(quantifier: &str) -> Option<(usize, Option<usize>)> {
+        let inner = quantifier.strip_prefix('{')?.strip_suffix('}')?;
+        match inner.split_once(',') {
+            None => {
+                let n = inner.trim().parse().ok()?;
+                Some((n, Some(n)))
+            }
+            Some((min, max)) => {
+                let min = min.trim().parse().ok()?;
+                let max = match max.trim() {
+                    "" => None,
+                    max => Some(max.parse().ok()?),
+                };
+                if max.is_some_and(|max| max < min) {
+                    return None;
+                }
+                Some((min, max))
+            }
+        }
+    }
+}
+
+impl RouteParamConstraint {
+    /// Parse constraint source (the text between `<` and `>`).
+    pub fn parse(source: &str) -> Result<Self, PatternError> {
+        let source = source.trim();
+        match source {
+            "" => Err(invalid("Constraint cannot be empty".to_string())),
+            "u64" => Ok(Self::U64),
+            "i64" => Ok(Self::I64),
+            "f64" => Ok(Self::F64),
+            "bool" => Ok(Self::Bool),
+            "uuid" => Ok(Self::Uuid),
+            s if s.starts_with('(') => {
+                let inner = s
+                    .strip_prefix('(')
+                    .and_then(|s| s.strip_suffix(')'))
+                    .ok_or_else(|| {
+                        invalid(format!(
+                            "Unterminated alternation in constraint <{}>",
+                            source
+                        ))
+                    })?;
+                let options: Vec<String> = inner.split('|').map(|s| s.to_string()).collect();
+                if options.iter().any(|o| o.is_empty()) {
+                    return Err(invalid(format!(
+                        "Empty alternative in constraint <{}>",
+                        source
+                    )));
+                }
+                Ok(Self::OneOf(options))
+            }
+            s if s.starts_with('[') => Ok(Self::CharClass(RouteCharClass::parse(s)?)),
+            _ => Err(invalid(format!("Unknown constraint <{}>", source))),
+        }
+    }
+
+    /// Check whether a (non-empty) segment value satisfies this constraint.
+    pub fn matches(&self, value: &str) -> bool {
+        match self {
+            Self::U64 => value.parse::<u64>().is_ok(),
+            Self::I64 => value.parse::<i64>().is_ok(),
+            Self::F64 => value.parse::<f64>().is_ok(),
+            Self::Bool => matches!(
+                value.to_ascii_lowercase().as_str(),
+                "1" | "0" | "true" | "false" | "yes" | "no" | "on" | "off"
+            ),
+            Self::Uuid => is_uuid(value),
+            Self::OneOf(options) => options.iter().any(|o| o == value),
+            Self::CharClass(class) => class.matches(value),
+        }
+    }
+}
+
+impl fmt::Display for RouteCharClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let write_char = |f: &mut fmt::Formatter<'_>, c: u32| {
+            let c = char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER);
+            if matches!(c, ']' | '\\' | '-' | '^') {
+                write!(f, "\\{}", c)
+            } else {
+                write!(f, "{}", c)
+            }
+        };
+        write!(f, "[")?;
+        if self.negated {
+            write!(f, "^")?;
+        }
+        for range in &self.ranges {
+            write_char(f, range.start)?;
+            if range.end != range.start {
+                write!(f, "-")?;
+                write_char(f, range.end)?;
+            }
+        }
+        write!(f, "]")?;
+        match (self.min, self.max) {
+            (1, Some(1)) => Ok(()),
+            (1, None) => write!(f, "+"),
+            (0, None) => write!(f, "*"),
+            (0, Some(1)) => write!(f, "?"),
+            (min, None) => write!(f, "{{{},}}", min),
+            (min, Some(max)) if min == max => write!(f, "{{{}}}", min),
+            (min, Some(max)) => write!(f, "{{{},{}}}", min, max),
+        }
+    }
+}
+
+impl fmt::Display for RouteParamConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::U64 => write!(f, "u64"),
+            Self::I64 => write!(f, "i64"),
+            Self::F64 => write!(f, "f64"),
+            Self::Bool => write!(f, "bool"),
+            Self::Uuid => write!(f, "uuid"),
+            Self::OneOf(options) => write!(f, "({})", options.join("|")),
+            Self::CharClass(class) => write!(f, "{}", class),
+        }
+    }
+}
+
+fn is_uuid(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    if bytes.len() != 36 {
+        return false;
+    }
+    bytes.iter().enumerate().all(|(i, b)| match i {
+        8 | 13 | 18 | 23 => *b == b'-',
+        _ => b.is_ascii_hexdigit(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constraint_builtin_types() {
+        let c = RouteParamConstraint::parse("u64").unwrap();
+        assert!(c.matches("42"));
+        assert!(!c.matches("-1"));
+        assert!(!c.matches("settings"));
+
+        let c = RouteParamConstraint::parse("uuid").unwrap();
+        assert!(c.matches("123e4567-e89b-12d3-a456-426614174000"));
+        assert!(!c.matches("123e4567e89b12d3a456426614174000"));
+
+        assert!(RouteParamConstraint::parse("nope").is_err());
+    }
+
+    #[test]
+    fn test_constraint_one_of() {
+        let c = RouteParamConstraint::parse("(info|posts)").unwrap();
+        assert!(c.matches("info"));
+        assert!(c.matches("posts"));
+        assert!(!c.matches("settings"));
+        assert!(RouteParamConstraint::parse("(info|)").is_err());
+    }
+
+    #[test]
+    fn test_constraint_char_class() {
+        let c = RouteParamConstraint::parse("[a-z-]+").unwrap();
+        assert!(c.matches("my-post"));
+        assert!(!c.matches("My-Post"));
+
+        let c = RouteParamConstraint::parse("[0-9]{4}").unwrap();
+        assert!(c.matches("2024"));
+        assert!(!c.matches("202"));
+
+        let c = RouteParamConstraint::parse("[^.]+").unwrap();
+        assert!(c.matches("readme"));
+        assert!(!c.matches("readme.md"));
+
+        let c = RouteParamConstraint::parse("[a-z\\-]{2,8}").unwrap();
+        assert_eq!(c.to_string(), "[a-z\\-]{2,8}");
+
+        assert!(RouteParamConstraint::parse("[a-z").is_err());
+        assert!(RouteParamConstraint::parse("[a-z]{3,1}").is_err());
+    }
+}
@@ -0,0 +1,280 @@
+//! Error types for pattern parsing, route registration, path navigation, URL building and typed
+//! route arguments.
+
+use crate::registry::RouteDiagnostic;
+use makepad_live_id::LiveId;
+use std::fmt;
+
+/// Error produced while parsing a route pattern.
+///
+/// `position` is the byte offset in the source pattern where the problem was found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatternError {
+    /// The pattern is empty (or only whitespace).
+    Empty,
+    /// A dynamic segment has no parameter name (`/:` or `/:?`).
+    EmptyParamName { position: usize },
+    /// A dynamic segment has an empty default value (`/:page=`).
+    EmptyDefault { position: usize },
+    /// A multi-segment wildcard (`**`) is followed by other segments.
+    MultiWildcardNotLast { position: usize },
+    /// A required segment follows an optional one (`/:page?/edit`).
+    RequiredAfterOptional { position: usize },
+    /// A constraint is missing its closing `>` (`/:id<u64`).
+    UnterminatedConstraint { position: usize },
+    /// A constraint could not be parsed (`/:id<unknown>`).
+    InvalidConstraint { position: usize, reason: String },
+    /// A default value is rejected by the segment's constraint (`/:page<u64>=first`).
+    DefaultViolatesConstraint {
+        position: usize,
+        name: String,
+        default: String,
+    },
+}
+
+impl PatternError {
+    /// Byte offset in the source pattern, if the error refers to a specific location.
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            Self::Empty => None,
+            Self::EmptyParamName { position }
+            | Self::EmptyDefault { position }
+            | Self::MultiWildcardNotLast { position }
+            | Self::RequiredAfterOptional { position }
+            | Self::UnterminatedConstraint { position }
+            | Self::InvalidConstraint { position, .. }
+            | Self::DefaultViolatesConstraint { position, .. } => Some(*position),
+        }
+    }
+
+    /// Shift the position by `offset` (used when a sub-parser reports relative positions).
+    pub(crate) fn offset_by(mut self, offset: usize) -> Self {
+        match &mut self {
+            Self::Empty => {}
+            Self::EmptyParamName { position }
+            | Self::EmptyDefault { position }
+            | Self::MultiWildcardNotLast { position }
+            | Self::RequiredAfterOptional { position }
+            | Self::UnterminatedConstraint { position }
+            | Self::InvalidConstraint { position, .. }
+            | Self::DefaultViolatesConstraint { position, .. } => *position += offset,
+        }
+        self
+    }
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => return write!(f, "Pattern cannot be empty"),
+            Self::EmptyParamName { .. } => {
+                write!(f, "Dynamic segment parameter name cannot be empty")?
+            }
+            Self::EmptyDefault { .. } => {
+                write!(f, "Dynamic segment default value cannot be empty")?
+            }
+            Self::MultiWildcardNotLast { .. } => {
+                write!(f, "Multi-segment wildcard (**) must be the last segment")?
+            }
+            Self::RequiredAfterOptional { .. } => {
+                write!(f, "Required segment cannot follow an optional segment")?
+            }
+            Self::UnterminatedConstraint { .. } => write!(f, "Unterminated constraint")?,
+            Self::InvalidConstraint { reason, .. } => write!(f, "{}", reason)?,
+            Self::DefaultViolatesConstraint { name, default, .. } => write!(
+                f,
+                "Default value '{}' does not satisfy the constraint of ':{}'",
+                default, name
+            )?,
+        }
+        if let Some(position) = self.position() {
+            write!(f, " (at position {})", position)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+/// Error produced when registering a route pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegistryError {
+    /// The pattern failed to parse.
+    Pattern(PatternError),
+    /// Strict mode rejected a conflicting registration.
+    Conflict(RouteDiagnostic),
+    /// The pattern does not capture the params of a typed `RouteArgs` registration.
+    Args(RouteArgsError),
+    /// A redirect target needs a param the source pattern does not capture.
+    Redirect(UrlForError),
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pattern(err) => write!(f, "{}", err),
+            Self::Conflict(diagnostic) => write!(f, "{}", diagnostic),
+            Self::Args(err) => write!(f, "{}", err),
+            Self::Redirect(err) => write!(f, "Invalid redirect target: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Pattern(err) => Some(err),
+            Self::Args(err) => Some(err),
+            Self::Redirect(err) => Some(err),
+            Self::Conflict(_) => None,
+        }
+    }
+}
+
+impl From<PatternError> for RegistryError {
+    fn from(err: PatternError) -> Self {
+        Self::Pattern(err)
+    }
+}
+
+/// Error produced when navigating by path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NavigateError {
+    /// No registered pattern matches the path.
+    NoRouteForPath { path: String },
+    /// Redirects starting at the path loop or chain more than `ROUTE_MAX_REDIRECTS` times.
+    RedirectLoop { path: String },
+}
+
+impl fmt::Display for NavigateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoRouteForPath { path } => write!(f, "No route found for path: {}", path),
+            Self::RedirectLoop { path } => write!(f, "Redirect loop for path: {}", path),
+        }
+    }
+}
+
+impl std::error::Error for NavigateError {}
+
+/// Error produced when building a URL for a route (reverse routing).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UrlForError {
+    /// The route id is not registered with a pattern (in this router or any nested router).
+    UnknownRoute { route_id: LiveId },
+    /// A required param (`:id`, `*file`) has no value.
+    MissingParam { name: String },
+    /// A later segment has a value while the optional segment `name` before it has none.
+    OptionalGap { name: String },
+    /// The pattern contains an unnamed wildcard (`*`, `**`), which has no value to format.
+    UnnamedWildcard,
+}
+
+impl fmt::Display for UrlForError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownRoute { route_id } => {
+                write!(f, "No pattern registered for route {}", route_id)
+            }
+            Self::MissingParam { name } => write!(f, "Missing value for param ':{}'", name),
+            Self::OptionalGap { name } => write!(
+                f,
+                "Optional param ':{}' has no value but a later segment does",
+                name
+            ),
+            Self::UnnamedWildcard => write!(f, "Pattern has an unnamed wildcard"),
+        }
+    }
+}
+
+impl std::error::Error for UrlForError {}
+
+/// Error produced when reading typed route arguments (`RouteArgs`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RouteArgsError {
+    /// A required path param has no value.
+    MissingParam { name: String },
+    /// A path param value could not be parsed as the field type.
+    InvalidParam { name: String, value: String },
+    /// A required query value is absent.
+    MissingQuery { key: String },
+    /// A query value could not be parsed as the field type.
+    InvalidQuery { key: String, value: String },
+    /// The pattern has no segment for a param read by the args type.
+    UnknownParam { name: String },
+    /// A required field maps to a segment that may be absent from the path.
+    OptionalParam { name: String },
+}
+
+impl fmt::Display for RouteArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingParam { name } => write!(f, "Missing value for param ':{}'", name),
+            Self::InvalidParam { name, value } => {
+                write!(f, "Invalid value '{}' for param ':{}'", value, name)
+            }
+            Self::MissingQuery { key } => write!(f, "Missing query value '{}'", key),
+            Self::InvalidQuery { key, value } => {
+                write!(f, "Invalid value '{}' for query '{}'", value, key)
+            }
+            Self::UnknownParam { name } => write!(f, "Pattern has no param ':{}'", name),
+            Self::OptionalParam { name } => write!(
+                f,
+                "Param ':{}' is optional in the pattern but required by the args type",
+                name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RouteArgsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::RoutePattern;
+
+    #[test]
+    fn test_pattern_error_positions() {
+        assert_eq!(RoutePattern::parse("  "), Err(PatternError::Empty));
+        assert_eq!(
+            RoutePattern::parse("/docs/**/edit"),
+            Err(PatternError::MultiWildcardNotLast { position: 6 })
+        );
+        assert_eq!(
+            RoutePattern::parse("/posts/:page?/edit"),
+            Err(PatternError::RequiredAfterOptional { position: 14 })
+        );
+        assert_eq!(
+            RoutePattern::parse("/user/:"),
+            Err(PatternError::EmptyParamName { position: 6 })
+        );
+        assert_eq!(
+            RoutePattern::parse("/posts/:page="),
+            Err(PatternError::EmptyDefault { position: 12 })
+        );
+        assert_eq!(
+            RoutePattern::parse("/user/:id<u64"),
+            Err(PatternError::UnterminatedConstraint { position: 9 })
+        );
+
+        let err = RoutePattern::parse("/user/:id<nope>").unwrap_err();
+        assert!(matches!(
+            err,
+            PatternError::InvalidConstraint { position: 10, .. }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Unknown constraint <nope> (at position 10)"
+        );
+    }
+
+    #[test]
+    fn test_registry_error_source() {
+        use std::error::Error;
+
+        let err = RegistryError::from(PatternError::Empty);
+        assert_eq!(err.to_string(), "Pattern cannot be empty");
+        assert!(err.source().is_some());
+    }
+}
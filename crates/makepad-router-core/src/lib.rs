@@ -1,6 +1,10 @@
 pub use makepad_live_id;
 pub use makepad_micro_serde;
 
+pub mod args;
+pub mod constraint;
+pub mod error;
+pub mod meta;
 pub mod navigation;
 pub mod pattern;
 pub mod registry;
@@ -9,18 +13,39 @@ pub mod router;
 pub mod state;
 pub mod url;
 
-pub use crate::navigation::NavigationHistory;
-pub use crate::pattern::{RouteParams, RoutePattern, RoutePatternRef, RouteSegment};
-pub use crate::registry::RouteRegistry;
+pub use crate::args::{RouteArgParam, RouteArgValue, RouteArgs};
+pub use crate::constraint::RouteParamConstraint;
+pub use crate::error::{NavigateError, PatternError, RegistryError, RouteArgsError, UrlForError};
+pub use crate::meta::{RouteMeta, RouteMetaValue};
+pub use crate::navigation::{
+    HistoryEviction, HistoryLimit, HistoryStack, HistoryStacks, NavigationHistory,
+};
+pub use crate::pattern::{
+    RouteParamValue, RouteParams, RoutePattern, RoutePatternRef, RouteSegment,
+};
+pub use crate::registry::{RouteDiagnostic, RouteDiagnosticKind, RouteRegistry};
 pub use crate::route::{Route, RouteQuery};
 pub use crate::router::{Router, RouterAction};
 pub use crate::state::RouterState;
-pub use crate::url::{build_query_string, parse_query_map, RouterUrl};
+pub use crate::url::{
+    build_query_string, build_query_string_sorted, parse_query_map, DuplicateSlashes, MatchPolicy,
+    RouterUrl, TrailingSlash,
+};
+// The derive macro shares its name with the trait (like serde's `Serialize`).
+#[cfg(feature = "derive")]
+pub use makepad_router_derive::RouteArgs;
+
+#[doc(hidden)]
+pub use crate::args::__private;
 
 pub mod prelude {
+    pub use crate::args::RouteArgs;
+    pub use crate::meta::RouteMeta;
     pub use crate::pattern::{RouteParams, RoutePattern, RoutePatternRef, RouteSegment};
     pub use crate::route::{Route, RouteQuery};
     pub use crate::router::{Router, RouterAction};
     pub use crate::state::RouterState;
     pub use crate::url::RouterUrl;
+    #[cfg(feature = "derive")]
+    pub use makepad_router_derive::RouteArgs;
 }
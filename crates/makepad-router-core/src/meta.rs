@@ -0,0 +1,230 @@
+//! Per-route metadata (title, icon, required role, ...) declared next to a route's pattern.
+//!
+//! Metadata is keyed by `LiveId` and stored in the [`RouteRegistry`](crate::registry::RouteRegistry)
+//! by route id, so guards and app code can read it instead of hardcoding lists of route ids.
+
+use makepad_live_id::*;
+use std::sync::Arc;
+
+/// A metadata value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RouteMetaValue {
+    Bool(bool),
+    Number(f64),
+    Id(LiveId),
+    Str(Arc<str>),
+    List(Vec<RouteMetaValue>),
+}
+
+impl RouteMetaValue {
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[RouteMetaValue]> {
+        match self {
+            Self::List(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// The value as a `LiveId` (string values are hashed, not interned).
+    pub fn as_live_id(&self) -> Option<LiveId> {
+        match self {
+            Self::Id(id) => Some(*id),
+            Self::Str(value) => Some(LiveId::from_str(value)),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for RouteMetaValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<f64> for RouteMetaValue {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<LiveId> for RouteMetaValue {
+    fn from(id: LiveId) -> Self {
+        Self::Id(id)
+    }
+}
+
+impl From<&str> for RouteMetaValue {
+    fn from(value: &str) -> Self {
+        Self::Str(value.into())
+    }
+}
+
+impl From<String> for RouteMetaValue {
+    fn from(value: String) -> Self {
+        Self::Str(value.into())
+    }
+}
+
+impl<T: Into<RouteMetaValue>> From<Vec<T>> for RouteMetaValue {
+    fn from(values: Vec<T>) -> Self {
+        Self::List(values.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Metadata attached to a route. Cloning is cheap (entries are shared).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RouteMeta {
+    entries: Arc<Vec<(LiveId, RouteMetaValue)>>,
+}
+
+impl RouteMeta {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder-style `insert`.
+    pub fn with(mut self, key: LiveId, value: impl Into<RouteMetaValue>) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    /// Set `key`, replacing any previous value.
+    pub fn insert(&mut self, key: LiveId, value: impl Into<RouteMetaValue>) {
+        let value = value.into();
+        let entries = Arc::make_mut(&mut self.entries);
+        match entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, slot)) => *slot = value,
+            None => entries.push((key, value)),
+        }
+    }
+
+    pub fn remove(&mut self, key: LiveId) -> Option<RouteMetaValue> {
+        let pos = self.entries.iter().position(|(k, _)| *k == key)?;
+        Some(Arc::make_mut(&mut self.entries).remove(pos).1)
+    }
+
+    pub fn get(&self, key: LiveId) -> Option<&RouteMetaValue> {
+        self.entries.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    pub fn contains(&self, key: LiveId) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn get_bool(&self, key: LiveId) -> Option<bool> {
+        self.get(key)?.as_bool()
+    }
+
+    /// `true` only if `key` is set to `true` (handy for flags like `requires_auth`).
+    pub fn flag(&self, key: LiveId) -> bool {
+        self.get_bool(key).unwrap_or(false)
+    }
+
+    pub fn get_f64(&self, key: LiveId) -> Option<f64> {
+        self.get(key)?.as_f64()
+    }
+
+    pub fn get_str(&self, key: LiveId) -> Option<&str> {
+        self.get(key)?.as_str()
+    }
+
+    pub fn get_id(&self, key: LiveId) -> Option<LiveId> {
+        self.get(key)?.as_live_id()
+    }
+
+    /// Strings listed under `key`: the string items of a list, or a comma separated string.
+    pub fn get_strs(&self, key: LiveId) -> Vec<&str> {
+        match self.get(key) {
+            Some(RouteMetaValue::Str(value)) => value
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .collect(),
+            Some(RouteMetaValue::List(values)) => {
+                values.iter().filter_map(RouteMetaValue::as_str).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Entries in declaration order.
+    pub fn iter(&self) -> impl Iterator<Item = (LiveId, &RouteMetaValue)> {
+        self.entries.iter().map(|(k, v)| (*k, v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_meta_values() {
+        let mut meta = RouteMeta::new()
+            .with(live_id!(title), "Settings")
+            .with(live_id!(requires_auth), true)
+            .with(live_id!(order), 2.0)
+            .with(live_id!(role), live_id!(admin));
+
+        assert_eq!(meta.get_str(live_id!(title)), Some("Settings"));
+        assert!(meta.flag(live_id!(requires_auth)));
+        assert!(!meta.flag(live_id!(title)));
+        assert_eq!(meta.get_f64(live_id!(order)), Some(2.0));
+        assert_eq!(meta.get_id(live_id!(role)), Some(live_id!(admin)));
+        assert_eq!(
+            meta.get_id(live_id!(title)),
+            Some(LiveId::from_str("Settings"))
+        );
+
+        let shared = meta.clone();
+        meta.insert(live_id!(title), "Preferences");
+        assert_eq!(meta.len(), 4);
+        assert_eq!(meta.get_str(live_id!(title)), Some("Preferences"));
+        assert_eq!(shared.get_str(live_id!(title)), Some("Settings"));
+
+        assert_eq!(
+            meta.remove(live_id!(order)),
+            Some(RouteMetaValue::Number(2.0))
+        );
+        assert!(!meta.contains(live_id!(order)));
+        meta.insert(live_id!(requires), vec!["admin", "editor"]);
+        assert_eq!(meta.get_strs(live_id!(requires)), ["admin", "editor"]);
+        meta.insert(live_id!(requires), "admin, editor");
+        assert_eq!(meta.get_strs(live_id!(requires)), ["admin", "editor"]);
+        assert_eq!(meta.get_strs(live_id!(title)), ["Preferences"]);
+        meta.remove(live_id!(requires));
+
+        let keys: Vec<LiveId> = meta.iter().map(|(k, _)| k).collect();
+        assert_eq!(
+            keys,
+            [live_id!(title), live_id!(requires_auth), live_id!(role)]
+        );
+    }
+}
@@ -3,6 +3,47 @@ use makepad_live_id::*;
 use makepad_micro_serde::*;
 use std::collections::HashMap;
 
+/// Which entries a bounded [`NavigationHistory`] evicts once it grows past its limit.
+///
+/// The current entry is never evicted; if it sits too far back, forward entries are dropped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum HistoryEviction {
+    /// Drop the oldest entries.
+    #[default]
+    DropOldest,
+    /// Collapse runs of consecutive entries for the same route id (keeping the newest of each
+    /// run), then drop the oldest entries if still over the limit.
+    CollapseDuplicates,
+    /// Drop the oldest entries after the root, which stays pinned at index 0.
+    KeepRoot,
+}
+
+/// Maximum history depth and how to get back under it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HistoryLimit {
+    /// Maximum number of entries (`0` = unbounded).
+    pub max_depth: usize,
+    pub eviction: HistoryEviction,
+}
+
+impl HistoryLimit {
+    pub fn new(max_depth: usize, eviction: HistoryEviction) -> Self {
+        Self {
+            max_depth,
+            eviction,
+        }
+    }
+
+    /// No limit (the default).
+    pub fn unbounded() -> Self {
+        Self::default()
+    }
+
+    pub fn is_bounded(&self) -> bool {
+        self.max_depth > 0
+    }
+}
+
 /// Navigation history stack for managing route navigation
 #[derive(Clone, Debug, Default)]
 pub struct NavigationHistory {
@@ -12,6 +53,9 @@ pub struct NavigationHistory {
     current_index: usize,
     /// Reverse index for stack-style operations (not serialized).
     index: HashMap<LiveId, Vec<usize>>,
+    /// Depth limit. Configuration rather than state, so it is not serialized: deserialized and
+    /// `from_parts` histories are unbounded until `set_limit` is called again.
+    limit: HistoryLimit,
 }
 
 impl NavigationHistory {
@@ -21,6 +65,7 @@ impl NavigationHistory {
             stack: vec![initial_route],
             current_index: 0,
             index: HashMap::new(),
+            limit: HistoryLimit::default(),
         };
         out.rebuild_index();
         out
@@ -32,9 +77,20 @@ impl NavigationHistory {
             stack: Vec::new(),
             current_index: 0,
             index: HashMap::new(),
+            limit: HistoryLimit::default(),
         }
     }
 
+    /// Bound the history depth, evicting entries right away if it is already deeper.
+    pub fn set_limit(&mut self, limit: HistoryLimit) {
+        self.limit = limit;
+        self.enforce_limit();
+    }
+
+    pub fn limit(&self) -> HistoryLimit {
+        self.limit
+    }
+
     /// Get the current route
     pub fn current(&self) -> Option<&Route> {
         self.stack.get(self.current_index)
@@ -48,6 +104,7 @@ impl NavigationHistory {
         self.stack.push(route);
         self.current_index = self.stack.len() - 1;
         self.index_entry(self.current_index);
+        self.enforce_limit();
     }
 
     /// Replace the current route without adding to history
@@ -177,6 +234,8 @@ impl NavigationHistory {
     }
 
     /// Restore history from stack + current index.
+    ///
+    /// The result is unbounded; re-apply the depth limit with `set_limit`.
     pub fn from_parts(stack: Vec<Route>, current_index: usize) -> Self {
         if stack.is_empty() {
             return Self::empty();
@@ -186,6 +245,7 @@ impl NavigationHistory {
             stack,
             current_index,
             index: HashMap::new(),
+            limit: HistoryLimit::default(),
         };
         out.rebuild_index();
         out
@@ -205,6 +265,7 @@ impl NavigationHistory {
         self.stack = stack;
         self.current_index = self.stack.len() - 1;
         self.rebuild_index();
+        self.enforce_limit();
     }
 
     /// Pops the current route (stack-style semantics).
@@ -249,6 +310,49 @@ impl NavigationHistory {
         true
     }
 
+    fn enforce_limit(&mut self) {
+        let max = self.limit.max_depth;
+        if max == 0 || self.stack.len() <= max {
+            return;
+        }
+        if self.limit.eviction == HistoryEviction::CollapseDuplicates {
+            self.collapse_duplicates();
+        }
+        let pinned = usize::from(self.limit.eviction == HistoryEviction::KeepRoot && max >= 2);
+        while self.stack.len() > max && self.current_index > pinned {
+            self.evict_entry(pinned);
+        }
+        if self.stack.len() > max {
+            // The current entry sits too far back: drop forward history instead.
+            let new_len = max.max(self.current_index + 1);
+            self.stack.truncate(new_len);
+            self.rebuild_index_for_truncate(new_len);
+        }
+    }
+
+    /// Drop all but the newest entry of each run of the same route id (the current entry stays).
+    fn collapse_duplicates(&mut self) {
+        for i in (0..self.stack.len().saturating_sub(1)).rev() {
+            if i != self.current_index && self.stack[i + 1].id == self.stack[i].id {
+                self.evict_entry(i);
+            }
+        }
+    }
+
+    /// Remove entry `idx`, shifting the index and the current position past it.
+    fn evict_entry(&mut self, idx: usize) {
+        self.unindex_entry(idx);
+        self.stack.remove(idx);
+        for list in self.index.values_mut() {
+            for i in list.iter_mut().filter(|i| **i > idx) {
+                *i -= 1;
+            }
+        }
+        if self.current_index > idx {
+            self.current_index -= 1;
+        }
+    }
+
     fn rebuild_index(&mut self) {
         self.index.clear();
         for (i, r) in self.stack.iter().enumerate() {
@@ -299,6 +403,87 @@ impl NavigationHistory {
     }
 }
 
+/// A named history stack that is not currently active (e.g. a background tab).
+#[derive(Clone, Debug, PartialEq, Eq, SerBin, DeBin, SerRon, DeRon)]
+pub struct HistoryStack {
+    pub id: LiveId,
+    pub history: NavigationHistory,
+}
+
+/// Named history stacks: the id of the active one plus the inactive ones.
+///
+/// The active stack's entries live in `Router::history`; switching swaps them, so each stack
+/// keeps its own back/forward position.
+#[derive(Clone, Debug, Default, PartialEq, Eq, SerBin, DeBin, SerRon, DeRon)]
+pub struct HistoryStacks {
+    /// Id of the active stack (`LiveId(0)` for the initial, unnamed one).
+    pub active: LiveId,
+    pub inactive: Vec<HistoryStack>,
+}
+
+impl HistoryStacks {
+    /// Only the initial stack exists.
+    pub fn is_single(&self) -> bool {
+        self.active.0 == 0 && self.inactive.is_empty()
+    }
+
+    pub fn contains(&self, stack_id: LiveId) -> bool {
+        self.active == stack_id || self.position(stack_id).is_some()
+    }
+
+    /// An inactive stack's history.
+    pub fn get(&self, stack_id: LiveId) -> Option<&NavigationHistory> {
+        let pos = self.position(stack_id)?;
+        Some(&self.inactive[pos].history)
+    }
+
+    /// Ids of all stacks, active first.
+    pub fn ids(&self) -> Vec<LiveId> {
+        std::iter::once(self.active)
+            .chain(self.inactive.iter().map(|stack| stack.id))
+            .collect()
+    }
+
+    fn position(&self, stack_id: LiveId) -> Option<usize> {
+        self.inactive.iter().position(|stack| stack.id == stack_id)
+    }
+
+    /// Add an inactive stack; `false` if a stack with that id exists.
+    pub fn insert(&mut self, stack_id: LiveId, history: NavigationHistory) -> bool {
+        if self.contains(stack_id) {
+            return false;
+        }
+        self.inactive.push(HistoryStack {
+            id: stack_id,
+            history,
+        });
+        true
+    }
+
+    /// Remove an inactive stack (the active one cannot be removed).
+    pub fn remove(&mut self, stack_id: LiveId) -> Option<NavigationHistory> {
+        let pos = self.position(stack_id)?;
+        Some(self.inactive.remove(pos).history)
+    }
+
+    /// Make `stack_id` active, swapping its history with `active_history`.
+    ///
+    /// Returns `false` if the stack is unknown or already active.
+    pub fn switch(&mut self, stack_id: LiveId, active_history: &mut NavigationHistory) -> bool {
+        let Some(pos) = self.position(stack_id) else {
+            return false;
+        };
+        let stack = &mut self.inactive[pos];
+        std::mem::swap(&mut stack.history, active_history);
+        stack.id = std::mem::replace(&mut self.active, stack_id);
+        true
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut HistoryStack> {
+        self.inactive.iter_mut()
+    }
+}
+
 impl PartialEq for NavigationHistory {
     fn eq(&self, other: &Self) -> bool {
         self.stack == other.stack && self.current_index == other.current_index
@@ -432,6 +617,120 @@ mod tests {
         assert_eq!(history.depth(), 1);
     }
 
+    #[test]
+    fn test_history_limit_drop_oldest() {
+        let mut history = NavigationHistory::new(Route::new(live_id!(home)));
+        history.set_limit(HistoryLimit::new(3, HistoryEviction::DropOldest));
+        for id in [live_id!(a), live_id!(b), live_id!(c), live_id!(d)] {
+            history.push(Route::new(id));
+        }
+        let ids: Vec<LiveId> = history.all_routes().iter().map(|r| r.id).collect();
+        assert_eq!(ids, [live_id!(b), live_id!(c), live_id!(d)]);
+        assert_eq!(history.current_index(), 2);
+        assert_eq!(history.preview_pop_to(live_id!(b)).unwrap().id, live_id!(b));
+        assert!(history.preview_pop_to(live_id!(a)).is_none());
+
+        // The current entry survives even when it is the oldest one.
+        assert!(history.back());
+        assert!(history.back());
+        history.set_limit(HistoryLimit::new(2, HistoryEviction::DropOldest));
+        let ids: Vec<LiveId> = history.all_routes().iter().map(|r| r.id).collect();
+        assert_eq!(ids, [live_id!(b), live_id!(c)]);
+        assert_eq!(history.current().unwrap().id, live_id!(b));
+    }
+
+    #[test]
+    fn test_history_limit_keep_root() {
+        let mut history = NavigationHistory::new(Route::new(live_id!(home)));
+        history.set_limit(HistoryLimit::new(3, HistoryEviction::KeepRoot));
+        for id in [live_id!(a), live_id!(b), live_id!(c)] {
+            history.push(Route::new(id));
+        }
+        let ids: Vec<LiveId> = history.all_routes().iter().map(|r| r.id).collect();
+        assert_eq!(ids, [live_id!(home), live_id!(b), live_id!(c)]);
+        assert!(history.pop_to(live_id!(home)));
+        assert_eq!(history.depth(), 1);
+    }
+
+    #[test]
+    fn test_history_limit_collapse_duplicates() {
+        let mut history = NavigationHistory::new(Route::new(live_id!(home)));
+        for id in [
+            live_id!(detail),
+            live_id!(detail),
+            live_id!(detail),
+            live_id!(a),
+        ] {
+            history.push(Route::new(id));
+        }
+        history.set_limit(HistoryLimit::new(4, HistoryEviction::CollapseDuplicates));
+        let ids: Vec<LiveId> = history.all_routes().iter().map(|r| r.id).collect();
+        assert_eq!(ids, [live_id!(home), live_id!(detail), live_id!(a)]);
+        assert_eq!(history.current_index(), 2);
+        assert_eq!(
+            history.preview_pop_to(live_id!(detail)).unwrap().id,
+            live_id!(detail)
+        );
+
+        // Still over the limit after collapsing: fall back to dropping the oldest.
+        history.set_limit(HistoryLimit::new(2, HistoryEviction::CollapseDuplicates));
+        let ids: Vec<LiveId> = history.all_routes().iter().map(|r| r.id).collect();
+        assert_eq!(ids, [live_id!(detail), live_id!(a)]);
+        assert!(history.preview_pop_to(live_id!(home)).is_none());
+    }
+
+    #[test]
+    fn test_history_limit_keeps_index_in_sync() {
+        let ids = [
+            live_id!(a),
+            live_id!(b),
+            live_id!(a),
+            live_id!(a),
+            live_id!(c),
+        ];
+        for eviction in [
+            HistoryEviction::DropOldest,
+            HistoryEviction::KeepRoot,
+            HistoryEviction::CollapseDuplicates,
+        ] {
+            let mut history = NavigationHistory::new(Route::new(live_id!(home)));
+            history.set_limit(HistoryLimit::new(3, eviction));
+            for id in ids.iter().cycle().take(20) {
+                history.push(Route::new(*id));
+                let mut rebuilt = history.clone();
+                rebuilt.rebuild_index();
+                assert_eq!(history.index, rebuilt.index, "{:?}", eviction);
+                assert!(history.depth() <= 3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_history_stacks_switch() {
+        let mut active = NavigationHistory::new(Route::new(live_id!(home)));
+        active.push(Route::new(live_id!(detail)));
+        let mut stacks = HistoryStacks::default();
+        assert!(stacks.is_single());
+        assert!(stacks.insert(
+            live_id!(search),
+            NavigationHistory::new(Route::new(live_id!(search)))
+        ));
+        assert!(!stacks.insert(live_id!(search), NavigationHistory::empty()));
+
+        assert!(stacks.switch(live_id!(search), &mut active));
+        assert_eq!(stacks.active, live_id!(search));
+        assert_eq!(active.current().unwrap().id, live_id!(search));
+        assert_eq!(stacks.get(LiveId(0)).unwrap().depth(), 2);
+        assert!(!stacks.switch(live_id!(search), &mut active));
+
+        assert!(stacks.switch(LiveId(0), &mut active));
+        assert_eq!(active.current().unwrap().id, live_id!(detail));
+        assert_eq!(stacks.ids(), [LiveId(0), live_id!(search)]);
+        assert!(stacks.remove(LiveId(0)).is_none());
+        assert!(stacks.remove(live_id!(search)).is_some());
+        assert!(stacks.is_single());
+    }
+
     #[test]
     fn test_stack_set_stack() {
         let mut history = NavigationHistory::empty();
@@ -3,35 +3,34 @@
 //! This module provides types for defining and matching URL patterns with support for:
 //! - Static segments (e.g., `/user/profile`)
 //! - Dynamic segments (e.g., `/user/:id`)
-//! - Single-segment wildcards (e.g., `/admin/*`)
-//! - Multi-segment wildcards (e.g., `/admin/**`)
+//! - Optional dynamic segments (e.g., `/posts/:page?`) and defaults (e.g., `/posts/:page=1`)
+//! - Typed constraints on dynamic segments (e.g., `/user/:id<u64>`, `/:tab<(info|posts)>`)
+//! - Single-segment wildcards (e.g., `/admin/*`, or named: `/files/*file`)
+//! - Multi-segment wildcards (e.g., `/admin/**`, or named: `/docs/**rest`)
+//!
+//! Named wildcards store the matched text in `RouteParams` under their name, so a `/docs/**rest`
+//! route can read `rest = "guide/intro"` without re-parsing the path. A `**` value is decoded
+//! segment by segment, keeping `%2F` and `%25` escaped so `/` always separates segments.
 
+use crate::constraint::RouteParamConstraint;
+use crate::error::{PatternError, UrlForError};
+use crate::url::{self, MatchPolicy};
 use makepad_live_id::*;
 use makepad_micro_serde::*;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::fmt;
 use std::ops::Deref;
 use std::sync::Arc;
 
 fn next_non_empty_segment<'a>(segments: &mut std::str::Split<'a, char>) -> Option<&'a str> {
-    while let Some(seg) = segments.next() {
-        if !seg.is_empty() {
-            return Some(seg);
-        }
-    }
-    None
+    segments.find(|seg| !seg.is_empty())
 }
 
-fn collect_tail(
-    first: Option<&str>,
-    segments: &mut std::str::Split<'_, char>,
-) -> String {
+fn collect_tail(first: Option<&str>, segments: &mut std::str::Split<'_, char>) -> String {
     let mut out = String::new();
     let push_segment = |segment: &str, out: &mut String| {
-        if out.is_empty() {
-            out.push('/');
-        } else {
-            out.push('/');
-        }
+        out.push('/');
         out.push_str(segment);
     };
 
@@ -48,17 +47,311 @@ fn collect_tail(
     out
 }
 
+/// Percent-encode a param value for use as a path segment.
+fn encode_segment(value: &str) -> String {
+    url::encode_path_segment(value).into_owned()
+}
+
+/// Value of a `**name` capture: the matched segments, each decoded, joined with `/`.
+fn decode_tail(rest: &str) -> String {
+    let segments: Vec<Cow<'_, str>> = rest
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(url::decode_tail_segment)
+        .collect();
+    segments.join("/")
+}
+
+/// Parse the optional capture name following a wildcard token (`*file`, `**rest`).
+fn parse_wildcard_name(name: &str) -> (Option<String>, Option<LiveId>) {
+    if name.is_empty() {
+        (None, None)
+    } else {
+        (Some(name.to_string()), Some(LiveId::from_str(name)))
+    }
+}
+
+/// Parse the body of a dynamic segment (`id`, `page?`, `page=1`, `id<u64>`) after the leading `:`.
+///
+/// `offset` is the position of `body` in the source pattern, used for error positions.
+/// A constraint ends at the first `>` that is followed by the end of the body, `?` or `=`.
+fn parse_dynamic_segment(body: &str, offset: usize) -> Result<RouteSegment, PatternError> {
+    let default_at = body.find('=');
+    let (name_end, constraint, suffix_start) = match body.find('<') {
+        Some(open) if default_at.is_none_or(|eq| open < eq) => {
+            let close = body[open..]
+                .match_indices('>')
+                .map(|(i, _)| open + i)
+                .find(|&i| {
+                    let rest = &body[i + 1..];
+                    rest.is_empty() || rest == "?" || rest.starts_with('=')
+                })
+                .ok_or(PatternError::UnterminatedConstraint {
+                    position: offset + open,
+                })?;
+            let constraint = RouteParamConstraint::parse(&body[open + 1..close])
+                .map_err(|err| err.offset_by(offset + open + 1))?;
+            (open, Some(constraint), close + 1)
+        }
+        _ => {
+            let end = default_at
+                .or_else(|| body.strip_suffix('?').map(str::len))
+                .unwrap_or(body.len());
+            (end, None, end)
+        }
+    };
+
+    let name = &body[..name_end];
+    let suffix = &body[suffix_start..];
+    let (optional, default) = match suffix.strip_prefix('=') {
+        Some("") => {
+            return Err(PatternError::EmptyDefault {
+                position: offset + suffix_start,
+            })
+        }
+        Some(default) => (true, Some(default.to_string())),
+        None => (suffix == "?", None),
+    };
+    if name.is_empty() {
+        return Err(PatternError::EmptyParamName {
+            position: offset.saturating_sub(1),
+        });
+    }
+    if let (Some(constraint), Some(default)) = (&constraint, &default) {
+        if !constraint.matches(default) {
+            return Err(PatternError::DefaultViolatesConstraint {
+                position: offset + suffix_start + 1,
+                name: name.to_string(),
+                default: default.clone(),
+            });
+        }
+    }
+    Ok(RouteSegment::Dynamic {
+        name: name.to_string(),
+        key: LiveId::from_str(name),
+        optional,
+        default,
+        constraint,
+    })
+}
+
 /// Represents a route segment type in a pattern
-#[derive(Clone, Debug, PartialEq, Eq, Hash, SerBin, DeBin, SerRon, DeRon)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, SerRon)]
 pub enum RouteSegment {
     /// Static segment (e.g., "user", "profile")
     Static(String),
-    /// Dynamic segment with parameter name (e.g., ":id", ":postId")
-    Dynamic { name: String, key: LiveId },
-    /// Single-segment wildcard
-    WildcardSingle,
-    /// Multi-segment wildcard
-    WildcardMulti,
+    /// Dynamic segment with parameter name (e.g., ":id", ":postId").
+    ///
+    /// Optional segments (":page?") may be absent from the path; a default (":page=1") implies
+    /// optional and is stored in the params when the segment is absent.
+    /// A constraint (":id<u64>") must accept the segment value for the pattern to match.
+    Dynamic {
+        name: String,
+        key: LiveId,
+        optional: bool,
+        default: Option<String>,
+        constraint: Option<RouteParamConstraint>,
+    },
+    /// Single-segment wildcard (e.g., "*", or "*file" to capture the segment as a param)
+    WildcardSingle {
+        name: Option<String>,
+        key: Option<LiveId>,
+    },
+    /// Multi-segment wildcard (e.g., "**", or "**rest" to capture the remaining segments as a param)
+    WildcardMulti {
+        name: Option<String>,
+        key: Option<LiveId>,
+    },
+}
+
+impl RouteSegment {
+    /// Whether this segment may be absent from a matching path.
+    pub fn is_optional(&self) -> bool {
+        matches!(
+            self,
+            RouteSegment::Dynamic { optional: true, .. } | RouteSegment::WildcardMulti { .. }
+        )
+    }
+
+    /// Whether this segment is a single- or multi-segment wildcard.
+    pub fn is_wildcard(&self) -> bool {
+        matches!(
+            self,
+            RouteSegment::WildcardSingle { .. } | RouteSegment::WildcardMulti { .. }
+        )
+    }
+}
+
+/// Binary layout of `RouteSegment`. The `Legacy*` variants are the shapes written before
+/// wildcards could be named and params made optional, defaulted or constrained, so persisted
+/// patterns from then still load.
+#[allow(clippy::question_mark)]
+#[derive(SerBin, DeBin)]
+enum RouteSegmentBin {
+    Static(String),
+    LegacyDynamic {
+        name: String,
+        key: LiveId,
+    },
+    LegacyWildcardSingle,
+    LegacyWildcardMulti,
+    Dynamic {
+        name: String,
+        key: LiveId,
+        optional: bool,
+        default: Option<String>,
+        constraint: Option<RouteParamConstraint>,
+    },
+    WildcardSingle {
+        name: Option<String>,
+        key: Option<LiveId>,
+    },
+    WildcardMulti {
+        name: Option<String>,
+        key: Option<LiveId>,
+    },
+}
+
+impl SerBin for RouteSegment {
+    fn ser_bin(&self, s: &mut Vec<u8>) {
+        let bin = match self.clone() {
+            RouteSegment::Static(value) => RouteSegmentBin::Static(value),
+            RouteSegment::Dynamic {
+                name,
+                key,
+                optional,
+                default,
+                constraint,
+            } => RouteSegmentBin::Dynamic {
+                name,
+                key,
+                optional,
+                default,
+                constraint,
+            },
+            RouteSegment::WildcardSingle { name, key } => {
+                RouteSegmentBin::WildcardSingle { name, key }
+            }
+            RouteSegment::WildcardMulti { name, key } => {
+                RouteSegmentBin::WildcardMulti { name, key }
+            }
+        };
+        bin.ser_bin(s);
+    }
+}
+
+impl DeBin for RouteSegment {
+    fn de_bin(o: &mut usize, d: &[u8]) -> Result<Self, DeBinErr> {
+        Ok(match RouteSegmentBin::de_bin(o, d)? {
+            RouteSegmentBin::Static(value) => RouteSegment::Static(value),
+            RouteSegmentBin::LegacyDynamic { name, key } => RouteSegment::Dynamic {
+                name,
+                key,
+                optional: false,
+                default: None,
+                constraint: None,
+            },
+            RouteSegmentBin::LegacyWildcardSingle => RouteSegment::WildcardSingle {
+                name: None,
+                key: None,
+            },
+            RouteSegmentBin::LegacyWildcardMulti => RouteSegment::WildcardMulti {
+                name: None,
+                key: None,
+            },
+            RouteSegmentBin::Dynamic {
+                name,
+                key,
+                optional,
+                default,
+                constraint,
+            } => RouteSegment::Dynamic {
+                name,
+                key,
+                optional,
+                default,
+                constraint,
+            },
+            RouteSegmentBin::WildcardSingle { name, key } => {
+                RouteSegment::WildcardSingle { name, key }
+            }
+            RouteSegmentBin::WildcardMulti { name, key } => {
+                RouteSegment::WildcardMulti { name, key }
+            }
+        })
+    }
+}
+
+// Also reads the previous shapes: `Dynamic` without the optional/default/constraint fields and
+// unit `WildcardSingle` / `WildcardMulti`.
+impl DeRon for RouteSegment {
+    fn de_ron(s: &mut DeRonState, i: &mut std::str::Chars) -> Result<Self, DeRonErr> {
+        if s.tok != DeRonTok::Ident {
+            return Err(s.err_token("RouteSegment variant"));
+        }
+        let variant = s.identbuf.clone();
+        s.ident(i)?;
+        let mut name: Option<String> = None;
+        let mut key: Option<LiveId> = None;
+        let mut optional = false;
+        let mut default: Option<String> = None;
+        let mut constraint: Option<RouteParamConstraint> = None;
+        match variant.as_str() {
+            "Static" => {
+                s.paren_open(i)?;
+                let value = String::de_ron(s, i)?;
+                s.eat_comma_paren(i)?;
+                s.paren_close(i)?;
+                return Ok(RouteSegment::Static(value));
+            }
+            "Dynamic" | "WildcardSingle" | "WildcardMulti" => {}
+            _ => return Err(s.err_token("Static, Dynamic, WildcardSingle or WildcardMulti")),
+        }
+        if s.tok == DeRonTok::ParenOpen {
+            s.paren_open(i)?;
+            loop {
+                match s.tok {
+                    DeRonTok::ParenClose => {
+                        s.paren_close(i)?;
+                        break;
+                    }
+                    DeRonTok::Ident => {
+                        let field = s.identbuf.clone();
+                        s.ident(i)?;
+                        s.colon(i)?;
+                        match (variant.as_str(), field.as_str()) {
+                            ("Dynamic", "name") => name = Some(String::de_ron(s, i)?),
+                            ("Dynamic", "key") => key = Some(LiveId::de_ron(s, i)?),
+                            ("Dynamic", "optional") => optional = bool::de_ron(s, i)?,
+                            ("Dynamic", "default") => default = Option::<String>::de_ron(s, i)?,
+                            ("Dynamic", "constraint") => {
+                                constraint = Option::<RouteParamConstraint>::de_ron(s, i)?
+                            }
+                            (_, "name") => name = Option::<String>::de_ron(s, i)?,
+                            (_, "key") => key = Option::<LiveId>::de_ron(s, i)?,
+                            _ => return Err(s.err_token("RouteSegment field")),
+                        }
+                        s.eat_comma_paren(i)?;
+                    }
+                    _ => return Err(s.err_token("Identifier or )")),
+                }
+            }
+        }
+        Ok(match variant.as_str() {
+            "Dynamic" => {
+                let name = name.unwrap_or_default();
+                RouteSegment::Dynamic {
+                    key: key.unwrap_or_else(|| LiveId::from_str(&name)),
+                    name,
+                    optional,
+                    default,
+                    constraint,
+                }
+            }
+            "WildcardSingle" => RouteSegment::WildcardSingle { name, key },
+            _ => RouteSegment::WildcardMulti { name, key },
+        })
+    }
 }
 
 /// Route pattern for matching paths with dynamic segments and wildcards
@@ -121,50 +414,124 @@ impl DeRon for RoutePatternRef {
 /// Route parameters - optimized for small param counts.
 #[derive(Clone, Debug, Default)]
 pub struct RouteParams {
-    /// Generic parameters stored as LiveId key-value pairs.
+    /// Generic parameters stored as LiveId keys with LiveId or string values.
     pub data: RouteParamStore,
 }
 
 #[derive(Clone, Debug)]
 pub enum RouteParamStore {
-    Small(Vec<(LiveId, LiveId)>),
-    Map(HashMap<LiveId, LiveId>),
+    Small(Vec<(LiveId, RouteParamValue)>),
+    Map(HashMap<LiveId, RouteParamValue>),
+}
+
+/// A route parameter value.
+///
+/// Values captured from paths are stored as shared strings so they never enter the global
+/// `LiveId` intern table; values set from code can still be plain `LiveId`s.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RouteParamValue {
+    Id(LiveId),
+    Str(Arc<str>),
+}
+
+impl RouteParamValue {
+    /// The value as a `LiveId` (string values are hashed, not interned).
+    pub fn as_live_id(&self) -> LiveId {
+        match self {
+            Self::Id(id) => *id,
+            Self::Str(value) => LiveId::from_str(value),
+        }
+    }
+
+    /// Whether both values hold the same param: strings compare by text, ids by id, and a
+    /// string equals an id of the same text.
+    fn same_value(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Str(a), Self::Str(b)) => a == b,
+            (Self::Id(a), Self::Id(b)) => a == b,
+            _ => self.as_live_id() == other.as_live_id(),
+        }
+    }
+
+    /// The value as text: borrowed for string values, looked up in the intern table for ids.
+    pub fn to_str(&self) -> Option<Cow<'_, str>> {
+        match self {
+            Self::Id(id) => id.as_string(|s| s.map(|s| Cow::Owned(s.to_string()))),
+            Self::Str(value) => Some(Cow::Borrowed(value)),
+        }
+    }
+}
+
+impl fmt::Display for RouteParamValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Id(id) => write!(f, "{}", id),
+            Self::Str(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl From<LiveId> for RouteParamValue {
+    fn from(id: LiveId) -> Self {
+        Self::Id(id)
+    }
+}
+
+impl From<&str> for RouteParamValue {
+    fn from(value: &str) -> Self {
+        Self::Str(value.into())
+    }
+}
+
+impl From<String> for RouteParamValue {
+    fn from(value: String) -> Self {
+        Self::Str(value.into())
+    }
 }
 
 impl RoutePattern {
-    /// Parse a route pattern string (e.g., "/user/:id" or "/admin/*")
-    pub fn parse(pattern: &str) -> Result<Self, String> {
-        let pattern = pattern.trim();
-        if pattern.is_empty() {
-            return Err("Pattern cannot be empty".to_string());
+    /// Parse a route pattern string (e.g., "/user/:id", "/posts/:page?", "/admin/*" or "/docs/**rest")
+    pub fn parse(pattern: &str) -> Result<Self, PatternError> {
+        let trimmed = pattern.trim();
+        if trimmed.is_empty() {
+            return Err(PatternError::Empty);
         }
 
-        // Normalize: ensure it starts with /
-        let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+        // Keep each part's byte offset in `pattern` for error positions.
+        let mut parts: Vec<(usize, &str)> = Vec::new();
+        let mut offset = pattern.len() - pattern.trim_start().len();
+        for part in trimmed.split('/') {
+            if !part.is_empty() {
+                parts.push((offset, part));
+            }
+            offset += part.len() + 1;
+        }
 
         let mut segments = Vec::new();
-        let parts: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
-
-        for (i, part) in parts.iter().enumerate() {
-            if part == &"**" {
+        for (i, &(position, part)) in parts.iter().enumerate() {
+            if let Some(capture) = part.strip_prefix("**") {
                 // Multi-segment wildcard must be the last segment
                 if i != parts.len() - 1 {
-                    return Err("Multi-segment wildcard (**) must be the last segment".to_string());
+                    return Err(PatternError::MultiWildcardNotLast { position });
                 }
-                segments.push(RouteSegment::WildcardMulti);
+                let (name, key) = parse_wildcard_name(capture);
+                segments.push(RouteSegment::WildcardMulti { name, key });
                 break;
-            } else if part == &"*" {
-                segments.push(RouteSegment::WildcardSingle);
-            } else if let Some(param_name) = part.strip_prefix(':') {
-                if param_name.is_empty() {
-                    return Err("Dynamic segment parameter name cannot be empty".to_string());
-                }
-                let name = param_name.to_string();
-                let key = LiveId::from_str(param_name);
-                segments.push(RouteSegment::Dynamic { name, key });
+            } else if let Some(capture) = part.strip_prefix('*') {
+                let (name, key) = parse_wildcard_name(capture);
+                segments.push(RouteSegment::WildcardSingle { name, key });
+            } else if let Some(body) = part.strip_prefix(':') {
+                segments.push(parse_dynamic_segment(body, position + 1)?);
             } else {
                 segments.push(RouteSegment::Static(part.to_string()));
             }
+
+            // Optional segments can only be followed by other optional segments (or `**`).
+            if let [.., prev, last] = segments.as_slice() {
+                if prev.is_optional() && !last.is_optional() {
+                    return Err(PatternError::RequiredAfterOptional { position });
+                }
+            }
         }
 
         Ok(RoutePattern { segments })
@@ -172,6 +539,13 @@ impl RoutePattern {
 
     /// Match a path against this pattern and extract parameters
     pub fn matches(&self, path: &str) -> Option<RouteParams> {
+        self.matches_with_policy(path, &MatchPolicy::default())
+    }
+
+    /// Like [`matches`](Self::matches), comparing static segments under `policy`.
+    ///
+    /// Slash handling is applied by `MatchPolicy::normalize_path` before matching.
+    pub fn matches_with_policy(&self, path: &str, policy: &MatchPolicy) -> Option<RouteParams> {
         let path = path.trim();
         // Normalize: ensure it starts with /
         let path = path.strip_prefix('/').unwrap_or(path);
@@ -181,29 +555,51 @@ impl RoutePattern {
         for segment in &self.segments {
             match segment {
                 RouteSegment::Static(expected) => {
-                    let Some(actual) = next_non_empty_segment(&mut path_segments) else {
-                        return None;
-                    };
-                    if actual != expected {
+                    let actual = next_non_empty_segment(&mut path_segments)?;
+                    if !policy.static_segment_eq(&url::decode_path_segment(actual), expected) {
                         return None;
                     }
                 }
-                RouteSegment::Dynamic { key, .. } => {
-                    let Some(value) = next_non_empty_segment(&mut path_segments) else {
-                        return None;
-                    };
-                    // Use from_str_with_intern to store the string so it can be retrieved later
-                    use makepad_live_id::InternLiveId;
-                    let param_value = LiveId::from_str_with_intern(value, InternLiveId::Yes);
-                    params.add(*key, param_value);
-                }
-                RouteSegment::WildcardSingle => {
+                RouteSegment::Dynamic {
+                    key,
+                    optional,
+                    default,
+                    constraint,
+                    ..
+                } => match next_non_empty_segment(&mut path_segments) {
+                    Some(value) => {
+                        let value = url::decode_path_segment(value);
+                        if constraint.as_ref().is_some_and(|c| !c.matches(&value)) {
+                            return None;
+                        }
+                        params.add_string(*key, value);
+                    }
+                    None if *optional => {
+                        if let Some(default) = default {
+                            params.add_string(*key, default.as_str());
+                        }
+                    }
+                    None => return None,
+                },
+                RouteSegment::WildcardSingle { key, .. } => {
                     // Match exactly one segment
-                    next_non_empty_segment(&mut path_segments)?;
+                    let value = next_non_empty_segment(&mut path_segments)?;
+                    if let Some(key) = key {
+                        params.add_string(*key, url::decode_path_segment(value));
+                    }
                 }
-                RouteSegment::WildcardMulti => {
+                RouteSegment::WildcardMulti { key, .. } => {
                     // Match remaining segments (zero or more)
                     // This is the last segment, so we're done
+                    if let Some(key) = key {
+                        let rest = collect_tail(
+                            next_non_empty_segment(&mut path_segments),
+                            &mut path_segments,
+                        );
+                        if let Some(rest) = rest.strip_prefix('/') {
+                            params.add_string(*key, decode_tail(rest));
+                        }
+                    }
                     return Some(params);
                 }
             }
@@ -225,8 +621,19 @@ impl RoutePattern {
     /// Tail rules:
     /// - If the pattern ends before the path, the tail is the remaining unmatched segments.
     /// - If the pattern ends with `*` or `**`, the tail is the segment(s) matched by that wildcard.
+    ///   Named wildcards also store the text they matched in the returned params.
     /// - The returned tail is `""` if there is nothing to delegate, otherwise it starts with `/`.
     pub fn matches_prefix_with_tail(&self, path: &str) -> Option<(RouteParams, String)> {
+        self.matches_prefix_with_policy(path, &MatchPolicy::default())
+    }
+
+    /// Like [`matches_prefix_with_tail`](Self::matches_prefix_with_tail), comparing static
+    /// segments under `policy`.
+    pub fn matches_prefix_with_policy(
+        &self,
+        path: &str,
+        policy: &MatchPolicy,
+    ) -> Option<(RouteParams, String)> {
         let path = path.trim();
         let path = path.strip_prefix('/').unwrap_or(path);
         let mut path_segments = path.split('/');
@@ -238,35 +645,52 @@ impl RoutePattern {
         for (pattern_idx, segment) in self.segments.iter().enumerate() {
             match segment {
                 RouteSegment::Static(expected) => {
-                    let Some(actual) = next_non_empty_segment(&mut path_segments) else {
-                        return None;
-                    };
-                    if actual != expected {
+                    let actual = next_non_empty_segment(&mut path_segments)?;
+                    if !policy.static_segment_eq(&url::decode_path_segment(actual), expected) {
                         return None;
                     }
                 }
-                RouteSegment::Dynamic { key, .. } => {
-                    let Some(value) = next_non_empty_segment(&mut path_segments) else {
-                        return None;
-                    };
-                    use makepad_live_id::InternLiveId;
-                    let param_value = LiveId::from_str_with_intern(value, InternLiveId::Yes);
-                    params.add(*key, param_value);
-                }
-                RouteSegment::WildcardSingle => {
+                RouteSegment::Dynamic {
+                    key,
+                    optional,
+                    default,
+                    constraint,
+                    ..
+                } => match next_non_empty_segment(&mut path_segments) {
+                    Some(value) => {
+                        let value = url::decode_path_segment(value);
+                        if constraint.as_ref().is_some_and(|c| !c.matches(&value)) {
+                            return None;
+                        }
+                        params.add_string(*key, value);
+                    }
+                    None if *optional => {
+                        if let Some(default) = default {
+                            params.add_string(*key, default.as_str());
+                        }
+                    }
+                    None => return None,
+                },
+                RouteSegment::WildcardSingle { key, .. } => {
                     let matched = next_non_empty_segment(&mut path_segments)?;
+                    if let Some(key) = key {
+                        params.add_string(*key, url::decode_path_segment(matched));
+                    }
                     // For nested routing we capture the tail if wildcard is trailing.
                     if pattern_idx == last_idx {
                         tail = Some(collect_tail(Some(matched), &mut path_segments));
                     }
                 }
-                RouteSegment::WildcardMulti => {
+                RouteSegment::WildcardMulti { key, .. } => {
                     // Must be last (enforced by parser). Capture the rest (could be empty).
-                    tail = Some(collect_tail(
+                    let rest = collect_tail(
                         next_non_empty_segment(&mut path_segments),
                         &mut path_segments,
-                    ));
-                    return Some((params, tail.unwrap_or_default()));
+                    );
+                    if let (Some(key), Some(value)) = (key, rest.strip_prefix('/')) {
+                        params.add_string(*key, decode_tail(value));
+                    }
+                    return Some((params, rest));
                 }
             }
         }
@@ -282,33 +706,120 @@ impl RoutePattern {
     }
 
     /// Get the priority for route matching (lower = higher priority)
+    ///
+    /// Constrained dynamic segments rank ahead of unconstrained ones, so `/user/:id<u64>` is tried
+    /// before `/user/:name`.
     pub fn priority(&self) -> usize {
         let mut priority = 0;
         for segment in &self.segments {
             match segment {
                 RouteSegment::Static(_) => priority += 1,
-                RouteSegment::Dynamic { .. } => priority += 100,
-                RouteSegment::WildcardSingle => priority += 10000,
-                RouteSegment::WildcardMulti => priority += 100000,
+                RouteSegment::Dynamic {
+                    optional,
+                    constraint,
+                    ..
+                } => {
+                    priority += match (optional, constraint.is_some()) {
+                        (false, true) => 50,
+                        (false, false) => 100,
+                        (true, true) => 125,
+                        (true, false) => 150,
+                    }
+                }
+                RouteSegment::WildcardSingle { .. } => priority += 10000,
+                RouteSegment::WildcardMulti { .. } => priority += 100000,
             }
         }
         priority
     }
 
-    /// Format a concrete path (no wildcards) from this pattern and params.
+    /// Format a concrete path from this pattern and params.
+    ///
+    /// Named wildcards are re-emitted from their captured params (a missing `**name` param formats
+    /// as zero segments); unnamed wildcards cannot be formatted and yield `None`.
+    /// Trailing optional segments that are missing or equal to their default are omitted.
+    /// Static segments and param values are percent-encoded as path segments (`**` values keep
+    /// their `/` separators and existing `%XX` escapes).
     pub fn format_path(&self, params: &RouteParams) -> Option<String> {
+        self.try_format_path(params).ok()
+    }
+
+    /// Like [`format_path`](Self::format_path), but reports why a path cannot be built.
+    pub fn try_format_path(&self, params: &RouteParams) -> Result<String, UrlForError> {
         let mut out: Vec<String> = Vec::with_capacity(self.segments.len());
+        // Optional values held back until a later segment is emitted (dropped if none is).
+        let mut deferred: Vec<String> = Vec::new();
+        // Name of the first optional segment skipped without value or default.
+        let mut gap: Option<&str> = None;
+        let gap_error = |gap: &str| UrlForError::OptionalGap {
+            name: gap.to_string(),
+        };
         for segment in &self.segments {
+            if let RouteSegment::Dynamic {
+                name,
+                key,
+                optional: true,
+                default,
+                ..
+            } = segment
+            {
+                let value = params.get_value(*key).map(|v| v.to_string());
+                match (value, default) {
+                    (Some(value), Some(default)) if value == *default => {
+                        deferred.push(encode_segment(&value))
+                    }
+                    (Some(value), _) => {
+                        if let Some(gap) = gap {
+                            return Err(gap_error(gap));
+                        }
+                        out.append(&mut deferred);
+                        out.push(encode_segment(&value));
+                    }
+                    (None, Some(default)) => deferred.push(encode_segment(default)),
+                    (None, None) => {
+                        gap.get_or_insert(name.as_str());
+                    }
+                }
+                continue;
+            }
             match segment {
-                RouteSegment::Static(s) => out.push(s.clone()),
-                RouteSegment::Dynamic { key, .. } => {
-                    let value = params.get(*key)?;
-                    out.push(value.to_string());
+                RouteSegment::Static(s) => {
+                    out.push(url::encode_path_segment_keep_escapes(s).into_owned())
+                }
+                RouteSegment::Dynamic { name, key, .. }
+                | RouteSegment::WildcardSingle {
+                    name: Some(name),
+                    key: Some(key),
+                } => {
+                    let value = params
+                        .get_value(*key)
+                        .ok_or_else(|| UrlForError::MissingParam { name: name.clone() })?;
+                    out.push(encode_segment(&value.to_string()));
+                }
+                RouteSegment::WildcardMulti { key: Some(key), .. } => {
+                    if let Some(value) = params.get_value(*key) {
+                        let value = value.to_string();
+                        let value = value.trim_matches('/');
+                        if !value.is_empty() {
+                            if let Some(gap) = gap {
+                                return Err(gap_error(gap));
+                            }
+                            out.append(&mut deferred);
+                            let encoded: Vec<Cow<'_, str>> = value
+                                .split('/')
+                                .map(url::encode_path_segment_keep_escapes)
+                                .collect();
+                            out.push(encoded.join("/"));
+                        }
+                    }
+                }
+                RouteSegment::WildcardSingle { .. }
+                | RouteSegment::WildcardMulti { key: None, .. } => {
+                    return Err(UrlForError::UnnamedWildcard)
                 }
-                RouteSegment::WildcardSingle | RouteSegment::WildcardMulti => return None,
             }
         }
-        Some(format!("/{}", out.join("/")))
+        Ok(format!("/{}", out.join("/")))
     }
 
     /// Format the "base" part of a pattern, stopping before wildcards.
@@ -318,15 +829,17 @@ impl RoutePattern {
         let mut out: Vec<String> = Vec::with_capacity(self.segments.len());
         for segment in &self.segments {
             match segment {
-                RouteSegment::Static(s) => out.push(s.clone()),
+                RouteSegment::Static(s) => {
+                    out.push(url::encode_path_segment_keep_escapes(s).into_owned())
+                }
                 RouteSegment::Dynamic { key, .. } => {
-                    if let Some(value) = params.get(*key) {
-                        out.push(value.to_string());
+                    if let Some(value) = params.get_value(*key) {
+                        out.push(encode_segment(&value.to_string()));
                     } else {
                         break;
                     }
                 }
-                RouteSegment::WildcardSingle | RouteSegment::WildcardMulti => break,
+                RouteSegment::WildcardSingle { .. } | RouteSegment::WildcardMulti { .. } => break,
             }
         }
         if out.is_empty() {
@@ -337,6 +850,139 @@ impl RoutePattern {
     }
 }
 
+impl fmt::Display for RouteSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteSegment::Static(s) => write!(f, "{}", s),
+            RouteSegment::Dynamic {
+                name,
+                optional,
+                default,
+                constraint,
+                ..
+            } => {
+                write!(f, ":{}", name)?;
+                if let Some(constraint) = constraint {
+                    write!(f, "<{}>", constraint)?;
+                }
+                match default {
+                    Some(default) => write!(f, "={}", default),
+                    None if *optional => write!(f, "?"),
+                    None => Ok(()),
+                }
+            }
+            RouteSegment::WildcardSingle { name, .. } => {
+                write!(f, "*{}", name.as_deref().unwrap_or_default())
+            }
+            RouteSegment::WildcardMulti { name, .. } => {
+                write!(f, "**{}", name.as_deref().unwrap_or_default())
+            }
+        }
+    }
+}
+
+impl fmt::Display for RoutePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.segments.is_empty() {
+            return write!(f, "/");
+        }
+        for segment in &self.segments {
+            write!(f, "/{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether single-segment `outer` accepts every value single-segment `inner` accepts.
+fn segment_accepts(outer: &RouteSegment, inner: &RouteSegment, policy: &MatchPolicy) -> bool {
+    match (outer, inner) {
+        (
+            RouteSegment::Dynamic {
+                constraint: None, ..
+            }
+            | RouteSegment::WildcardSingle { .. },
+            _,
+        ) => true,
+        (RouteSegment::Static(a), RouteSegment::Static(b)) => policy.static_segment_eq(b, a),
+        (
+            RouteSegment::Dynamic {
+                constraint: Some(c),
+                ..
+            },
+            RouteSegment::Static(value),
+        ) => c.matches(value),
+        (
+            RouteSegment::Dynamic {
+                constraint: Some(a),
+                ..
+            },
+            RouteSegment::Dynamic {
+                constraint: Some(b),
+                ..
+            },
+        ) => a == b,
+        _ => false,
+    }
+}
+
+/// Conservative check that every path matched by `inner` is also matched by `outer`.
+fn segments_cover(outer: &[RouteSegment], inner: &[RouteSegment], policy: &MatchPolicy) -> bool {
+    let Some((first, rest)) = inner.split_first() else {
+        return outer.iter().all(RouteSegment::is_optional);
+    };
+    match first {
+        RouteSegment::WildcardMulti { .. } => match outer.split_first() {
+            Some((RouteSegment::WildcardMulti { .. }, _)) => true,
+            Some((
+                RouteSegment::Dynamic {
+                    optional: true,
+                    constraint: None,
+                    ..
+                },
+                outer_rest,
+            )) => segments_cover(outer_rest, inner, policy),
+            _ => false,
+        },
+        RouteSegment::Dynamic { optional: true, .. } => {
+            // Both the "absent" and the "present" forms must be covered.
+            let mut required = first.clone();
+            if let RouteSegment::Dynamic {
+                optional, default, ..
+            } = &mut required
+            {
+                *optional = false;
+                *default = None;
+            }
+            let mut present = vec![required];
+            present.extend_from_slice(rest);
+            segments_cover(outer, rest, policy) && segments_cover(outer, &present, policy)
+        }
+        _ => match outer.split_first() {
+            None => false,
+            Some((RouteSegment::WildcardMulti { .. }, _)) => true,
+            Some((outer_first, outer_rest)) => {
+                segment_accepts(outer_first, first, policy)
+                    && segments_cover(outer_rest, rest, policy)
+            }
+        },
+    }
+}
+
+impl RoutePattern {
+    /// Whether every path matched by `other` is also matched by this pattern.
+    ///
+    /// The check is conservative: `false` means "not provably covered" (e.g., two different
+    /// character-class constraints are never treated as overlapping).
+    pub fn covers(&self, other: &RoutePattern) -> bool {
+        self.covers_with_policy(other, &MatchPolicy::default())
+    }
+
+    /// Like [`covers`](Self::covers), comparing static segments under `policy`.
+    pub fn covers_with_policy(&self, other: &RoutePattern, policy: &MatchPolicy) -> bool {
+        segments_cover(&self.segments, &other.segments, policy)
+    }
+}
+
 impl RouteParams {
     /// Create empty route parameters
     pub fn new() -> Self {
@@ -345,6 +991,16 @@ impl RouteParams {
 
     /// Add a parameter
     pub fn add(&mut self, key: LiveId, value: LiveId) {
+        self.insert(key, RouteParamValue::Id(value));
+    }
+
+    /// Add a string parameter (not interned).
+    pub fn add_string(&mut self, key: LiveId, value: impl Into<Arc<str>>) {
+        self.insert(key, RouteParamValue::Str(value.into()));
+    }
+
+    /// Set a parameter value, replacing any previous value for `key`.
+    pub fn insert(&mut self, key: LiveId, value: RouteParamValue) {
         match &mut self.data {
             RouteParamStore::Small(entries) => {
                 for (k, v) in entries.iter_mut() {
@@ -356,7 +1012,8 @@ impl RouteParams {
                 if entries.len() < 4 {
                     entries.push((key, value));
                 } else {
-                    let mut map: HashMap<LiveId, LiveId> = entries.iter().copied().collect();
+                    let mut map: HashMap<LiveId, RouteParamValue> =
+                        std::mem::take(entries).into_iter().collect();
                     map.insert(key, value);
                     self.data = RouteParamStore::Map(map);
                 }
@@ -367,16 +1024,30 @@ impl RouteParams {
         }
     }
 
-    /// Get a parameter value by key
+    /// Get a parameter value by key as a `LiveId`.
+    ///
+    /// String values (everything captured from a path) come back as a hash of the text that is
+    /// not interned, so `as_string` cannot recover it and distinct strings may collide. Use
+    /// `get_str` to read captured values; this is meant for ids set with `add`.
     pub fn get(&self, key: LiveId) -> Option<LiveId> {
+        self.get_value(key).map(RouteParamValue::as_live_id)
+    }
+
+    /// Get the stored parameter value by key.
+    pub fn get_value(&self, key: LiveId) -> Option<&RouteParamValue> {
         match &self.data {
             RouteParamStore::Small(entries) => {
-                entries.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+                entries.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
             }
-            RouteParamStore::Map(map) => map.get(&key).copied(),
+            RouteParamStore::Map(map) => map.get(&key),
         }
     }
 
+    /// Get a parameter value as text (borrowed for string values).
+    pub fn get_str(&self, key: LiveId) -> Option<Cow<'_, str>> {
+        self.get_value(key)?.to_str()
+    }
+
     pub fn is_empty(&self) -> bool {
         match &self.data {
             RouteParamStore::Small(entries) => entries.is_empty(),
@@ -403,7 +1074,8 @@ impl PartialEq for RouteParams {
         if self.len() != other.len() {
             return false;
         }
-        self.iter().all(|(k, v)| other.get(*k) == Some(*v))
+        self.iter()
+            .all(|(k, v)| other.get_value(*k).is_some_and(|other| v.same_value(other)))
     }
 }
 
@@ -417,34 +1089,26 @@ impl RouteParams {
         }
     }
 
-    fn to_hash_map(&self) -> HashMap<LiveId, LiveId> {
-        match &self.data {
-            RouteParamStore::Small(entries) => entries.iter().copied().collect(),
-            RouteParamStore::Map(map) => map.clone(),
-        }
-    }
-
-    fn from_hash_map(map: HashMap<LiveId, LiveId>) -> Self {
-        if map.len() <= 4 {
-            let entries = map.into_iter().collect();
+    fn from_entries(entries: Vec<(LiveId, RouteParamValue)>) -> Self {
+        if entries.len() <= 4 {
             Self {
                 data: RouteParamStore::Small(entries),
             }
         } else {
             Self {
-                data: RouteParamStore::Map(map),
+                data: RouteParamStore::Map(entries.into_iter().collect()),
             }
         }
     }
 }
 
 pub enum RouteParamIter<'a> {
-    Small(std::slice::Iter<'a, (LiveId, LiveId)>),
-    Map(std::collections::hash_map::Iter<'a, LiveId, LiveId>),
+    Small(std::slice::Iter<'a, (LiveId, RouteParamValue)>),
+    Map(std::collections::hash_map::Iter<'a, LiveId, RouteParamValue>),
 }
 
 impl<'a> Iterator for RouteParamIter<'a> {
-    type Item = (&'a LiveId, &'a LiveId);
+    type Item = (&'a LiveId, &'a RouteParamValue);
 
     fn next(&mut self) -> Option<Self::Item> {
         match self {
@@ -454,29 +1118,93 @@ impl<'a> Iterator for RouteParamIter<'a> {
     }
 }
 
+/// Leading marker of the tagged binary format. The previous format (a `HashMap<LiveId, LiveId>`)
+/// starts with its length, which can never be `u64::MAX`, so both formats can be read.
+const ROUTE_PARAMS_TAGGED_MARKER: u64 = u64::MAX;
+
 impl SerBin for RouteParams {
     fn ser_bin(&self, s: &mut Vec<u8>) {
-        self.to_hash_map().ser_bin(s);
+        ROUTE_PARAMS_TAGGED_MARKER.ser_bin(s);
+        self.len().ser_bin(s);
+        for (key, value) in self.iter() {
+            key.ser_bin(s);
+            match value {
+                RouteParamValue::Id(id) => {
+                    false.ser_bin(s);
+                    id.ser_bin(s);
+                }
+                RouteParamValue::Str(value) => {
+                    true.ser_bin(s);
+                    value.to_string().ser_bin(s);
+                }
+            }
+        }
     }
 }
 
 impl DeBin for RouteParams {
     fn de_bin(o: &mut usize, d: &[u8]) -> Result<Self, DeBinErr> {
-        let map = HashMap::<LiveId, LiveId>::de_bin(o, d)?;
-        Ok(Self::from_hash_map(map))
+        let head = u64::de_bin(o, d)?;
+        let mut entries = Vec::new();
+        if head != ROUTE_PARAMS_TAGGED_MARKER {
+            for _ in 0..head {
+                let key = LiveId::de_bin(o, d)?;
+                let value = LiveId::de_bin(o, d)?;
+                entries.push((key, RouteParamValue::Id(value)));
+            }
+            return Ok(Self::from_entries(entries));
+        }
+        let len = usize::de_bin(o, d)?;
+        for _ in 0..len {
+            let key = LiveId::de_bin(o, d)?;
+            let value = if bool::de_bin(o, d)? {
+                RouteParamValue::Str(String::de_bin(o, d)?.into())
+            } else {
+                RouteParamValue::Id(LiveId::de_bin(o, d)?)
+            };
+            entries.push((key, value));
+        }
+        Ok(Self::from_entries(entries))
     }
 }
 
+// String values are written as RON strings and ids as numbers, so maps written by the previous
+// `HashMap<LiveId, LiveId>` representation still load.
 impl SerRon for RouteParams {
     fn ser_ron(&self, d: usize, s: &mut SerRonState) {
-        self.to_hash_map().ser_ron(d, s);
+        s.out.push_str("{\n");
+        for (key, value) in self.iter() {
+            s.indent(d + 1);
+            key.ser_ron(d + 1, s);
+            s.out.push(':');
+            match value {
+                RouteParamValue::Id(id) => id.ser_ron(d + 1, s),
+                RouteParamValue::Str(value) => value.to_string().ser_ron(d + 1, s),
+            }
+            s.conl();
+        }
+        s.indent(d);
+        s.out.push('}');
     }
 }
 
 impl DeRon for RouteParams {
     fn de_ron(s: &mut DeRonState, i: &mut std::str::Chars) -> Result<Self, DeRonErr> {
-        let map = HashMap::<LiveId, LiveId>::de_ron(s, i)?;
-        Ok(Self::from_hash_map(map))
+        let mut entries = Vec::new();
+        s.curly_open(i)?;
+        while s.tok != DeRonTok::CurlyClose {
+            let key = LiveId::de_ron(s, i)?;
+            s.colon(i)?;
+            let value = if s.tok == DeRonTok::Str {
+                RouteParamValue::Str(String::de_ron(s, i)?.into())
+            } else {
+                RouteParamValue::Id(LiveId::de_ron(s, i)?)
+            };
+            s.eat_comma_curly(i)?;
+            entries.push((key, value));
+        }
+        s.curly_close(i)?;
+        Ok(Self::from_entries(entries))
     }
 }
 
@@ -507,7 +1235,10 @@ mod tests {
         let pattern = RoutePattern::parse("/admin/*").unwrap();
         assert_eq!(pattern.segments.len(), 2);
         assert!(matches!(pattern.segments[0], RouteSegment::Static(ref s) if s == "admin"));
-        assert!(matches!(pattern.segments[1], RouteSegment::WildcardSingle));
+        assert!(matches!(
+            pattern.segments[1],
+            RouteSegment::WildcardSingle { name: None, .. }
+        ));
     }
 
     #[test]
@@ -515,7 +1246,10 @@ mod tests {
         let pattern = RoutePattern::parse("/admin/**").unwrap();
         assert_eq!(pattern.segments.len(), 2);
         assert!(matches!(pattern.segments[0], RouteSegment::Static(ref s) if s == "admin"));
-        assert!(matches!(pattern.segments[1], RouteSegment::WildcardMulti));
+        assert!(matches!(
+            pattern.segments[1],
+            RouteSegment::WildcardMulti { name: None, .. }
+        ));
     }
 
     #[test]
@@ -527,7 +1261,10 @@ mod tests {
             matches!(pattern.segments[1], RouteSegment::Dynamic { ref name, .. } if name == "id")
         );
         assert!(matches!(pattern.segments[2], RouteSegment::Static(ref s) if s == "posts"));
-        assert!(matches!(pattern.segments[3], RouteSegment::WildcardSingle));
+        assert!(matches!(
+            pattern.segments[3],
+            RouteSegment::WildcardSingle { .. }
+        ));
     }
 
     #[test]
@@ -639,6 +1376,206 @@ mod tests {
         assert_eq!(tail, "/d");
     }
 
+    #[test]
+    fn test_pattern_parse_named_wildcards() {
+        let pattern = RoutePattern::parse("/files/*file").unwrap();
+        assert!(matches!(
+            pattern.segments[1],
+            RouteSegment::WildcardSingle { name: Some(ref name), .. } if name == "file"
+        ));
+
+        let pattern = RoutePattern::parse("/docs/**rest").unwrap();
+        assert!(matches!(
+            pattern.segments[1],
+            RouteSegment::WildcardMulti { name: Some(ref name), .. } if name == "rest"
+        ));
+
+        assert!(RoutePattern::parse("/docs/**rest/edit").is_err());
+    }
+
+    #[test]
+    fn test_pattern_match_named_wildcards() {
+        let pattern = RoutePattern::parse("/files/*file").unwrap();
+        let params = pattern.matches("/files/report.pdf").unwrap();
+        assert_eq!(
+            params.get(LiveId::from_str("file")),
+            Some(LiveId::from_str("report.pdf"))
+        );
+
+        let pattern = RoutePattern::parse("/docs/**rest").unwrap();
+        let params = pattern.matches("/docs/guide//intro").unwrap();
+        assert_eq!(
+            params.get(LiveId::from_str("rest")),
+            Some(LiveId::from_str("guide/intro"))
+        );
+
+        // Zero segments matched: no param is stored.
+        let params = pattern.matches("/docs").unwrap();
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_pattern_prefix_tail_named_wildcard_captures() {
+        let pattern = RoutePattern::parse("/docs/**rest").unwrap();
+        let (params, tail) = pattern.matches_prefix_with_tail("/docs/a/b").unwrap();
+        assert_eq!(tail, "/a/b");
+        assert_eq!(
+            params.get(LiveId::from_str("rest")),
+            Some(LiveId::from_str("a/b"))
+        );
+    }
+
+    #[test]
+    fn test_pattern_format_path_named_wildcards() {
+        let pattern = RoutePattern::parse("/docs/:lang/**rest").unwrap();
+        let params = pattern.matches("/docs/en/guide/intro").unwrap();
+        assert_eq!(
+            pattern.format_path(&params).as_deref(),
+            Some("/docs/en/guide/intro")
+        );
+
+        let params = pattern.matches("/docs/en").unwrap();
+        assert_eq!(pattern.format_path(&params).as_deref(), Some("/docs/en"));
+
+        let unnamed = RoutePattern::parse("/docs/**").unwrap();
+        assert_eq!(unnamed.format_path(&RouteParams::new()), None);
+    }
+
+    #[test]
+    fn test_pattern_parse_optional_and_default() {
+        let pattern = RoutePattern::parse("/posts/:page?").unwrap();
+        assert!(matches!(
+            pattern.segments[1],
+            RouteSegment::Dynamic { ref name, optional: true, default: None, .. } if name == "page"
+        ));
+
+        let pattern = RoutePattern::parse("/posts/:page=1").unwrap();
+        assert!(matches!(
+            pattern.segments[1],
+            RouteSegment::Dynamic { optional: true, default: Some(ref d), .. } if d == "1"
+        ));
+
+        assert!(RoutePattern::parse("/posts/:page?/edit").is_err());
+        assert!(RoutePattern::parse("/posts/:page=").is_err());
+        assert!(RoutePattern::parse("/posts/:page?/**rest").is_ok());
+    }
+
+    #[test]
+    fn test_pattern_match_optional_and_default() {
+        let optional = RoutePattern::parse("/posts/:page?").unwrap();
+        assert!(optional.matches("/posts").unwrap().is_empty());
+        assert_eq!(
+            optional
+                .matches("/posts/3")
+                .unwrap()
+                .get(LiveId::from_str("page")),
+            Some(LiveId::from_str("3"))
+        );
+        assert!(optional.matches("/posts/3/4").is_none());
+
+        let defaulted = RoutePattern::parse("/posts/:page=1").unwrap();
+        assert_eq!(
+            defaulted
+                .matches("/posts")
+                .unwrap()
+                .get(LiveId::from_str("page")),
+            Some(LiveId::from_str("1"))
+        );
+
+        let (params, tail) = defaulted.matches_prefix_with_tail("/posts").unwrap();
+        assert_eq!(
+            params.get(LiveId::from_str("page")),
+            Some(LiveId::from_str("1"))
+        );
+        assert_eq!(tail, "");
+    }
+
+    #[test]
+    fn test_pattern_format_path_optional_and_default() {
+        let pattern = RoutePattern::parse("/posts/:page=1/:sort?").unwrap();
+        let params = pattern.matches("/posts").unwrap();
+        assert_eq!(pattern.format_path(&params).as_deref(), Some("/posts"));
+
+        let params = pattern.matches("/posts/2").unwrap();
+        assert_eq!(pattern.format_path(&params).as_deref(), Some("/posts/2"));
+
+        let mut params = RouteParams::new();
+        params.add(
+            LiveId::from_str("sort"),
+            LiveId::from_str_with_intern("new", InternLiveId::Yes),
+        );
+        assert_eq!(
+            pattern.format_path(&params).as_deref(),
+            Some("/posts/1/new")
+        );
+
+        let gap = RoutePattern::parse("/posts/:page?/:sort?").unwrap();
+        assert_eq!(gap.format_path(&params), None);
+    }
+
+    #[test]
+    fn test_matched_params_are_strings_without_interning() {
+        let pattern = RoutePattern::parse("/user/:name").unwrap();
+        let params = pattern.matches("/user/not_interned_user_7f3a").unwrap();
+        assert!(matches!(
+            params.get_value(live_id!(name)),
+            Some(RouteParamValue::Str(_))
+        ));
+        assert_eq!(
+            params.get_str(live_id!(name)).as_deref(),
+            Some("not_interned_user_7f3a")
+        );
+        // The LiveId view is a plain hash; nothing was added to the intern table.
+        let id = params.get(live_id!(name)).unwrap();
+        assert_eq!(id, LiveId::from_str("not_interned_user_7f3a"));
+        assert!(id.as_string(|s| s.is_none()));
+    }
+
+    #[test]
+    fn test_route_params_eq_compares_values() {
+        let mut a = RouteParams::new();
+        a.add_string(live_id!(name), "John Doe");
+        a.add(live_id!(tab), live_id!(settings));
+        let mut b = RouteParams::new();
+        b.add(live_id!(tab), live_id!(settings));
+        b.add_string(live_id!(name), "John Doe");
+        assert_eq!(a, b);
+
+        let mut c = RouteParams::new();
+        c.add_string(live_id!(name), "Jane Doe");
+        c.add(live_id!(tab), live_id!(settings));
+        assert_ne!(a, c);
+
+        // A captured string equals the id of the same text.
+        let mut d = RouteParams::new();
+        d.add_string(live_id!(name), "John Doe");
+        d.add_string(live_id!(tab), "settings");
+        assert_eq!(a, d);
+    }
+
+    #[test]
+    fn test_route_params_serialization() {
+        let mut params = RouteParams::new();
+        params.add(live_id!(tab), live_id!(settings));
+        params.add_string(live_id!(name), "John Doe");
+
+        let de = RouteParams::deserialize_bin(&params.serialize_bin()).unwrap();
+        assert_eq!(de.get_str(live_id!(name)).as_deref(), Some("John Doe"));
+        assert_eq!(de.get(live_id!(tab)), Some(live_id!(settings)));
+
+        let de = RouteParams::deserialize_ron(&params.serialize_ron()).unwrap();
+        assert_eq!(de.get_str(live_id!(name)).as_deref(), Some("John Doe"));
+        assert_eq!(de.get(live_id!(tab)), Some(live_id!(settings)));
+
+        // The previous `HashMap<LiveId, LiveId>` format still loads.
+        let mut old = HashMap::new();
+        old.insert(live_id!(tab), live_id!(settings));
+        let de = RouteParams::deserialize_bin(&old.serialize_bin()).unwrap();
+        assert_eq!(de.get(live_id!(tab)), Some(live_id!(settings)));
+        let de = RouteParams::deserialize_ron(&old.serialize_ron()).unwrap();
+        assert_eq!(de.get(live_id!(tab)), Some(live_id!(settings)));
+    }
+
     #[test]
     fn test_pattern_priority() {
         let static_pattern = RoutePattern::parse("/user/profile").unwrap();
@@ -649,6 +1586,101 @@ mod tests {
         assert!(static_pattern.priority() < dynamic_pattern.priority());
         assert!(dynamic_pattern.priority() < wildcard_single.priority());
         assert!(wildcard_single.priority() < wildcard_multi.priority());
+
+        let optional_pattern = RoutePattern::parse("/user/:id?").unwrap();
+        assert!(dynamic_pattern.priority() < optional_pattern.priority());
+        assert!(optional_pattern.priority() < wildcard_single.priority());
+
+        let constrained = RoutePattern::parse("/user/:id<u64>").unwrap();
+        assert!(static_pattern.priority() < constrained.priority());
+        assert!(constrained.priority() < dynamic_pattern.priority());
+    }
+
+    #[test]
+    fn test_pattern_parse_constraints() {
+        let pattern = RoutePattern::parse("/user/:id<u64>").unwrap();
+        assert!(matches!(
+            pattern.segments[1],
+            RouteSegment::Dynamic { ref name, constraint: Some(RouteParamConstraint::U64), .. }
+                if name == "id"
+        ));
+
+        let pattern = RoutePattern::parse("/posts/:page<u64>=1").unwrap();
+        assert!(matches!(
+            pattern.segments[1],
+            RouteSegment::Dynamic { optional: true, default: Some(ref d), constraint: Some(_), .. }
+                if d == "1"
+        ));
+
+        assert!(RoutePattern::parse("/user/:id<u64").is_err());
+        assert!(RoutePattern::parse("/user/:id<unknown>").is_err());
+        assert!(RoutePattern::parse("/posts/:page<u64>=first").is_err());
+    }
+
+    #[test]
+    fn test_pattern_match_constraints() {
+        let user = RoutePattern::parse("/user/:id<u64>").unwrap();
+        assert!(user.matches("/user/42").is_some());
+        assert!(user.matches("/user/settings").is_none());
+        assert!(user.matches_prefix_with_tail("/user/settings/x").is_none());
+
+        let slug = RoutePattern::parse("/blog/:slug<[a-z-]+>").unwrap();
+        assert!(slug.matches("/blog/hello-world").is_some());
+        assert!(slug.matches("/blog/Hello_World").is_none());
+
+        let tab = RoutePattern::parse("/user/:id<u64>/:tab<(info|posts)>?").unwrap();
+        assert!(tab.matches("/user/1").is_some());
+        assert!(tab.matches("/user/1/posts").is_some());
+        assert!(tab.matches("/user/1/likes").is_none());
+    }
+
+    #[test]
+    fn test_pattern_display_round_trip() {
+        for source in [
+            "/",
+            "/user/:id",
+            "/posts/:page?",
+            "/posts/:page<u64>=1",
+            "/blog/:slug<[a-z\\-]+>",
+            "/files/*file",
+            "/docs/**rest",
+            "/admin/*",
+        ] {
+            let pattern = RoutePattern::parse(source).unwrap();
+            assert_eq!(pattern.to_string(), source);
+            assert_eq!(RoutePattern::parse(&pattern.to_string()).unwrap(), pattern);
+        }
+    }
+
+    #[test]
+    fn test_pattern_covers() {
+        let parse = |s: &str| RoutePattern::parse(s).unwrap();
+
+        assert!(parse("/user/:id").covers(&parse("/user/:name")));
+        assert!(parse("/user/:id").covers(&parse("/user/profile")));
+        assert!(parse("/user/:id").covers(&parse("/user/:id<u64>")));
+        assert!(!parse("/user/:id<u64>").covers(&parse("/user/:id")));
+        assert!(parse("/user/:id<u64>").covers(&parse("/user/42")));
+        assert!(parse("/user/**").covers(&parse("/user/:id/posts")));
+        assert!(parse("/user/:id?").covers(&parse("/user")));
+        assert!(!parse("/user/:id").covers(&parse("/user/:id?")));
+        assert!(!parse("/user/:id").covers(&parse("/user/**")));
+        assert!(!parse("/user/:id").covers(&parse("/team/:id")));
+    }
+
+    #[test]
+    fn test_pattern_matches_with_policy() {
+        let pattern = RoutePattern::parse("/Docs/:page/**").unwrap();
+        let insensitive = MatchPolicy::case_insensitive();
+        assert!(pattern.matches("/docs/intro/a").is_none());
+        let params = pattern
+            .matches_with_policy("/DOCS/Intro/a", &insensitive)
+            .unwrap();
+        assert_eq!(params.get_str(live_id!(page)).as_deref(), Some("Intro"));
+        let (_, tail) = pattern
+            .matches_prefix_with_policy("/docs/Intro/a/b", &insensitive)
+            .unwrap();
+        assert_eq!(tail, "/a/b");
     }
 
     #[test]
@@ -2,28 +2,26 @@
 //! Route registry for pattern-based route management.
 //!
 //! This module provides the route registry that handles:
-//! - Route registration by ID and by pattern
+//! - Route registration by ID and by pattern (plus alias patterns)
+//! - Declarative redirects between path patterns
 //! - Path resolution with pattern matching
 //! - Priority-based route ordering
-//! - Optimized lookups via indexing
+//! - Optimized lookups via an exact static index and a segment trie
 
-use crate::pattern::{RoutePattern, RoutePatternRef, RouteSegment};
-use crate::route::Route;
-use crate::url;
+use crate::args::RouteArgs;
+use crate::error::{PatternError, RegistryError, UrlForError};
+use crate::meta::RouteMeta;
+use crate::pattern::{RouteParams, RoutePattern, RoutePatternRef, RouteSegment};
+use crate::route::{Route, RouteQuery};
+use crate::url::{self, MatchPolicy};
 use makepad_live_id::*;
 use makepad_micro_serde::*;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::fmt;
 
-fn first_segment(path: &str) -> &str {
-    let trimmed = path.strip_prefix('/').unwrap_or(path);
-    if trimmed.is_empty() {
-        return "";
-    }
-    match trimmed.find('/') {
-        Some(pos) => &trimmed[..pos],
-        None => trimmed,
-    }
-}
+/// Maximum number of chained redirects followed while resolving a path.
+pub const ROUTE_MAX_REDIRECTS: u8 = 8;
 
 /// Route registry entry
 #[allow(clippy::question_mark)]
@@ -34,14 +32,404 @@ struct RouteEntry {
     priority: usize,
 }
 
+/// Candidate group used to order trie matches: patterns with a static first segment are tried
+/// before patterns starting with a param, which are tried before ones starting with a wildcard.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+enum RouteLookupGroup {
+    #[default]
+    StaticFirst,
+    Dynamic,
+    Wildcard,
+}
+
+impl RouteLookupGroup {
+    fn of(pattern: &RoutePattern) -> Self {
+        match pattern.segments.first() {
+            Some(RouteSegment::Static(_)) => Self::StaticFirst,
+            _ if pattern.segments.iter().any(RouteSegment::is_wildcard) => Self::Wildcard,
+            _ => Self::Dynamic,
+        }
+    }
+}
+
+/// Declarative redirect between two path patterns (`/old-settings/**` -> `/settings/**`).
+///
+/// Unnamed wildcards are paired by position and given internal names so their captures carry
+/// over to the target along with the named params.
+#[derive(Clone, Debug)]
+struct RouteRedirect {
+    /// Source pattern as registered (used to find the redirect again).
+    source: RoutePattern,
+    from: RoutePattern,
+    to: RoutePattern,
+    priority: usize,
+}
+
+impl RouteRedirect {
+    fn new(from: RoutePattern, to: RoutePattern) -> Self {
+        let source = from.clone();
+        let priority = from.priority();
+        Self {
+            source,
+            from: name_unnamed_wildcards(from),
+            to: name_unnamed_wildcards(to),
+            priority,
+        }
+    }
+
+    /// Params captured by `from` whenever it matches (placeholder values), for validating `to`.
+    fn guaranteed_params(&self) -> RouteParams {
+        let mut params = RouteParams::new();
+        for segment in &self.from.segments {
+            match segment {
+                RouteSegment::Dynamic {
+                    key,
+                    optional,
+                    default,
+                    ..
+                } if !optional || default.is_some() => params.add_string(*key, "_"),
+                RouteSegment::WildcardSingle { key: Some(key), .. }
+                | RouteSegment::WildcardMulti { key: Some(key), .. } => {
+                    params.add_string(*key, "_")
+                }
+                _ => {}
+            }
+        }
+        params
+    }
+}
+
+/// Replace static segments of a matched `path` with the pattern's spelling where they differ
+/// (only in case, since the path matched). Segments past the pattern (a nested prefix's tail)
+/// are kept.
+fn respell_static_segments(pattern: &RoutePattern, path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    for segment in &pattern.segments {
+        let Some(actual) = segments.next() else {
+            break;
+        };
+        out.push('/');
+        match segment {
+            RouteSegment::Static(expected) if url::decode_path_segment(actual) != *expected => {
+                out.push_str(&url::encode_path_segment(expected));
+            }
+            RouteSegment::WildcardMulti { .. } => {
+                out.push_str(actual);
+                for rest in segments.by_ref() {
+                    out.push('/');
+                    out.push_str(rest);
+                }
+            }
+            _ => out.push_str(actual),
+        }
+    }
+    for rest in segments {
+        out.push('/');
+        out.push_str(rest);
+    }
+    if out.is_empty() {
+        out.push('/');
+    }
+    out
+}
+
+/// Name unnamed wildcards `*0`, `*1`, ... in order of appearance.
+fn name_unnamed_wildcards(mut pattern: RoutePattern) -> RoutePattern {
+    let mut index = 0;
+    for segment in &mut pattern.segments {
+        if let RouteSegment::WildcardSingle { name, key }
+        | RouteSegment::WildcardMulti { name, key } = segment
+        {
+            if name.is_none() {
+                let generated = format!("*{}", index);
+                *key = Some(LiveId::from_str(&generated));
+                *name = Some(generated);
+                index += 1;
+            }
+        }
+    }
+    pattern
+}
+
 #[derive(Clone, Debug, Default)]
 struct RouteEntryMeta {
-    first_static_segment: Option<String>,
-    segment_count: usize,
-    has_wildcard: bool,
+    lookup_group: RouteLookupGroup,
     exact_static_path: Option<String>,
 }
 
+impl RouteEntryMeta {
+    fn new(pattern: &RoutePattern, policy: &MatchPolicy) -> Self {
+        let mut exact_static_path = None;
+        if pattern
+            .segments
+            .iter()
+            .all(|s| matches!(s, RouteSegment::Static(_)))
+        {
+            let mut path = String::new();
+            path.push('/');
+            for (seg_i, segment) in pattern.segments.iter().enumerate() {
+                if seg_i > 0 {
+                    path.push('/');
+                }
+                if let RouteSegment::Static(v) = segment {
+                    path.push_str(v);
+                }
+            }
+            exact_static_path = Some(policy.fold_case(&path).into_owned());
+        }
+        Self {
+            lookup_group: RouteLookupGroup::of(pattern),
+            exact_static_path,
+        }
+    }
+}
+
+/// Segment trie node. Param segments share one child regardless of name or constraint; the
+/// full pattern (including constraints) is verified once the trie has narrowed the candidates.
+#[derive(Clone, Debug, Default)]
+struct RouteTrieNode {
+    static_children: HashMap<String, usize>,
+    param_child: Option<usize>,
+    wildcard_child: Option<usize>,
+    /// Patterns that match when the path ends at this node.
+    terminals: Vec<usize>,
+    /// Patterns ending in `**` at this node (match any remaining segments, including none).
+    multi_terminals: Vec<usize>,
+}
+
+impl RouteTrieNode {
+    fn is_empty(&self) -> bool {
+        self.static_children.is_empty()
+            && self.param_child.is_none()
+            && self.wildcard_child.is_none()
+            && self.terminals.is_empty()
+            && self.multi_terminals.is_empty()
+    }
+}
+
+/// Segment trie over `by_pattern` indices (node 0 is the root).
+#[derive(Clone, Debug)]
+struct RouteTrie {
+    nodes: Vec<RouteTrieNode>,
+    /// Slots of pruned nodes, reused before `nodes` grows.
+    free: Vec<usize>,
+}
+
+impl Default for RouteTrie {
+    fn default() -> Self {
+        Self {
+            nodes: vec![RouteTrieNode::default()],
+            free: Vec::new(),
+        }
+    }
+}
+
+impl RouteTrie {
+    fn clear(&mut self) {
+        self.nodes.clear();
+        self.nodes.push(RouteTrieNode::default());
+        self.free.clear();
+    }
+
+    fn alloc(&mut self) -> usize {
+        if let Some(slot) = self.free.pop() {
+            return slot;
+        }
+        self.nodes.push(RouteTrieNode::default());
+        self.nodes.len() - 1
+    }
+
+    fn existing_child(
+        &self,
+        node: usize,
+        segment: &RouteSegment,
+        policy: &MatchPolicy,
+    ) -> Option<usize> {
+        let current = &self.nodes[node];
+        match segment {
+            RouteSegment::Static(value) => current
+                .static_children
+                .get(policy.fold_case(value).as_ref())
+                .copied(),
+            RouteSegment::Dynamic { .. } => current.param_child,
+            RouteSegment::WildcardSingle { .. } | RouteSegment::WildcardMulti { .. } => {
+                current.wildcard_child
+            }
+        }
+    }
+
+    fn child(&mut self, node: usize, segment: &RouteSegment, policy: &MatchPolicy) -> usize {
+        if let Some(child) = self.existing_child(node, segment, policy) {
+            return child;
+        }
+        let next = self.alloc();
+        let current = &mut self.nodes[node];
+        match segment {
+            RouteSegment::Static(value) => {
+                current
+                    .static_children
+                    .insert(policy.fold_case(value).into_owned(), next);
+            }
+            RouteSegment::Dynamic { .. } => current.param_child = Some(next),
+            RouteSegment::WildcardSingle { .. } | RouteSegment::WildcardMulti { .. } => {
+                current.wildcard_child = Some(next)
+            }
+        }
+        next
+    }
+
+    /// Insert pattern `idx`; static segments are case-folded when `policy` is case-insensitive.
+    fn insert(&mut self, pattern: &RoutePattern, idx: usize, policy: &MatchPolicy) {
+        let segments = &pattern.segments;
+        let mut node = 0;
+        for (i, segment) in segments.iter().enumerate() {
+            if matches!(segment, RouteSegment::WildcardMulti { .. }) {
+                self.nodes[node].multi_terminals.push(idx);
+                return;
+            }
+            // The path may end here if every remaining segment is optional.
+            if segments[i..].iter().all(RouteSegment::is_optional) {
+                self.nodes[node].terminals.push(idx);
+            }
+            node = self.child(node, segment, policy);
+        }
+        self.nodes[node].terminals.push(idx);
+    }
+
+    /// Shift indices at or above `idx` up to make room for a `by_pattern` insertion.
+    fn shift_up(&mut self, idx: usize) {
+        for node in &mut self.nodes {
+            for i in node
+                .terminals
+                .iter_mut()
+                .chain(node.multi_terminals.iter_mut())
+            {
+                if *i >= idx {
+                    *i += 1;
+                }
+            }
+        }
+    }
+
+    /// Drop pattern `idx` and shift higher indices down to follow a `by_pattern` removal.
+    ///
+    /// Nodes along `pattern`'s path that no longer lead to any pattern are unlinked and their
+    /// slots reused by later insertions.
+    fn remove(&mut self, pattern: Option<&RoutePattern>, idx: usize, policy: &MatchPolicy) {
+        let shift = |list: &mut Vec<usize>| {
+            list.retain(|&i| i != idx);
+            for i in list.iter_mut() {
+                if *i > idx {
+                    *i -= 1;
+                }
+            }
+        };
+        for node in &mut self.nodes {
+            shift(&mut node.terminals);
+            shift(&mut node.multi_terminals);
+        }
+        if let Some(pattern) = pattern {
+            self.prune(pattern, policy);
+        }
+    }
+
+    fn prune(&mut self, pattern: &RoutePattern, policy: &MatchPolicy) {
+        let mut path = Vec::new();
+        let mut node = 0;
+        for segment in &pattern.segments {
+            if matches!(segment, RouteSegment::WildcardMulti { .. }) {
+                break;
+            }
+            let Some(child) = self.existing_child(node, segment, policy) else {
+                break;
+            };
+            path.push((node, segment, child));
+            node = child;
+        }
+        for (parent, segment, child) in path.into_iter().rev() {
+            if !self.nodes[child].is_empty() {
+                break;
+            }
+            let parent = &mut self.nodes[parent];
+            match segment {
+                RouteSegment::Static(value) => {
+                    parent
+                        .static_children
+                        .remove(policy.fold_case(value).as_ref());
+                }
+                RouteSegment::Dynamic { .. } => parent.param_child = None,
+                _ => parent.wildcard_child = None,
+            }
+            self.free.push(child);
+        }
+    }
+
+    /// Collect indices of patterns whose shape can match the (decoded, case-folded) `segments`.
+    fn collect(&self, node: usize, segments: &[Cow<'_, str>], out: &mut Vec<usize>) {
+        let current = &self.nodes[node];
+        out.extend_from_slice(&current.multi_terminals);
+        let Some((first, rest)) = segments.split_first() else {
+            out.extend_from_slice(&current.terminals);
+            return;
+        };
+        if let Some(&child) = current.static_children.get(first.as_ref()) {
+            self.collect(child, rest, out);
+        }
+        if let Some(child) = current.param_child {
+            self.collect(child, rest, out);
+        }
+        if let Some(child) = current.wildcard_child {
+            self.collect(child, rest, out);
+        }
+    }
+}
+
+/// Kind of conflict detected when registering a pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RouteDiagnosticKind {
+    /// The route id was already registered; the new pattern replaces it for id lookups.
+    DuplicateRouteId,
+    /// Both patterns match exactly the same paths, so only the earlier one is ever resolved.
+    Ambiguous,
+    /// Every path the pattern matches is claimed by a higher-priority pattern.
+    Unreachable,
+}
+
+/// Conflict between two registered patterns (see `RouteRegistry::diagnostics`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RouteDiagnostic {
+    pub kind: RouteDiagnosticKind,
+    /// Route whose pattern is shadowed (or re-registered).
+    pub route_id: LiveId,
+    pub pattern: String,
+    /// Route whose pattern wins (the previous registration for `DuplicateRouteId`).
+    pub conflicting_route_id: LiveId,
+    pub conflicting_pattern: String,
+}
+
+impl fmt::Display for RouteDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            RouteDiagnosticKind::DuplicateRouteId => write!(
+                f,
+                "Route {} registered again with {} (previously {})",
+                self.route_id, self.pattern, self.conflicting_pattern
+            ),
+            RouteDiagnosticKind::Ambiguous => write!(
+                f,
+                "Pattern {} for route {} is ambiguous with {} for route {}, which takes precedence",
+                self.pattern, self.route_id, self.conflicting_pattern, self.conflicting_route_id
+            ),
+            RouteDiagnosticKind::Unreachable => write!(
+                f,
+                "Pattern {} for route {} is unreachable: shadowed by {} for route {}",
+                self.pattern, self.route_id, self.conflicting_pattern, self.conflicting_route_id
+            ),
+        }
+    }
+}
+
 /// Registry for pattern-based routes
 #[derive(Clone, Debug, Default)]
 pub struct RouteRegistry {
@@ -49,16 +437,22 @@ pub struct RouteRegistry {
     by_id: HashMap<LiveId, RouteEntry>,
     /// Routes by pattern (for path-based matching)
     by_pattern: Vec<RouteEntry>,
-    /// Exact lookup for fully-static patterns (normalized path -> route_id).
-    exact_static: HashMap<String, LiveId>,
-    /// Candidate pattern indices keyed by first static segment.
-    by_first_segment: HashMap<String, Vec<usize>>,
-    /// Candidate pattern indices for patterns without a static first segment.
-    fallback_dynamic: Vec<usize>,
-    /// Candidate pattern indices that contain wildcards and no static first segment.
-    fallback_wildcard: Vec<usize>,
+    /// Exact lookup for fully-static patterns (normalized path -> entry).
+    exact_static: HashMap<String, RouteEntry>,
+    /// Segment trie narrowing `by_pattern` candidates for a path.
+    trie: RouteTrie,
     /// Precomputed metadata for entries in `by_pattern`.
     metas: Vec<RouteEntryMeta>,
+    /// Reject conflicting registrations instead of recording diagnostics.
+    strict: bool,
+    /// Conflicts recorded by `register_pattern` (non-strict mode).
+    diagnostics: Vec<RouteDiagnostic>,
+    /// Redirects checked before pattern matching, in priority order (not serialized).
+    redirects: Vec<RouteRedirect>,
+    /// Case and slash handling for incoming paths (not serialized).
+    policy: MatchPolicy,
+    /// Metadata declared per route id (not serialized).
+    route_meta: HashMap<LiveId, RouteMeta>,
 }
 
 impl RouteRegistry {
@@ -68,10 +462,13 @@ impl RouteRegistry {
             by_id: HashMap::new(),
             by_pattern: Vec::new(),
             exact_static: HashMap::new(),
-            by_first_segment: HashMap::new(),
-            fallback_dynamic: Vec::new(),
-            fallback_wildcard: Vec::new(),
+            trie: RouteTrie::default(),
             metas: Vec::new(),
+            strict: false,
+            diagnostics: Vec::new(),
+            redirects: Vec::new(),
+            policy: MatchPolicy::default(),
+            route_meta: HashMap::new(),
         }
     }
 
@@ -85,83 +482,294 @@ impl RouteRegistry {
         self.by_id.insert(route_id, entry);
     }
 
+    /// Enable or disable strict mode.
+    ///
+    /// In strict mode `register_pattern` fails on duplicate route ids, ambiguous patterns and
+    /// unreachable patterns; otherwise they are registered and recorded in `diagnostics`.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Whether strict mode is enabled.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Set how incoming paths are compared against patterns (case, trailing and duplicate slashes).
+    pub fn set_match_policy(&mut self, policy: MatchPolicy) {
+        if self.policy != policy {
+            self.policy = policy;
+            self.rebuild_indices();
+        }
+    }
+
+    /// Current match policy.
+    pub fn match_policy(&self) -> MatchPolicy {
+        self.policy
+    }
+
+    /// Conflicts recorded since the last `take_diagnostics`.
+    pub fn diagnostics(&self) -> &[RouteDiagnostic] {
+        &self.diagnostics
+    }
+
+    /// Drain recorded conflicts.
+    pub fn take_diagnostics(&mut self) -> Vec<RouteDiagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
     /// Register a route pattern
-    pub fn register_pattern(&mut self, pattern: &str, route_id: LiveId) -> Result<(), String> {
+    pub fn register_pattern(
+        &mut self,
+        pattern: &str,
+        route_id: LiveId,
+    ) -> Result<(), RegistryError> {
+        self.insert_pattern(pattern, route_id, true)
+    }
+
+    /// Register an additional pattern that resolves to `route_id`.
+    ///
+    /// Unlike `register_pattern`, an alias does not replace the route's pattern used for id
+    /// lookups and `url_for`, so `/me` can resolve to `user_profile` while URLs keep `/user/:id`.
+    pub fn register_alias(&mut self, pattern: &str, route_id: LiveId) -> Result<(), RegistryError> {
+        self.insert_pattern(pattern, route_id, false)
+    }
+
+    fn insert_pattern(
+        &mut self,
+        pattern: &str,
+        route_id: LiveId,
+        canonical: bool,
+    ) -> Result<(), RegistryError> {
         let route_pattern = RoutePattern::parse(pattern)?;
         let priority = route_pattern.priority();
+        let pos = self.insert_position(priority);
+
+        let mut diagnostics = self.diagnose(&route_pattern, route_id, pos, canonical);
+        if self.strict && !diagnostics.is_empty() {
+            return Err(RegistryError::Conflict(diagnostics.swap_remove(0)));
+        }
+
         let entry = RouteEntry {
             route_id,
             pattern: Some(RoutePatternRef::new(route_pattern)),
             priority,
         };
+        if canonical {
+            self.by_id.insert(route_id, entry.clone());
+        }
+        self.insert_pattern_index(pos, entry);
+        self.diagnostics.extend(diagnostics);
+        Ok(())
+    }
 
-        self.by_id.insert(route_id, entry.clone());
+    /// Insertion point keeping `by_pattern` sorted by priority (lower value = higher priority).
+    fn insert_position(&self, priority: usize) -> usize {
+        self.by_pattern
+            .iter()
+            .position(|e| e.priority > priority)
+            .unwrap_or(self.by_pattern.len())
+    }
+
+    /// Register a route pattern for a typed `RouteArgs` route.
+    ///
+    /// Fails with `RegistryError::Args` if the pattern does not capture the params `T` reads.
+    pub fn register_args<T: RouteArgs>(
+        &mut self,
+        pattern: &str,
+        route_id: LiveId,
+    ) -> Result<(), RegistryError> {
+        T::validate_pattern(&RoutePattern::parse(pattern)?).map_err(RegistryError::Args)?;
+        self.register_pattern(pattern, route_id)
+    }
+
+    /// Find conflicts between `pattern` (to be inserted at `pos`) and the registered patterns.
+    ///
+    /// `canonical` is `false` for aliases, which do not re-register the route id.
+    fn diagnose(
+        &self,
+        pattern: &RoutePattern,
+        route_id: LiveId,
+        pos: usize,
+        canonical: bool,
+    ) -> Vec<RouteDiagnostic> {
+        let mut out = Vec::new();
+        if let Some(previous) = self.by_id.get(&route_id).filter(|_| canonical) {
+            out.push(RouteDiagnostic {
+                kind: RouteDiagnosticKind::DuplicateRouteId,
+                route_id,
+                pattern: pattern.to_string(),
+                conflicting_route_id: route_id,
+                conflicting_pattern: previous
+                    .pattern
+                    .as_ref()
+                    .map(|p| p.to_string())
+                    .unwrap_or_default(),
+            });
+        }
+
+        let is_exact = |p: &RoutePattern| {
+            p.segments
+                .iter()
+                .all(|s| matches!(s, RouteSegment::Static(_)))
+        };
+        let new_rank = (RouteLookupGroup::of(pattern), pos);
+        for (idx, entry) in self.by_pattern.iter().enumerate() {
+            let Some(existing) = entry.pattern.as_deref() else {
+                continue;
+            };
+            if entry.route_id == route_id {
+                continue;
+            }
+            let existing_rank = (
+                RouteLookupGroup::of(existing),
+                idx + usize::from(idx >= pos),
+            );
+            let ((earlier, earlier_id), (later, later_id)) = if existing_rank < new_rank {
+                ((existing, entry.route_id), (pattern, route_id))
+            } else {
+                ((pattern, route_id), (existing, entry.route_id))
+            };
+            // Fully static patterns resolve through the exact index before anything else.
+            if is_exact(later) && !is_exact(earlier) {
+                continue;
+            }
+            if !earlier.covers_with_policy(later, &self.policy) {
+                continue;
+            }
+            let kind = if later.covers_with_policy(earlier, &self.policy) {
+                RouteDiagnosticKind::Ambiguous
+            } else {
+                RouteDiagnosticKind::Unreachable
+            };
+            out.push(RouteDiagnostic {
+                kind,
+                route_id: later_id,
+                pattern: later.to_string(),
+                conflicting_route_id: earlier_id,
+                conflicting_pattern: earlier.to_string(),
+            });
+        }
+        out
+    }
 
-        // Insert in sorted order by priority (lower priority value = higher priority)
-        // Find insertion point
+    /// Register a redirect from paths matching `from` to the path built from `to`.
+    ///
+    /// Params captured by `from` fill the same-named params of `to`; unnamed wildcards are
+    /// paired by position (`/old-settings/**` -> `/settings/**` keeps the tail). Registering
+    /// the same `from` again replaces its target. Fails with `RegistryError::Redirect` if `to`
+    /// needs a param that `from` does not always capture.
+    pub fn register_redirect(&mut self, from: &str, to: &str) -> Result<(), RegistryError> {
+        let redirect = RouteRedirect::new(RoutePattern::parse(from)?, RoutePattern::parse(to)?);
+        redirect
+            .to
+            .try_format_path(&redirect.guaranteed_params())
+            .map_err(RegistryError::Redirect)?;
+
+        self.redirects.retain(|r| r.source != redirect.source);
         let pos = self
-            .by_pattern
+            .redirects
             .iter()
-            .position(|e| e.priority > priority)
-            .unwrap_or(self.by_pattern.len());
-        self.by_pattern.insert(pos, entry);
-        self.rebuild_indices();
+            .position(|r| r.priority > redirect.priority)
+            .unwrap_or(self.redirects.len());
+        self.redirects.insert(pos, redirect);
         Ok(())
     }
 
+    /// Remove the redirect registered for `from`. Returns `false` if there was none.
+    pub fn unregister_redirect(&mut self, from: &str) -> Result<bool, PatternError> {
+        let source = RoutePattern::parse(from)?;
+        let before = self.redirects.len();
+        self.redirects.retain(|r| r.source != source);
+        Ok(self.redirects.len() != before)
+    }
+
+    /// Whether any redirects are registered.
+    pub fn has_redirects(&self) -> bool {
+        !self.redirects.is_empty()
+    }
+
+    /// Apply registered redirects to `path` until none matches.
+    ///
+    /// Returns `path` unchanged (borrowed) if no redirect applies, or `None` if the chain loops
+    /// or exceeds [`ROUTE_MAX_REDIRECTS`].
+    pub fn follow_redirects<'a>(&self, path: &'a str) -> Option<Cow<'a, str>> {
+        let mut current = Cow::Borrowed(path);
+        let mut hops = 0;
+        while let Some(next) = self.redirect_target(&current) {
+            if hops == ROUTE_MAX_REDIRECTS {
+                return None;
+            }
+            hops += 1;
+            current = Cow::Owned(next);
+        }
+        Some(current)
+    }
+
+    /// Target of the first redirect matching `path`.
+    fn redirect_target(&self, path: &str) -> Option<String> {
+        if self.redirects.is_empty() {
+            return None;
+        }
+        let normalized = self.policy.normalize_path(path)?;
+        self.redirects.iter().find_map(|redirect| {
+            let params = redirect
+                .from
+                .matches_with_policy(&normalized, &self.policy)?;
+            redirect.to.try_format_path(&params).ok()
+        })
+    }
+
     /// Resolve a path to a route (exact static match first, then pattern match).
+    ///
+    /// Redirects are applied first; a redirect loop resolves to `None`, as does a path rejected
+    /// by the match policy.
     pub fn resolve_path(&self, path: &str) -> Option<Route> {
-        let normalized = url::normalize_path_cow(path);
+        self.resolve_redirected_path(&self.follow_redirects(path)?)
+    }
+
+    /// Resolve a path that already went through `follow_redirects`, without applying them again.
+    pub fn resolve_redirected_path(&self, path: &str) -> Option<Route> {
+        let normalized = self.policy.normalize_path(path)?;
         let normalized = normalized.as_ref();
 
-        if let Some(route_id) = self.exact_static.get(normalized).copied() {
-            let pattern = self.by_id.get(&route_id).and_then(|e| e.pattern.clone());
+        if let Some(entry) = self
+            .exact_static
+            .get(self.policy.fold_case(normalized).as_ref())
+        {
             return Some(Route {
-                id: route_id,
+                id: entry.route_id,
                 params: Default::default(),
                 query: Default::default(),
                 hash: String::new(),
-                pattern,
+                pattern: entry.pattern.clone(),
             });
         }
 
-        let first = first_segment(normalized);
+        let segments: Vec<Cow<'_, str>> = normalized
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(|segment| match url::decode_path_segment(segment) {
+                Cow::Borrowed(segment) => self.policy.fold_case(segment),
+                Cow::Owned(segment) => Cow::Owned(self.policy.fold_case(&segment).into_owned()),
+            })
+            .collect();
+        let mut candidates = Vec::new();
+        self.trie.collect(0, &segments, &mut candidates);
+        candidates.sort_unstable_by_key(|&idx| {
+            let group = self.metas.get(idx).map(|m| m.lookup_group);
+            (group.unwrap_or_default(), idx)
+        });
+        candidates.dedup();
 
-        if let Some(candidates) = self.by_first_segment.get(first) {
-            for &idx in candidates {
-                let Some(entry) = self.by_pattern.get(idx) else {
-                    continue;
-                };
-                if let Some(meta) = self.metas.get(idx) {
-                    debug_assert_eq!(meta.first_static_segment.as_deref(), Some(first));
-                    let _ = (meta.segment_count, meta.has_wildcard, &meta.exact_static_path);
-                }
-                let Some(ref pattern) = entry.pattern else {
-                    continue;
-                };
-                if let Some(params) = pattern.matches(normalized) {
-                    return Some(Route {
-                        id: entry.route_id,
-                        params,
-                        query: Default::default(),
-                        hash: String::new(),
-                        pattern: Some(pattern.clone()),
-                    });
-                }
-            }
-        }
-
-        for &idx in &self.fallback_dynamic {
+        for idx in candidates {
             let Some(entry) = self.by_pattern.get(idx) else {
                 continue;
             };
-            if let Some(meta) = self.metas.get(idx) {
-                let _ = (meta.segment_count, meta.has_wildcard, &meta.exact_static_path);
-            }
             let Some(ref pattern) = entry.pattern else {
                 continue;
             };
-            if let Some(params) = pattern.matches(normalized) {
+            if let Some(params) = pattern.matches_with_policy(normalized, &self.policy) {
                 return Some(Route {
                     id: entry.route_id,
                     params,
@@ -172,28 +780,65 @@ impl RouteRegistry {
             }
         }
 
-        for &idx in &self.fallback_wildcard {
-            let Some(entry) = self.by_pattern.get(idx) else {
-                continue;
-            };
-            if let Some(meta) = self.metas.get(idx) {
-                let _ = (meta.segment_count, meta.has_wildcard, &meta.exact_static_path);
-            }
-            let Some(ref pattern) = entry.pattern else {
-                continue;
-            };
-            if let Some(params) = pattern.matches(normalized) {
-                return Some(Route {
-                    id: entry.route_id,
-                    params,
-                    query: Default::default(),
-                    hash: String::new(),
-                    pattern: Some(pattern.clone()),
-                });
+        None
+    }
+
+    /// Canonical form of `path` if it differs from `path` itself.
+    ///
+    /// The canonical form follows redirects, drops duplicate and trailing slashes and, when
+    /// matching case-insensitively, uses the registered spelling of static segments. Query and
+    /// hash are not part of it. Returns `None` if `path` is canonical or does not resolve.
+    pub fn canonical_path(&self, path: &str) -> Option<String> {
+        let redirected = self.follow_redirects(path)?;
+        let route = self.resolve_redirected_path(&redirected)?;
+        let canonical = self.canonical_spelling(route.pattern.as_deref(), &redirected)?;
+        (canonical != url::path_part(path)).then_some(canonical)
+    }
+
+    /// Canonical form of a redirected `path` matched by `pattern`, fully or as a nested prefix.
+    ///
+    /// Slashes are collapsed and, when matching case-insensitively, the pattern's static
+    /// segments get their registered spelling; segments past the pattern keep theirs. Returns
+    /// `None` if the match policy rejects `path`.
+    pub fn canonical_spelling(&self, pattern: Option<&RoutePattern>, path: &str) -> Option<String> {
+        let normalized = self.policy.normalize_path(path)?;
+        Some(match pattern {
+            Some(pattern) if !self.policy.case_sensitive => {
+                respell_static_segments(pattern, &normalized)
             }
-        }
+            _ => normalized.into_owned(),
+        })
+    }
 
-        None
+    /// Build the path for a registered route from its pattern and `params`.
+    pub fn path_for(&self, route_id: LiveId, params: &RouteParams) -> Result<String, UrlForError> {
+        self.get_pattern(route_id)
+            .ok_or(UrlForError::UnknownRoute { route_id })?
+            .try_format_path(params)
+    }
+
+    /// Build a full URL (`/user/42?tab=posts#bio`) for a registered route.
+    ///
+    /// `hash` may be given with or without its leading `#`.
+    pub fn url_for(
+        &self,
+        route_id: LiveId,
+        params: &RouteParams,
+        query: &RouteQuery,
+        hash: &str,
+    ) -> Result<String, UrlForError> {
+        let path = self.path_for(route_id, params)?;
+        Ok(url::build_url(&path, &query.data, hash))
+    }
+
+    /// Build a full URL for a registered route from typed `RouteArgs`.
+    pub fn url_for_args<T: RouteArgs>(
+        &self,
+        route_id: LiveId,
+        args: &T,
+        hash: &str,
+    ) -> Result<String, UrlForError> {
+        self.url_for(route_id, &args.to_params(), &args.to_query(), hash)
     }
 
     /// Check if a route ID is registered
@@ -201,83 +846,224 @@ impl RouteRegistry {
         self.by_id.contains_key(&route_id)
     }
 
+    /// Attach metadata to a route id, replacing any previous metadata.
+    ///
+    /// The route does not need a pattern; empty metadata removes the entry.
+    pub fn set_meta(&mut self, route_id: LiveId, meta: RouteMeta) {
+        if meta.is_empty() {
+            self.route_meta.remove(&route_id);
+        } else {
+            self.route_meta.insert(route_id, meta);
+        }
+    }
+
+    /// Metadata attached to a route id.
+    pub fn meta(&self, route_id: LiveId) -> Option<&RouteMeta> {
+        self.route_meta.get(&route_id)
+    }
+
     /// Get pattern for a route ID (if registered with a pattern).
     pub fn get_pattern(&self, route_id: LiveId) -> Option<&RoutePatternRef> {
         self.by_id.get(&route_id).and_then(|e| e.pattern.as_ref())
     }
 
-    fn rebuild_indices(&mut self) {
-        self.exact_static.clear();
-        self.by_first_segment.clear();
-        self.fallback_dynamic.clear();
-        self.fallback_wildcard.clear();
-        self.metas.clear();
+    /// Remove a route, its metadata and every pattern registered for it.
+    ///
+    /// Returns `false` if the route was not registered.
+    pub fn unregister_route(&mut self, route_id: LiveId) -> bool {
+        self.route_meta.remove(&route_id);
+        self.remove_route_patterns(route_id)
+    }
 
-        for (idx, entry) in self.by_pattern.iter().enumerate() {
-            let Some(pattern) = entry.pattern.as_ref() else {
-                continue;
-            };
+    /// Remove a route's patterns and their diagnostics, keeping its metadata.
+    fn remove_route_patterns(&mut self, route_id: LiveId) -> bool {
+        let removed = self.by_id.remove(&route_id).is_some();
+        self.diagnostics
+            .retain(|d| d.route_id != route_id && d.conflicting_route_id != route_id);
+        let mut idx = self.by_pattern.len();
+        while idx > 0 {
+            idx -= 1;
+            if self.by_pattern[idx].route_id == route_id {
+                self.remove_pattern_index(idx);
+            }
+        }
+        removed
+    }
 
-            let segment_count = pattern.segments.len();
-            let has_wildcard = pattern
-                .segments
-                .iter()
-                .any(|s| matches!(s, RouteSegment::WildcardSingle | RouteSegment::WildcardMulti));
-            let first_static_segment = match pattern.segments.first() {
-                Some(RouteSegment::Static(first)) => Some(first.clone()),
-                _ => None,
-            };
-            let mut meta = RouteEntryMeta {
-                first_static_segment: first_static_segment.clone(),
-                segment_count,
-                has_wildcard,
-                exact_static_path: None,
-            };
+    /// Remove every registration of `pattern` and return the affected route ids.
+    ///
+    /// A route whose only pattern is removed is unregistered; otherwise it keeps its remaining patterns.
+    pub fn unregister_pattern(&mut self, pattern: &str) -> Result<Vec<LiveId>, PatternError> {
+        let target = RoutePattern::parse(pattern)?;
+        let target_source = target.to_string();
+        self.diagnostics
+            .retain(|d| d.pattern != target_source && d.conflicting_pattern != target_source);
+        let mut affected = Vec::new();
+        let mut idx = self.by_pattern.len();
+        while idx > 0 {
+            idx -= 1;
+            let entry = &self.by_pattern[idx];
+            if entry.pattern.as_deref() != Some(&target) {
+                continue;
+            }
+            let route_id = entry.route_id;
+            self.remove_pattern_index(idx);
+            if !affected.contains(&route_id) {
+                affected.push(route_id);
+            }
+        }
 
-            // Build exact static lookup.
-            if pattern
-                .segments
+        for route_id in &affected {
+            let points_at_target = self
+                .by_id
+                .get(route_id)
+                .is_some_and(|e| e.pattern.as_deref() == Some(&target));
+            if !points_at_target {
+                continue;
+            }
+            match self
+                .by_pattern
                 .iter()
-                .all(|s| matches!(s, RouteSegment::Static(_)))
+                .rev()
+                .find(|e| e.route_id == *route_id)
             {
-                let mut path = String::new();
-                path.push('/');
-                for (seg_i, segment) in pattern.segments.iter().enumerate() {
-                    if seg_i > 0 {
-                        path.push('/');
-                    }
-                    if let RouteSegment::Static(v) = segment {
-                        path.push_str(v);
-                    }
+                Some(remaining) => {
+                    self.by_id.insert(*route_id, remaining.clone());
                 }
-                meta.exact_static_path = Some(path.clone());
-                self.exact_static.entry(path).or_insert(entry.route_id);
-            }
-
-            // Index by first segment (if static).
-            match first_static_segment {
-                Some(first) => {
-                    self.by_first_segment
-                        .entry(first)
-                        .or_default()
-                        .push(idx);
+                None => {
+                    self.by_id.remove(route_id);
                 }
-                None if has_wildcard => self.fallback_wildcard.push(idx),
-                None => self.fallback_dynamic.push(idx),
             }
-            self.metas.push(meta);
         }
+        Ok(affected)
     }
-}
-
-impl SerBin for RouteRegistry {
-    fn ser_bin(&self, s: &mut Vec<u8>) {
-        self.by_id.ser_bin(s);
-        self.by_pattern.ser_bin(s);
-    }
-}
 
-impl DeBin for RouteRegistry {
+    /// Re-point a route to a new pattern, replacing all of its existing patterns (its metadata is
+    /// kept).
+    ///
+    /// The registry is left unchanged if the new pattern fails to parse or is rejected in strict mode.
+    pub fn replace_pattern(
+        &mut self,
+        route_id: LiveId,
+        pattern: &str,
+    ) -> Result<(), RegistryError> {
+        let route_pattern = RoutePattern::parse(pattern)?;
+        let priority = route_pattern.priority();
+        // `diagnose` skips the route's own patterns, so the new pattern is checked before the
+        // old ones are removed and a rejected pattern leaves the registry untouched.
+        let mut diagnostics = self.diagnose(
+            &route_pattern,
+            route_id,
+            self.insert_position(priority),
+            false,
+        );
+        if self.strict && !diagnostics.is_empty() {
+            return Err(RegistryError::Conflict(diagnostics.swap_remove(0)));
+        }
+
+        self.remove_route_patterns(route_id);
+        let entry = RouteEntry {
+            route_id,
+            pattern: Some(RoutePatternRef::new(route_pattern)),
+            priority,
+        };
+        self.by_id.insert(route_id, entry.clone());
+        self.insert_pattern_index(self.insert_position(priority), entry);
+        self.diagnostics.extend(diagnostics);
+        Ok(())
+    }
+
+    /// Insert `entry` at `by_pattern[pos]`, updating the lookup indices in place.
+    fn insert_pattern_index(&mut self, pos: usize, entry: RouteEntry) {
+        if pos < self.by_pattern.len() {
+            self.trie.shift_up(pos);
+        }
+        let meta = match entry.pattern.as_deref() {
+            Some(pattern) => {
+                self.trie.insert(pattern, pos, &self.policy);
+                RouteEntryMeta::new(pattern, &self.policy)
+            }
+            None => RouteEntryMeta::default(),
+        };
+
+        // An earlier pattern with the same static path keeps the exact index entry.
+        if let Some(path) = &meta.exact_static_path {
+            let shadowed = self.exact_static.contains_key(path)
+                && self.metas[..pos]
+                    .iter()
+                    .any(|m| m.exact_static_path.as_ref() == Some(path));
+            if !shadowed {
+                self.exact_static.insert(path.clone(), entry.clone());
+            }
+        }
+        self.metas.insert(pos, meta);
+        self.by_pattern.insert(pos, entry);
+    }
+
+    /// Remove `by_pattern[idx]`, updating the lookup indices in place.
+    fn remove_pattern_index(&mut self, idx: usize) {
+        let entry = self.by_pattern.remove(idx);
+        self.trie
+            .remove(entry.pattern.as_deref(), idx, &self.policy);
+        let meta = if idx < self.metas.len() {
+            self.metas.remove(idx)
+        } else {
+            RouteEntryMeta::default()
+        };
+
+        let Some(path) = meta.exact_static_path else {
+            return;
+        };
+        if self.exact_static.get(&path).map(|e| e.route_id) != Some(entry.route_id) {
+            return;
+        }
+        // Fall back to the next pattern with the same static path, in priority order.
+        let replacement = self
+            .metas
+            .iter()
+            .position(|m| m.exact_static_path.as_deref() == Some(path.as_str()))
+            .and_then(|i| self.by_pattern.get(i))
+            .cloned();
+        match replacement {
+            Some(replacement) => {
+                self.exact_static.insert(path, replacement);
+            }
+            None => {
+                self.exact_static.remove(&path);
+            }
+        }
+    }
+
+    fn rebuild_indices(&mut self) {
+        self.exact_static.clear();
+        self.trie.clear();
+        self.metas.clear();
+
+        for (idx, entry) in self.by_pattern.iter().enumerate() {
+            let Some(pattern) = entry.pattern.as_ref() else {
+                self.metas.push(RouteEntryMeta::default());
+                continue;
+            };
+            let meta = RouteEntryMeta::new(pattern, &self.policy);
+            if let Some(path) = &meta.exact_static_path {
+                self.exact_static
+                    .entry(path.clone())
+                    .or_insert_with(|| entry.clone());
+            }
+            self.trie.insert(pattern, idx, &self.policy);
+            self.metas.push(meta);
+        }
+    }
+}
+
+impl SerBin for RouteRegistry {
+    fn ser_bin(&self, s: &mut Vec<u8>) {
+        self.by_id.ser_bin(s);
+        self.by_pattern.ser_bin(s);
+    }
+}
+
+impl DeBin for RouteRegistry {
     fn de_bin(o: &mut usize, d: &[u8]) -> Result<Self, DeBinErr> {
         let by_id = <HashMap<LiveId, RouteEntry>>::de_bin(o, d)?;
         let by_pattern = <Vec<RouteEntry>>::de_bin(o, d)?;
@@ -285,10 +1071,13 @@ impl DeBin for RouteRegistry {
             by_id,
             by_pattern,
             exact_static: HashMap::new(),
-            by_first_segment: HashMap::new(),
-            fallback_dynamic: Vec::new(),
-            fallback_wildcard: Vec::new(),
+            trie: RouteTrie::default(),
             metas: Vec::new(),
+            strict: false,
+            diagnostics: Vec::new(),
+            redirects: Vec::new(),
+            policy: MatchPolicy::default(),
+            route_meta: HashMap::new(),
         };
         out.rebuild_indices();
         Ok(out)
@@ -337,10 +1126,13 @@ impl DeRon for RouteRegistry {
             by_id: by_id.unwrap_or_default(),
             by_pattern: by_pattern.unwrap_or_default(),
             exact_static: HashMap::new(),
-            by_first_segment: HashMap::new(),
-            fallback_dynamic: Vec::new(),
-            fallback_wildcard: Vec::new(),
+            trie: RouteTrie::default(),
             metas: Vec::new(),
+            strict: false,
+            diagnostics: Vec::new(),
+            redirects: Vec::new(),
+            policy: MatchPolicy::default(),
+            route_meta: HashMap::new(),
         };
         out.rebuild_indices();
         Ok(out)
@@ -350,6 +1142,7 @@ impl DeRon for RouteRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::url::{DuplicateSlashes, TrailingSlash};
 
     #[test]
     fn test_route_registry_register_pattern() {
@@ -405,4 +1198,677 @@ mod tests {
         let route = registry.resolve_path("/user/123/posts").unwrap();
         assert_eq!(route.id, live_id!(user_wildcard));
     }
+
+    #[test]
+    fn test_route_registry_optional_segment_single_route() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/posts/:page=1", live_id!(posts))
+            .unwrap();
+
+        let route = registry.resolve_path("/posts").unwrap();
+        assert_eq!(route.id, live_id!(posts));
+        assert_eq!(route.get_param_u64(live_id!(page)), Some(1));
+
+        let route = registry.resolve_path("/posts/4").unwrap();
+        assert_eq!(route.id, live_id!(posts));
+        assert_eq!(route.get_param_u64(live_id!(page)), Some(4));
+    }
+
+    #[test]
+    fn test_route_registry_trie_dynamic_first_segments() {
+        let mut registry = RouteRegistry::new();
+        for i in 0..64 {
+            registry
+                .register_pattern(
+                    &format!("/:org/d{}/:id", i),
+                    LiveId::from_str(&format!("dyn_{}", i)),
+                )
+                .unwrap();
+        }
+        registry
+            .register_pattern("/:org/**rest", live_id!(org_fallback))
+            .unwrap();
+
+        let route = registry.resolve_path("/acme/d42/7").unwrap();
+        assert_eq!(route.id, LiveId::from_str("dyn_42"));
+        assert_eq!(route.get_param_u64(live_id!(id)), Some(7));
+
+        let route = registry.resolve_path("/acme/d99/7").unwrap();
+        assert_eq!(route.id, live_id!(org_fallback));
+
+        assert!(registry.resolve_path("/").is_none());
+    }
+
+    #[test]
+    fn test_route_registry_trie_keeps_group_order() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/:section/profile", live_id!(section_profile))
+            .unwrap();
+        registry
+            .register_pattern("/user/*", live_id!(user_any))
+            .unwrap();
+        registry
+            .register_pattern("/user/:id/:tab?", live_id!(user_tab))
+            .unwrap();
+
+        // Static-first patterns are tried before param-first ones, regardless of priority.
+        let route = registry.resolve_path("/user/profile").unwrap();
+        assert_eq!(route.id, live_id!(user_tab));
+
+        let route = registry.resolve_path("/team/profile").unwrap();
+        assert_eq!(route.id, live_id!(section_profile));
+
+        let route = registry.resolve_path("/user/5/posts").unwrap();
+        assert_eq!(route.id, live_id!(user_tab));
+    }
+
+    #[test]
+    fn test_route_registry_incremental_indices_match_rebuild() {
+        let mut registry = RouteRegistry::new();
+        // Mixed priorities so most registrations land before existing entries.
+        for (pattern, id) in [
+            ("/docs/**", live_id!(docs)),
+            ("/user/:id", live_id!(user)),
+            ("/about", live_id!(about)),
+            ("/user/*", live_id!(user_any)),
+            ("/:section/profile", live_id!(section_profile)),
+            ("/user/profile", live_id!(user_profile)),
+            ("/about", live_id!(about_again)),
+            ("/docs/intro", live_id!(docs_intro)),
+        ] {
+            registry.register_pattern(pattern, id).unwrap();
+        }
+        let mut rebuilt = registry.clone();
+        rebuilt.rebuild_indices();
+
+        for path in [
+            "/about",
+            "/user/7",
+            "/user/profile",
+            "/team/profile",
+            "/docs/intro",
+            "/docs/a/b",
+            "/missing",
+        ] {
+            assert_eq!(
+                registry.resolve_path(path).map(|r| r.id),
+                rebuilt.resolve_path(path).map(|r| r.id),
+                "{}",
+                path
+            );
+        }
+        assert_eq!(
+            registry.resolve_path("/about").map(|r| r.id),
+            Some(live_id!(about))
+        );
+
+        registry.unregister_route(live_id!(about));
+        assert_eq!(
+            registry.resolve_path("/about").map(|r| r.id),
+            Some(live_id!(about_again))
+        );
+    }
+
+    #[test]
+    fn test_route_registry_unregister_route() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/settings", live_id!(settings))
+            .unwrap();
+        registry
+            .register_pattern("/user/:id", live_id!(user))
+            .unwrap();
+        registry
+            .register_pattern("/beta/:id", live_id!(beta))
+            .unwrap();
+
+        assert!(registry.unregister_route(live_id!(user)));
+        assert!(!registry.unregister_route(live_id!(user)));
+        assert!(!registry.has_route(live_id!(user)));
+        assert!(registry.resolve_path("/user/1").is_none());
+
+        // Remaining routes still resolve through the updated indices.
+        assert_eq!(
+            registry.resolve_path("/settings").unwrap().id,
+            live_id!(settings)
+        );
+        assert_eq!(registry.resolve_path("/beta/2").unwrap().id, live_id!(beta));
+    }
+
+    #[test]
+    fn test_route_registry_unregister_pattern() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/about", live_id!(about_v1))
+            .unwrap();
+        registry
+            .register_pattern("/about", live_id!(about_v2))
+            .unwrap();
+        registry
+            .register_pattern("/user/:id", live_id!(user))
+            .unwrap();
+
+        assert_eq!(
+            registry.resolve_path("/about").unwrap().id,
+            live_id!(about_v1)
+        );
+        let removed = registry.unregister_pattern("/about").unwrap();
+        assert_eq!(removed, vec![live_id!(about_v2), live_id!(about_v1)]);
+        assert!(registry.resolve_path("/about").is_none());
+        assert!(!registry.has_route(live_id!(about_v1)));
+
+        assert!(registry.unregister_pattern("/missing").unwrap().is_empty());
+        assert!(registry.unregister_pattern("").is_err());
+        assert_eq!(registry.resolve_path("/user/3").unwrap().id, live_id!(user));
+    }
+
+    #[test]
+    fn test_route_registry_replace_pattern() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/profile/:id", live_id!(profile))
+            .unwrap();
+        registry.set_meta(
+            live_id!(profile),
+            RouteMeta::new().with(live_id!(requires_auth), true),
+        );
+
+        registry
+            .replace_pattern(live_id!(profile), "/u/:id")
+            .unwrap();
+        assert!(registry
+            .meta(live_id!(profile))
+            .is_some_and(|meta| meta.flag(live_id!(requires_auth))));
+        assert!(registry.resolve_path("/profile/1").is_none());
+        let route = registry.resolve_path("/u/1").unwrap();
+        assert_eq!(route.id, live_id!(profile));
+        assert_eq!(
+            registry
+                .get_pattern(live_id!(profile))
+                .map(|p| p.segments.len()),
+            Some(2)
+        );
+
+        assert!(registry
+            .replace_pattern(live_id!(profile), "/a/**/b")
+            .is_err());
+        assert_eq!(registry.resolve_path("/u/2").unwrap().id, live_id!(profile));
+
+        // A strict conflict is detected before the old pattern is removed.
+        registry
+            .register_pattern("/team/:name", live_id!(team))
+            .unwrap();
+        registry.set_strict(true);
+        assert!(matches!(
+            registry.replace_pattern(live_id!(profile), "/team/:id"),
+            Err(RegistryError::Conflict(_))
+        ));
+        assert_eq!(registry.resolve_path("/u/3").unwrap().id, live_id!(profile));
+        assert_eq!(registry.resolve_path("/team/a").unwrap().id, live_id!(team));
+        registry
+            .replace_pattern(live_id!(profile), "/u/:id/:tab?")
+            .unwrap();
+        assert_eq!(
+            registry.resolve_path("/u/3/a").unwrap().id,
+            live_id!(profile)
+        );
+    }
+
+    #[test]
+    fn test_route_registry_trie_prunes_removed_paths() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/user/:id", live_id!(user))
+            .unwrap();
+        let base = registry.trie.nodes.len();
+        registry
+            .register_pattern("/docs/:section/*/edit", live_id!(docs_edit))
+            .unwrap();
+        let grown = registry.trie.nodes.len();
+
+        registry.unregister_route(live_id!(docs_edit));
+        assert!(!registry.trie.nodes[0].static_children.contains_key("docs"));
+        assert_eq!(registry.trie.free.len(), grown - base);
+        assert_eq!(registry.resolve_path("/user/1").unwrap().id, live_id!(user));
+
+        // Pruned slots are reused instead of growing the trie.
+        registry
+            .register_pattern("/blog/:slug/*/edit", live_id!(blog_edit))
+            .unwrap();
+        assert_eq!(registry.trie.nodes.len(), grown);
+        assert!(registry.trie.free.is_empty());
+        assert_eq!(
+            registry.resolve_path("/blog/a/b/edit").unwrap().id,
+            live_id!(blog_edit)
+        );
+        assert!(registry.resolve_path("/docs/a/b/edit").is_none());
+    }
+
+    #[test]
+    fn test_route_registry_diagnostics() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/user/:id", live_id!(user_id))
+            .unwrap();
+        registry
+            .register_pattern("/user/:name", live_id!(user_name))
+            .unwrap();
+        registry
+            .register_pattern("/docs/*", live_id!(doc_any))
+            .unwrap();
+        registry
+            .register_pattern("/docs/:page?", live_id!(doc_page))
+            .unwrap();
+        registry
+            .register_pattern("/user/me", live_id!(user_me))
+            .unwrap();
+        registry
+            .register_pattern("/user/:id<u64>", live_id!(user_num))
+            .unwrap();
+
+        let diagnostics = registry.take_diagnostics();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].kind, RouteDiagnosticKind::Ambiguous);
+        assert_eq!(diagnostics[0].route_id, live_id!(user_name));
+        assert_eq!(diagnostics[0].conflicting_route_id, live_id!(user_id));
+        assert_eq!(diagnostics[0].conflicting_pattern, "/user/:id");
+        assert_eq!(diagnostics[1].kind, RouteDiagnosticKind::Unreachable);
+        assert_eq!(diagnostics[1].route_id, live_id!(doc_any));
+        assert_eq!(diagnostics[1].conflicting_route_id, live_id!(doc_page));
+        assert!(registry.diagnostics().is_empty());
+
+        registry
+            .register_pattern("/profile", live_id!(user_me))
+            .unwrap();
+        assert_eq!(
+            registry.diagnostics()[0].kind,
+            RouteDiagnosticKind::DuplicateRouteId
+        );
+    }
+
+    #[test]
+    fn test_route_registry_strict_mode() {
+        let mut registry = RouteRegistry::new();
+        registry.set_strict(true);
+        registry
+            .register_pattern("/user/:id", live_id!(user_id))
+            .unwrap();
+
+        let err = registry
+            .register_pattern("/user/:name", live_id!(user_name))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            RegistryError::Conflict(RouteDiagnostic {
+                kind: RouteDiagnosticKind::Ambiguous,
+                ..
+            })
+        ));
+        assert!(!registry.has_route(live_id!(user_name)));
+        assert!(registry
+            .register_pattern("/user/:id/posts", live_id!(user_id))
+            .is_err());
+
+        registry
+            .register_pattern("/team/:id", live_id!(team))
+            .unwrap();
+        assert!(registry
+            .replace_pattern(live_id!(team), "/user/:slug")
+            .is_err());
+        assert_eq!(registry.resolve_path("/team/1").unwrap().id, live_id!(team));
+        assert!(registry.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_route_registry_url_for() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/user/:id/:tab?", live_id!(user_profile))
+            .unwrap();
+        registry
+            .register_pattern("/files/*", live_id!(files))
+            .unwrap();
+
+        let mut params = RouteParams::new();
+        params.add_string(live_id!(id), "42");
+        let mut query = RouteQuery::new();
+        query.set("tab", "posts");
+        assert_eq!(
+            registry.url_for(live_id!(user_profile), &params, &query, "bio"),
+            Ok("/user/42?tab=posts#bio".to_string())
+        );
+        assert_eq!(
+            registry.path_for(live_id!(user_profile), &params),
+            Ok("/user/42".to_string())
+        );
+
+        assert_eq!(
+            registry.path_for(live_id!(user_profile), &RouteParams::new()),
+            Err(UrlForError::MissingParam {
+                name: "id".to_string()
+            })
+        );
+        assert_eq!(
+            registry.path_for(live_id!(missing), &params),
+            Err(UrlForError::UnknownRoute {
+                route_id: live_id!(missing)
+            })
+        );
+        assert_eq!(
+            registry.path_for(live_id!(files), &params),
+            Err(UrlForError::UnnamedWildcard)
+        );
+    }
+
+    #[test]
+    fn test_route_registry_prefers_constrained_segments() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/user/:section", live_id!(user_section))
+            .unwrap();
+        registry
+            .register_pattern("/user/:id<u64>", live_id!(user_detail))
+            .unwrap();
+
+        let route = registry.resolve_path("/user/42").unwrap();
+        assert_eq!(route.id, live_id!(user_detail));
+        assert_eq!(route.get_param_u64(live_id!(id)), Some(42));
+
+        let route = registry.resolve_path("/user/settings").unwrap();
+        assert_eq!(route.id, live_id!(user_section));
+    }
+
+    #[test]
+    fn test_route_registry_named_wildcard_captures() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/docs/**rest", live_id!(docs))
+            .unwrap();
+        registry
+            .register_pattern("/files/*file", live_id!(file))
+            .unwrap();
+
+        let route = registry.resolve_path("/docs/guide/intro.md").unwrap();
+        assert_eq!(route.id, live_id!(docs));
+        assert_eq!(
+            route.get_param_string(live_id!(rest)).as_deref(),
+            Some("guide/intro.md")
+        );
+
+        let route = registry.resolve_path("/files/report.pdf").unwrap();
+        assert_eq!(route.id, live_id!(file));
+        assert_eq!(
+            route.get_param_string(live_id!(file)).as_deref(),
+            Some("report.pdf")
+        );
+    }
+
+    #[test]
+    fn test_route_registry_aliases() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/user/:id", live_id!(user_profile))
+            .unwrap();
+        registry
+            .register_alias("/u/:id", live_id!(user_profile))
+            .unwrap();
+        registry
+            .register_alias("/me", live_id!(user_profile))
+            .unwrap();
+        assert!(registry.diagnostics().is_empty());
+
+        let route = registry.resolve_path("/u/42").unwrap();
+        assert_eq!(route.id, live_id!(user_profile));
+        assert_eq!(route.get_param_u64(live_id!(id)), Some(42));
+
+        let route = registry.resolve_path("/me").unwrap();
+        assert_eq!(route.id, live_id!(user_profile));
+        assert_eq!(route.pattern.unwrap().to_string(), "/me");
+
+        // The canonical pattern is kept for reverse routing.
+        let mut params = RouteParams::new();
+        params.add_string(live_id!(id), "42");
+        assert_eq!(
+            registry.path_for(live_id!(user_profile), &params),
+            Ok("/user/42".to_string())
+        );
+
+        assert!(registry.unregister_route(live_id!(user_profile)));
+        assert!(registry.resolve_path("/me").is_none());
+    }
+
+    #[test]
+    fn test_route_registry_redirects() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/user/:id", live_id!(user_profile))
+            .unwrap();
+        registry
+            .register_pattern("/settings/**", live_id!(settings))
+            .unwrap();
+        registry.register_redirect("/profile", "/user/me").unwrap();
+        registry
+            .register_redirect("/old-settings/**", "/settings/**")
+            .unwrap();
+        registry
+            .register_redirect("/people/:id", "/user/:id")
+            .unwrap();
+
+        let route = registry.resolve_path("/profile").unwrap();
+        assert_eq!(route.id, live_id!(user_profile));
+        assert_eq!(route.get_param_string(live_id!(id)).as_deref(), Some("me"));
+
+        let route = registry.resolve_path("/people/John%20Doe").unwrap();
+        assert_eq!(
+            route.get_param_string(live_id!(id)).as_deref(),
+            Some("John Doe")
+        );
+
+        assert_eq!(
+            registry.follow_redirects("/old-settings/privacy/advanced"),
+            Some(Cow::Owned("/settings/privacy/advanced".to_string()))
+        );
+        assert_eq!(
+            registry.follow_redirects("/old-settings"),
+            Some(Cow::Owned("/settings".to_string()))
+        );
+        assert!(matches!(
+            registry.follow_redirects("/user/42"),
+            Some(Cow::Borrowed("/user/42"))
+        ));
+
+        assert_eq!(
+            registry.register_redirect("/people/:name", "/user/:id"),
+            Err(RegistryError::Redirect(UrlForError::MissingParam {
+                name: "id".to_string()
+            }))
+        );
+
+        assert_eq!(registry.unregister_redirect("/profile"), Ok(true));
+        assert!(registry.resolve_path("/profile").is_none());
+    }
+
+    #[test]
+    fn test_route_registry_redirect_loops() {
+        let mut registry = RouteRegistry::new();
+        registry.register_pattern("/b", live_id!(b)).unwrap();
+        registry.register_redirect("/a", "/b").unwrap();
+        assert_eq!(registry.resolve_path("/a").unwrap().id, live_id!(b));
+
+        registry.register_redirect("/b", "/a").unwrap();
+        assert_eq!(registry.follow_redirects("/a"), None);
+        assert!(registry.resolve_path("/a").is_none());
+
+        // Long chains are cut off at ROUTE_MAX_REDIRECTS.
+        let mut registry = RouteRegistry::new();
+        for i in 0..=ROUTE_MAX_REDIRECTS {
+            registry
+                .register_redirect(&format!("/step{}", i), &format!("/step{}", i + 1))
+                .unwrap();
+        }
+        assert!(registry.follow_redirects("/step1").is_some());
+        assert_eq!(registry.follow_redirects("/step0"), None);
+    }
+
+    #[test]
+    fn test_route_registry_match_policy() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/Settings", live_id!(settings))
+            .unwrap();
+        registry
+            .register_pattern("/user/:id", live_id!(user))
+            .unwrap();
+        assert!(registry.resolve_path("/settings").is_none());
+
+        registry.set_match_policy(MatchPolicy::case_insensitive());
+        assert_eq!(
+            registry.resolve_path("/SETTINGS").unwrap().id,
+            live_id!(settings)
+        );
+        let route = registry.resolve_path("/USER/Ab").unwrap();
+        assert_eq!(route.id, live_id!(user));
+        // Parameter values keep their spelling.
+        assert_eq!(route.get_param_string(live_id!(id)).as_deref(), Some("Ab"));
+
+        registry.set_match_policy(MatchPolicy {
+            trailing_slash: TrailingSlash::Strict,
+            duplicate_slashes: DuplicateSlashes::Reject,
+            ..MatchPolicy::default()
+        });
+        assert!(registry.resolve_path("/Settings").is_some());
+        assert!(registry.resolve_path("/Settings/").is_none());
+        assert!(registry.resolve_path("//user/1").is_none());
+        assert!(registry.resolve_path("/user//1").is_none());
+
+        registry.set_match_policy(MatchPolicy::default());
+        assert!(registry.resolve_path("/Settings/").is_some());
+        assert!(registry.resolve_path("/user//1").is_some());
+    }
+
+    #[test]
+    fn test_route_registry_diagnostics_follow_match_policy() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/Settings", live_id!(settings))
+            .unwrap();
+        registry
+            .register_pattern("/settings", live_id!(settings_lower))
+            .unwrap();
+        assert!(registry.diagnostics().is_empty());
+
+        let mut registry = RouteRegistry::new();
+        registry.set_match_policy(MatchPolicy::case_insensitive());
+        registry
+            .register_pattern("/Settings", live_id!(settings))
+            .unwrap();
+        registry
+            .register_pattern("/settings", live_id!(settings_lower))
+            .unwrap();
+        registry
+            .register_pattern("/USER/:id", live_id!(user))
+            .unwrap();
+        registry
+            .register_pattern("/user/:name", live_id!(user_name))
+            .unwrap();
+        let kinds: Vec<_> = registry
+            .diagnostics()
+            .iter()
+            .map(|d| (d.kind, d.route_id, d.conflicting_route_id))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (
+                    RouteDiagnosticKind::Ambiguous,
+                    live_id!(settings_lower),
+                    live_id!(settings)
+                ),
+                (
+                    RouteDiagnosticKind::Ambiguous,
+                    live_id!(user_name),
+                    live_id!(user)
+                ),
+            ]
+        );
+
+        registry.set_strict(true);
+        assert!(matches!(
+            registry.register_pattern("/SETTINGS", live_id!(settings_upper)),
+            Err(RegistryError::Conflict(_))
+        ));
+    }
+
+    #[test]
+    fn test_route_registry_canonical_path() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/Settings/**", live_id!(settings))
+            .unwrap();
+        registry
+            .register_pattern("/user/:id", live_id!(user))
+            .unwrap();
+        registry.register_redirect("/me", "/user/self").unwrap();
+
+        assert_eq!(registry.canonical_path("/user/1"), None);
+        assert_eq!(
+            registry.canonical_path("/user//1/"),
+            Some("/user/1".to_string())
+        );
+        assert_eq!(
+            registry.canonical_path("/me?x=1"),
+            Some("/user/self".to_string())
+        );
+        assert_eq!(registry.canonical_path("/nope/"), None);
+
+        registry.set_match_policy(MatchPolicy::case_insensitive());
+        assert_eq!(
+            registry.canonical_path("/settings/Deep/Path"),
+            Some("/Settings/Deep/Path".to_string())
+        );
+        assert_eq!(
+            registry.canonical_path("/USER/Ab"),
+            Some("/user/Ab".to_string())
+        );
+
+        // A nested prefix is respelled; the tail it hands to the child keeps its spelling.
+        let prefix = RoutePattern::parse("/Admin/:section").unwrap();
+        assert_eq!(
+            registry
+                .canonical_spelling(Some(&prefix), "/admin//Users/Edit/")
+                .as_deref(),
+            Some("/Admin/Users/Edit")
+        );
+    }
+
+    #[test]
+    fn test_route_registry_meta() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/admin", live_id!(admin))
+            .unwrap();
+        registry.set_meta(
+            live_id!(admin),
+            RouteMeta::new()
+                .with(live_id!(title), "Admin")
+                .with(live_id!(requires_auth), true),
+        );
+        // Routes without a pattern can carry metadata too.
+        registry.set_meta(
+            live_id!(not_found),
+            RouteMeta::new().with(live_id!(title), "404"),
+        );
+
+        let meta = registry.meta(live_id!(admin)).unwrap();
+        assert_eq!(meta.get_str(live_id!(title)), Some("Admin"));
+        assert!(meta.flag(live_id!(requires_auth)));
+        assert!(registry.meta(live_id!(not_found)).is_some());
+
+        registry.set_meta(live_id!(not_found), RouteMeta::new());
+        assert!(registry.meta(live_id!(not_found)).is_none());
+        assert!(registry.unregister_route(live_id!(admin)));
+        assert!(registry.meta(live_id!(admin)).is_none());
+    }
 }
@@ -1,10 +1,13 @@
 #![allow(clippy::question_mark)]
 
+use crate::args::RouteArgs;
+use crate::error::{PatternError, RouteArgsError};
 use crate::pattern::{RouteParams, RoutePattern, RoutePatternRef};
 use crate::url;
 use makepad_live_id::*;
 use makepad_micro_serde::*;
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::sync::Arc;
 
 /// Represents a route in the application
 #[allow(clippy::question_mark)]
@@ -22,12 +25,11 @@ pub struct Route {
     pub pattern: Option<RoutePatternRef>,
 }
 
-/// Query parameters stored as a string map.
-#[allow(clippy::question_mark)]
-#[derive(Clone, Debug, Default, PartialEq, Eq, SerBin, DeBin, SerRon, DeRon)]
+/// Query parameters stored as an ordered multimap (`?tag=a&tag=b` keeps both values).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct RouteQuery {
-    /// Query parameters stored as key-value string pairs.
-    pub data: HashMap<String, String>,
+    /// Query parameters as key-value string pairs, in insertion order. Keys may repeat.
+    pub data: Vec<(String, String)>,
 }
 
 impl Route {
@@ -54,7 +56,7 @@ impl Route {
     }
 
     /// Create a route from a pattern string
-    pub fn from_pattern(pattern: &str, id: LiveId) -> Result<Self, String> {
+    pub fn from_pattern(pattern: &str, id: LiveId) -> Result<Self, PatternError> {
         let route_pattern = RoutePattern::parse(pattern)?;
         Ok(Self {
             id,
@@ -71,30 +73,41 @@ impl Route {
         self
     }
 
-    /// Get a parameter value by key
+    /// Get a parameter value by key as a `LiveId` (a hash for string values; see
+    /// `RouteParams::get`, and prefer `get_param_str` for values captured from a path).
     pub fn get_param(&self, key: LiveId) -> Option<LiveId> {
         self.params.get(key)
     }
 
+    /// Add a string parameter to the route (not interned).
+    pub fn param_string(mut self, key: LiveId, value: impl Into<Arc<str>>) -> Self {
+        self.params.add_string(key, value);
+        self
+    }
+
+    /// Get a parameter value as text (borrowed for string values).
+    pub fn get_param_str(&self, key: LiveId) -> Option<Cow<'_, str>> {
+        self.params.get_str(key)
+    }
+
     /// Get a parameter value as a `String`.
     pub fn get_param_string(&self, key: LiveId) -> Option<String> {
-        self.get_param(key)?
-            .as_string(|id_str| id_str.map(|s| s.to_string()))
+        self.get_param_str(key).map(Cow::into_owned)
     }
 
     /// Get a parameter value as `i64` (parsed).
     pub fn get_param_i64(&self, key: LiveId) -> Option<i64> {
-        self.get_param_string(key)?.parse().ok()
+        self.get_param_str(key)?.parse().ok()
     }
 
     /// Get a parameter value as `u64` (parsed).
     pub fn get_param_u64(&self, key: LiveId) -> Option<u64> {
-        self.get_param_string(key)?.parse().ok()
+        self.get_param_str(key)?.parse().ok()
     }
 
     /// Get a parameter value as `bool` (accepts 1/0, true/false, yes/no, on/off).
     pub fn get_param_bool(&self, key: LiveId) -> Option<bool> {
-        match self.get_param_string(key)?.to_ascii_lowercase().as_str() {
+        match self.get_param_str(key)?.to_ascii_lowercase().as_str() {
             "1" | "true" | "yes" | "on" => Some(true),
             "0" | "false" | "no" | "off" => Some(false),
             _ => None,
@@ -103,19 +116,34 @@ impl Route {
 
     /// Get a parameter value as `f64` (parsed).
     pub fn get_param_f64(&self, key: LiveId) -> Option<f64> {
-        self.get_param_string(key)?.parse().ok()
+        self.get_param_str(key)?.parse().ok()
+    }
+
+    /// Read the params and query as a typed `RouteArgs` value.
+    pub fn args<T: RouteArgs>(&self) -> Result<T, RouteArgsError> {
+        T::from_route(self)
     }
 
-    /// Build a query string from the stored query map.
+    /// Build a query string from the stored query pairs, preserving their order.
     pub fn query_string(&self) -> String {
-        url::build_query_string(&self.query.data)
+        self.query.to_query_string()
     }
 
-    /// Get a query value by key.
+    /// Build a query string with keys sorted (stable output regardless of insertion order).
+    pub fn query_string_sorted(&self) -> String {
+        self.query.to_query_string_sorted()
+    }
+
+    /// Get the first query value for a key.
     pub fn query_get(&self, key: &str) -> Option<&str> {
         self.query.get(key)
     }
 
+    /// Get all query values for a key, in order.
+    pub fn query_get_all(&self, key: &str) -> Vec<&str> {
+        self.query.get_all(key)
+    }
+
     /// Get a query value as a `String`.
     pub fn query_get_string(&self, key: &str) -> Option<String> {
         Some(self.query_get(key)?.to_string())
@@ -152,19 +180,60 @@ impl RouteQuery {
         Self::default()
     }
 
-    /// Get a query value by key.
+    /// Get the first value for a key.
     pub fn get(&self, key: &str) -> Option<&str> {
-        self.data.get(key).map(|v| v.as_str())
+        self.data
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Get all values for a key, in order.
+    pub fn get_all(&self, key: &str) -> Vec<&str> {
+        self.data
+            .iter()
+            .filter(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+            .collect()
+    }
+
+    /// Whether the key is present.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.data.iter().any(|(k, _)| k == key)
     }
 
-    /// Set or replace a query key.
+    /// Set a key to a single value, replacing all existing values.
+    ///
+    /// The key keeps the position of its first occurrence; new keys are appended.
     pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) {
-        self.data.insert(key.into(), value.into());
+        let key = key.into();
+        let value = value.into();
+        match self.data.iter().position(|(k, _)| *k == key) {
+            Some(pos) => {
+                self.data[pos].1 = value;
+                let mut i = pos + 1;
+                while i < self.data.len() {
+                    if self.data[i].0 == key {
+                        self.data.remove(i);
+                    } else {
+                        i += 1;
+                    }
+                }
+            }
+            None => self.data.push((key, value)),
+        }
+    }
+
+    /// Append a value for a key, keeping existing values.
+    pub fn append(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.data.push((key.into(), value.into()));
     }
 
-    /// Remove a query key, returning true if present.
+    /// Remove all values for a key, returning true if present.
     pub fn remove(&mut self, key: &str) -> bool {
-        self.data.remove(key).is_some()
+        let len = self.data.len();
+        self.data.retain(|(k, _)| k != key);
+        self.data.len() != len
     }
 
     /// Clear all query keys.
@@ -172,12 +241,112 @@ impl RouteQuery {
         self.data.clear();
     }
 
-    /// Build a query map from a raw query string (`?a=1&b=2`).
+    /// Number of key-value pairs (repeated keys count once per value).
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Whether there are no query pairs.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Iterate over key-value pairs in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.data.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Sort pairs by key. The sort is stable, so repeated keys keep their relative order.
+    pub fn sort(&mut self) {
+        self.data.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+
+    /// Build a query from a raw query string (`?a=1&b=2`).
     pub fn from_query_string(query: &str) -> Self {
         Self {
             data: url::parse_query_map(query),
         }
     }
+
+    /// Build a query string (`?a=1&b=2`) preserving pair order.
+    pub fn to_query_string(&self) -> String {
+        url::build_query_string(&self.data)
+    }
+
+    /// Build a query string with keys sorted.
+    pub fn to_query_string_sorted(&self) -> String {
+        url::build_query_string_sorted(&self.data)
+    }
+}
+
+// Serialized in the same shape as the previous `HashMap<String, String>` representation
+// (a length-prefixed list of pairs / a RON map), so persisted state stays readable.
+impl SerBin for RouteQuery {
+    fn ser_bin(&self, s: &mut Vec<u8>) {
+        self.data.ser_bin(s);
+    }
+}
+
+impl DeBin for RouteQuery {
+    fn de_bin(o: &mut usize, d: &[u8]) -> Result<Self, DeBinErr> {
+        Ok(Self {
+            data: Vec::<(String, String)>::de_bin(o, d)?,
+        })
+    }
+}
+
+impl SerRon for RouteQuery {
+    fn ser_ron(&self, d: usize, s: &mut SerRonState) {
+        s.st_pre();
+        s.field(d + 1, "data");
+        s.out.push_str("{\n");
+        for (k, v) in &self.data {
+            s.indent(d + 2);
+            k.ser_ron(d + 2, s);
+            s.out.push(':');
+            v.ser_ron(d + 2, s);
+            s.conl();
+        }
+        s.indent(d + 1);
+        s.out.push('}');
+        s.out.push('\n');
+        s.st_post(d);
+    }
+}
+
+impl DeRon for RouteQuery {
+    fn de_ron(s: &mut DeRonState, i: &mut std::str::Chars) -> Result<Self, DeRonErr> {
+        s.paren_open(i)?;
+        let mut data = Vec::new();
+        loop {
+            match s.tok {
+                DeRonTok::ParenClose => {
+                    s.paren_close(i)?;
+                    break;
+                }
+                DeRonTok::Ident => {
+                    let key = s.identbuf.clone();
+                    s.ident(i)?;
+                    s.colon(i)?;
+                    if key != "data" {
+                        return Err(s.err_token("data"));
+                    }
+                    s.curly_open(i)?;
+                    while s.tok != DeRonTok::CurlyClose {
+                        let k = String::de_ron(s, i)?;
+                        s.colon(i)?;
+                        let v = String::de_ron(s, i)?;
+                        s.eat_comma_curly(i)?;
+                        data.push((k, v));
+                    }
+                    s.curly_close(i)?;
+                    s.eat_comma_paren(i)?;
+                }
+                _ => return Err(s.err_token("Identifier or )")),
+            }
+        }
+        Ok(Self { data })
+    }
 }
 
 /// Macro to create routes easily
@@ -202,6 +371,49 @@ mod tests {
     use super::*;
     use makepad_live_id::live_id;
 
+    #[test]
+    fn test_route_query_multi_values() {
+        let mut query = RouteQuery::from_query_string("?tag=a&sort=new&tag=b");
+        assert_eq!(query.get("tag"), Some("a"));
+        assert_eq!(query.get_all("tag"), vec!["a", "b"]);
+        assert_eq!(query.to_query_string(), "?tag=a&sort=new&tag=b");
+        assert_eq!(query.to_query_string_sorted(), "?sort=new&tag=a&tag=b");
+
+        query.append("tag", "c");
+        assert_eq!(query.get_all("tag"), vec!["a", "b", "c"]);
+
+        query.set("tag", "z");
+        assert_eq!(query.to_query_string(), "?tag=z&sort=new");
+
+        assert!(query.remove("tag"));
+        assert!(!query.contains_key("tag"));
+        assert_eq!(query.len(), 1);
+    }
+
+    #[test]
+    fn test_route_query_reads_previous_map_format() {
+        use std::collections::HashMap;
+
+        let mut map = HashMap::new();
+        map.insert("tab".to_string(), "settings".to_string());
+
+        let bin = map.serialize_bin();
+        let query = RouteQuery::deserialize_bin(&bin).unwrap();
+        assert_eq!(query.get("tab"), Some("settings"));
+
+        let ron = format!("(data:{})", map.serialize_ron());
+        let query = RouteQuery::deserialize_ron(&ron).unwrap();
+        assert_eq!(query.get("tab"), Some("settings"));
+
+        let mut query = RouteQuery::new();
+        query.append("tag", "a");
+        query.append("tag", "b");
+        let de = RouteQuery::deserialize_ron(&query.serialize_ron()).unwrap();
+        assert_eq!(de, query);
+        let de = RouteQuery::deserialize_bin(&query.serialize_bin()).unwrap();
+        assert_eq!(de, query);
+    }
+
     #[test]
     fn test_route_from_pattern() {
         let route = Route::from_pattern("/user/:id", live_id!(user_profile)).unwrap();
@@ -1,14 +1,20 @@
-use crate::navigation::NavigationHistory;
+use crate::args::RouteArgs;
+use crate::error::{NavigateError, PatternError, RegistryError, UrlForError};
+use crate::meta::RouteMeta;
+use crate::navigation::{HistoryLimit, HistoryStacks, NavigationHistory};
+use crate::pattern::RouteParams;
 use crate::registry::RouteRegistry;
-use crate::route::Route;
+use crate::route::{Route, RouteQuery};
 use makepad_live_id::*;
 use makepad_micro_serde::*;
 
 /// Router configuration and state
 #[derive(Clone, Debug, SerBin, DeBin, SerRon, DeRon)]
 pub struct Router {
-    /// Navigation history
+    /// Navigation history (of the active stack)
     pub history: NavigationHistory,
+    /// Named history stacks (tabs) other than the active one.
+    pub stacks: HistoryStacks,
     /// Whether to persist router state
     pub persist_state: bool,
     /// Registry for pattern-based navigation (non-visual, usable headless).
@@ -19,6 +25,7 @@ impl Default for Router {
     fn default() -> Self {
         Self {
             history: NavigationHistory::empty(),
+            stacks: HistoryStacks::default(),
             persist_state: false,
             route_registry: RouteRegistry::default(),
         }
@@ -30,6 +37,7 @@ impl Router {
     pub fn new(initial_route: Route) -> Self {
         Self {
             history: NavigationHistory::new(initial_route),
+            stacks: HistoryStacks::default(),
             persist_state: false,
             route_registry: RouteRegistry::default(),
         }
@@ -39,6 +47,7 @@ impl Router {
     pub fn with_persistence(initial_route: Route) -> Self {
         Self {
             history: NavigationHistory::new(initial_route),
+            stacks: HistoryStacks::default(),
             persist_state: true,
             route_registry: RouteRegistry::default(),
         }
@@ -134,26 +143,122 @@ impl Router {
         self.history.depth()
     }
 
+    /// Bound the history depth of every stack; see [`NavigationHistory::set_limit`].
+    pub fn set_history_limit(&mut self, limit: HistoryLimit) {
+        self.history.set_limit(limit);
+        for stack in self.stacks.iter_mut() {
+            stack.history.set_limit(limit);
+        }
+    }
+
+    pub fn history_limit(&self) -> HistoryLimit {
+        self.history.limit()
+    }
+
     /// Register a pattern-based route for `navigate_by_path`.
     pub fn register_route_pattern(
         &mut self,
         pattern: &str,
         route_id: LiveId,
-    ) -> Result<(), String> {
+    ) -> Result<(), RegistryError> {
         self.route_registry.register_pattern(pattern, route_id)
     }
 
+    /// Register a route pattern for a typed `RouteArgs` route; see [`RouteRegistry::register_args`].
+    pub fn register_route_args<T: RouteArgs>(
+        &mut self,
+        pattern: &str,
+        route_id: LiveId,
+    ) -> Result<(), RegistryError> {
+        self.route_registry.register_args::<T>(pattern, route_id)
+    }
+
+    /// Register an additional pattern for a route; see [`RouteRegistry::register_alias`].
+    pub fn register_route_alias(
+        &mut self,
+        pattern: &str,
+        route_id: LiveId,
+    ) -> Result<(), RegistryError> {
+        self.route_registry.register_alias(pattern, route_id)
+    }
+
+    /// Redirect paths matching `from` to `to`; see [`RouteRegistry::register_redirect`].
+    pub fn register_route_redirect(&mut self, from: &str, to: &str) -> Result<(), RegistryError> {
+        self.route_registry.register_redirect(from, to)
+    }
+
+    /// Remove the redirect registered for `from`.
+    pub fn unregister_route_redirect(&mut self, from: &str) -> Result<bool, PatternError> {
+        self.route_registry.unregister_redirect(from)
+    }
+
+    /// Attach metadata to a route; see [`RouteRegistry::set_meta`].
+    pub fn set_route_meta(&mut self, route_id: LiveId, meta: RouteMeta) {
+        self.route_registry.set_meta(route_id, meta);
+    }
+
+    /// Metadata attached to a route.
+    pub fn route_meta(&self, route_id: LiveId) -> Option<&RouteMeta> {
+        self.route_registry.meta(route_id)
+    }
+
+    /// Remove a route and all of its patterns from path resolution.
+    pub fn unregister_route(&mut self, route_id: LiveId) -> bool {
+        self.route_registry.unregister_route(route_id)
+    }
+
+    /// Remove every registration of a pattern, returning the affected route ids.
+    pub fn unregister_route_pattern(&mut self, pattern: &str) -> Result<Vec<LiveId>, PatternError> {
+        self.route_registry.unregister_pattern(pattern)
+    }
+
+    /// Re-point a route to a new pattern, replacing its existing patterns.
+    pub fn replace_route_pattern(
+        &mut self,
+        route_id: LiveId,
+        pattern: &str,
+    ) -> Result<(), RegistryError> {
+        self.route_registry.replace_pattern(route_id, pattern)
+    }
+
     /// Navigate using a path string, using the registered route patterns.
     /// Returns the resolved `Route` on success.
-    pub fn navigate_by_path(&mut self, path: &str) -> Result<Route, String> {
-        let route = self
-            .route_registry
-            .resolve_path(path)
-            .ok_or_else(|| format!("No route found for path: {}", path))?;
+    pub fn navigate_by_path(&mut self, path: &str) -> Result<Route, NavigateError> {
+        if self.route_registry.follow_redirects(path).is_none() {
+            return Err(NavigateError::RedirectLoop {
+                path: path.to_string(),
+            });
+        }
+        let route = self.route_registry.resolve_path(path).ok_or_else(|| {
+            NavigateError::NoRouteForPath {
+                path: path.to_string(),
+            }
+        })?;
         self.navigate(route.clone());
         Ok(route)
     }
 
+    /// Build a URL for a registered route (reverse routing); see [`RouteRegistry::url_for`].
+    pub fn url_for(
+        &self,
+        route_id: LiveId,
+        params: &RouteParams,
+        query: &RouteQuery,
+        hash: &str,
+    ) -> Result<String, UrlForError> {
+        self.route_registry.url_for(route_id, params, query, hash)
+    }
+
+    /// Build a URL for a registered route from typed `RouteArgs`.
+    pub fn url_for_args<T: RouteArgs>(
+        &self,
+        route_id: LiveId,
+        args: &T,
+        hash: &str,
+    ) -> Result<String, UrlForError> {
+        self.route_registry.url_for_args(route_id, args, hash)
+    }
+
     /// Push a route onto the stack (alias of `navigate`).
     pub fn push(&mut self, route: Route) {
         self.navigate(route);
@@ -178,6 +283,50 @@ impl Router {
     pub fn set_stack(&mut self, stack: Vec<Route>) {
         self.history.set_stack(stack);
     }
+
+    /// Id of the active history stack (`LiveId(0)` until another stack is switched to).
+    pub fn active_stack(&self) -> LiveId {
+        self.stacks.active
+    }
+
+    pub fn has_stack(&self, stack_id: LiveId) -> bool {
+        self.stacks.contains(stack_id)
+    }
+
+    /// Add a named history stack rooted at `root` without switching to it.
+    ///
+    /// Returns `false` if a stack with that id exists.
+    pub fn add_stack(&mut self, stack_id: LiveId, root: Route) -> bool {
+        let mut history = NavigationHistory::new(root);
+        history.set_limit(self.history.limit());
+        self.stacks.insert(stack_id, history)
+    }
+
+    /// Make `stack_id` the active stack; the previous one keeps its entries and position.
+    ///
+    /// Returns `false` if the stack is unknown or already active.
+    pub fn switch_stack(&mut self, stack_id: LiveId) -> bool {
+        self.stacks.switch(stack_id, &mut self.history)
+    }
+
+    /// Remove an inactive stack.
+    pub fn remove_stack(&mut self, stack_id: LiveId) -> Option<NavigationHistory> {
+        self.stacks.remove(stack_id)
+    }
+
+    /// Ids of all history stacks, active first.
+    pub fn stack_ids(&self) -> Vec<LiveId> {
+        self.stacks.ids()
+    }
+
+    /// History of a stack (active or not).
+    pub fn stack_history(&self, stack_id: LiveId) -> Option<&NavigationHistory> {
+        if stack_id == self.stacks.active {
+            Some(&self.history)
+        } else {
+            self.stacks.get(stack_id)
+        }
+    }
 }
 
 /// Router actions for event handling
@@ -195,6 +344,15 @@ pub enum RouterAction {
     Reset(Route),
     /// Route changed notification
     RouteChanged { from: Option<LiveId>, to: LiveId },
+    /// The active history stack changed
+    StackSwitched(LiveId),
+    /// An overlay route was shown above the current page
+    OverlayPresented(LiveId),
+    /// An overlay route was left (closed or navigated away from)
+    OverlayDismissed(LiveId),
+    /// A path navigation landed on a different path than requested (a redirect or
+    /// `canonical_redirects`); hosts mirroring the URL should replace it with `to`
+    PathRewritten { from: String, to: String },
 }
 
 #[cfg(test)]
@@ -211,6 +369,25 @@ mod tests {
         assert_eq!(router.depth(), 2);
     }
 
+    #[test]
+    fn test_router_stacks() {
+        let mut router = Router::new(Route::new(live_id!(feed)));
+        router.navigate_to(live_id!(post));
+        assert!(router.add_stack(live_id!(profile), Route::new(live_id!(profile))));
+        assert!(!router.switch_stack(live_id!(missing)));
+
+        assert!(router.switch_stack(live_id!(profile)));
+        assert_eq!(router.active_stack(), live_id!(profile));
+        assert_eq!(router.current_route_id(), Some(live_id!(profile)));
+        assert!(!router.can_go_back());
+        router.navigate_to(live_id!(edit_profile));
+
+        assert!(router.switch_stack(LiveId(0)));
+        assert_eq!(router.current_route_id(), Some(live_id!(post)));
+        assert!(router.back());
+        assert_eq!(router.stack_history(live_id!(profile)).unwrap().depth(), 2);
+    }
+
     #[test]
     fn test_router_back() {
         let mut router = Router::new(Route::new(live_id!(home)));
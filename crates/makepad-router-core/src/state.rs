@@ -1,14 +1,90 @@
 #![allow(clippy::question_mark)]
 
-use crate::navigation::NavigationHistory;
+use crate::navigation::{HistoryStacks, NavigationHistory};
 use makepad_micro_serde::*;
 
 /// Serializable router state (history + optional URL override).
 #[allow(clippy::question_mark)]
-#[derive(Clone, Debug, Default, PartialEq, Eq, SerBin, DeBin, SerRon, DeRon)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, SerRon)]
 pub struct RouterState {
     /// Navigation history stack.
     pub history: NavigationHistory,
     /// Optional URL/path override used when displaying the not-found route.
     pub url_path_override: Option<String>,
+    /// Named history stacks; `None` when only the initial stack exists.
+    pub stacks: Option<HistoryStacks>,
+}
+
+/// Leading marker of the binary format with `stacks`. State saved before named stacks existed
+/// starts with the history's entry count, which can never be `u64::MAX`, so both formats can
+/// be read.
+const ROUTER_STATE_STACKS_MARKER: u64 = u64::MAX;
+
+impl SerBin for RouterState {
+    fn ser_bin(&self, s: &mut Vec<u8>) {
+        ROUTER_STATE_STACKS_MARKER.ser_bin(s);
+        self.history.ser_bin(s);
+        self.url_path_override.ser_bin(s);
+        self.stacks.ser_bin(s);
+    }
+}
+
+// State saved before named stacks existed has no `stacks` field; it loads as `None`.
+impl DeBin for RouterState {
+    fn de_bin(o: &mut usize, d: &[u8]) -> Result<Self, DeBinErr> {
+        let start = *o;
+        if u64::de_bin(o, d)? != ROUTER_STATE_STACKS_MARKER {
+            *o = start;
+            let history = NavigationHistory::de_bin(o, d)?;
+            let url_path_override = Option::<String>::de_bin(o, d)?;
+            return Ok(Self {
+                history,
+                url_path_override,
+                stacks: None,
+            });
+        }
+        let history = NavigationHistory::de_bin(o, d)?;
+        let url_path_override = Option::<String>::de_bin(o, d)?;
+        let stacks = Option::<HistoryStacks>::de_bin(o, d)?;
+        Ok(Self {
+            history,
+            url_path_override,
+            stacks,
+        })
+    }
+}
+
+impl DeRon for RouterState {
+    fn de_ron(s: &mut DeRonState, i: &mut std::str::Chars) -> Result<Self, DeRonErr> {
+        s.paren_open(i)?;
+        let mut history: Option<NavigationHistory> = None;
+        let mut url_path_override: Option<String> = None;
+        let mut stacks: Option<HistoryStacks> = None;
+        loop {
+            match s.tok {
+                DeRonTok::ParenClose => {
+                    s.paren_close(i)?;
+                    break;
+                }
+                DeRonTok::Ident => {
+                    let key = s.identbuf.clone();
+                    s.ident(i)?;
+                    s.colon(i)?;
+                    match key.as_str() {
+                        "history" => history = Some(NavigationHistory::de_ron(s, i)?),
+                        "url_path_override" => url_path_override = Option::<String>::de_ron(s, i)?,
+                        "stacks" => stacks = Option::<HistoryStacks>::de_ron(s, i)?,
+                        _ => return Err(s.err_token("history, url_path_override or stacks")),
+                    }
+                    s.eat_comma_paren(i)?;
+                }
+                _ => return Err(s.err_token("Identifier or )")),
+            }
+        }
+        Ok(Self {
+            history: history.unwrap_or_default(),
+            url_path_override,
+            stacks,
+        })
+    }
 }
@@ -1,5 +1,4 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
 use std::fmt;
 
 /// Parsed URL parts for router navigation.
@@ -56,8 +55,8 @@ impl RouterUrl {
         Self { path, query, hash }
     }
 
-    /// Parse the query string into a string map.
-    pub fn parse_query_map(&self) -> HashMap<String, String> {
+    /// Parse the query string into ordered key-value pairs.
+    pub fn parse_query_map(&self) -> Vec<(String, String)> {
         parse_query_map(&self.query)
     }
 }
@@ -79,24 +78,7 @@ pub fn normalize_path(input: &str) -> String {
 /// Returns a borrowed slice whenever normalization can be represented as a subslice of `input`,
 /// avoiding an allocation in the common case where the input is already a normalized path.
 pub fn normalize_path_cow(input: &str) -> Cow<'_, str> {
-    let trimmed = input.trim();
-    if trimmed.is_empty() {
-        return Cow::Borrowed("/");
-    }
-
-    let mut core = trimmed;
-    if let Some((_, after_scheme)) = trimmed.split_once("://") {
-        if let Some((_, after_host_slash)) = after_scheme.split_once('/') {
-            core = after_host_slash;
-        } else {
-            return Cow::Borrowed("/");
-        }
-    }
-
-    if let Some(pos) = core.find(['?', '#']) {
-        core = &core[..pos];
-    }
-
+    let core = path_part(input);
     if core.is_empty() {
         return Cow::Borrowed("/");
     }
@@ -118,14 +100,125 @@ pub fn normalize_path_cow(input: &str) -> Cow<'_, str> {
     Cow::Owned(out)
 }
 
-/// Parse a query string (`?a=1&b=2`) into a string map.
-pub fn parse_query_map(query: &str) -> HashMap<String, String> {
+/// Path portion of a path or URL (scheme/host, query and hash stripped; may be empty).
+pub(crate) fn path_part(input: &str) -> &str {
+    let mut core = input.trim();
+    if let Some((_, after_scheme)) = core.split_once("://") {
+        match after_scheme.split_once('/') {
+            Some((_, after_host_slash)) => core = after_host_slash,
+            None => return "",
+        }
+    }
+    if let Some(pos) = core.find(['?', '#']) {
+        core = &core[..pos];
+    }
+    core
+}
+
+/// How a trailing slash in an incoming path is treated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TrailingSlash {
+    /// `/about/` matches `/about` (the trailing slash is dropped).
+    #[default]
+    Lenient,
+    /// Paths with a trailing slash (other than `/`) do not match any pattern.
+    Strict,
+}
+
+/// How empty segments (`/a//b`) in an incoming path are treated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DuplicateSlashes {
+    /// `/a//b` matches `/a/b`.
+    #[default]
+    Collapse,
+    /// Paths containing `//` do not match any pattern.
+    Reject,
+}
+
+/// Rules for comparing incoming paths against registered patterns.
+///
+/// The default matches the historical behavior: case-sensitive static segments, trailing and
+/// duplicate slashes ignored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MatchPolicy {
+    /// Compare static segments case-sensitively (params always keep their case).
+    pub case_sensitive: bool,
+    pub trailing_slash: TrailingSlash,
+    pub duplicate_slashes: DuplicateSlashes,
+}
+
+impl Default for MatchPolicy {
+    fn default() -> Self {
+        Self {
+            case_sensitive: true,
+            trailing_slash: TrailingSlash::Lenient,
+            duplicate_slashes: DuplicateSlashes::Collapse,
+        }
+    }
+}
+
+impl MatchPolicy {
+    /// Case-insensitive matching with lenient slashes.
+    pub fn case_insensitive() -> Self {
+        Self {
+            case_sensitive: false,
+            ..Self::default()
+        }
+    }
+
+    /// Normalize `input` for matching under this policy (see `normalize_path_cow`).
+    ///
+    /// Duplicate slashes are collapsed; returns `None` if the policy rejects the path outright.
+    pub fn normalize_path<'a>(&self, input: &'a str) -> Option<Cow<'a, str>> {
+        let raw = path_part(input);
+        let has_duplicates = raw.contains("//");
+        if self.trailing_slash == TrailingSlash::Strict && raw.len() > 1 && raw.ends_with('/') {
+            return None;
+        }
+        if has_duplicates && self.duplicate_slashes == DuplicateSlashes::Reject {
+            return None;
+        }
+        let normalized = normalize_path_cow(input);
+        if !has_duplicates {
+            return Some(normalized);
+        }
+        let mut out = String::with_capacity(normalized.len());
+        for segment in normalized.split('/').filter(|s| !s.is_empty()) {
+            out.push('/');
+            out.push_str(segment);
+        }
+        if out.is_empty() {
+            out.push('/');
+        }
+        Some(Cow::Owned(out))
+    }
+
+    /// Fold the case of a (decoded) static segment or path when matching case-insensitively.
+    pub fn fold_case<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        if self.case_sensitive || !value.chars().any(char::is_uppercase) {
+            Cow::Borrowed(value)
+        } else {
+            Cow::Owned(value.to_lowercase())
+        }
+    }
+
+    /// Whether a decoded path segment equals a pattern's static segment under this policy.
+    pub fn static_segment_eq(&self, actual: &str, expected: &str) -> bool {
+        actual == expected
+            || (!self.case_sensitive && self.fold_case(actual) == self.fold_case(expected))
+    }
+}
+
+/// Parse a query string (`?a=1&b=2`) into ordered key-value pairs.
+///
+/// Repeated keys (`?tag=a&tag=b`) produce one pair per occurrence.
+pub fn parse_query_map(query: &str) -> Vec<(String, String)> {
     let q = query.trim();
     let q = q.strip_prefix('?').unwrap_or(q);
     if q.is_empty() {
-        return HashMap::new();
+        return Vec::new();
     }
-    let mut out = HashMap::new();
+    let mut out = Vec::new();
     for pair in q.split('&') {
         if pair.is_empty() {
             continue;
@@ -139,25 +232,40 @@ pub fn parse_query_map(query: &str) -> HashMap<String, String> {
             continue;
         }
         let val = decode_www_form_component(v);
-        out.insert(key, val);
+        out.push((key, val));
     }
     out
 }
 
-/// Build a stable, sorted query string from a map.
-pub fn build_query_string(map: &HashMap<String, String>) -> String {
-    if map.is_empty() {
-        return String::new();
+/// Join a path, query pairs and a hash (with or without its leading `#`) into a URL.
+pub fn build_url(path: &str, query: &[(String, String)], hash: &str) -> String {
+    let mut out = path.to_string();
+    out.push_str(&build_query_string(query));
+    if !hash.is_empty() && hash != "#" {
+        if !hash.starts_with('#') {
+            out.push('#');
+        }
+        out.push_str(hash);
     }
+    out
+}
+
+/// Build a query string from key-value pairs, preserving their order.
+pub fn build_query_string(pairs: &[(String, String)]) -> String {
+    write_query_string(pairs.iter())
+}
+
+/// Build a stable query string with keys sorted; repeated keys keep their relative order.
+pub fn build_query_string_sorted(pairs: &[(String, String)]) -> String {
+    let mut sorted: Vec<&(String, String)> = pairs.iter().collect();
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+    write_query_string(sorted.into_iter())
+}
+
+fn write_query_string<'a>(pairs: impl Iterator<Item = &'a (String, String)>) -> String {
     let mut out = String::new();
-    out.push('?');
-    let mut keys: Vec<&String> = map.keys().collect();
-    keys.sort();
-    for (i, k) in keys.iter().enumerate() {
-        if i > 0 {
-            out.push('&');
-        }
-        let v = map.get(*k).map(|s| s.as_str()).unwrap_or("");
+    for (i, (k, v)) in pairs.enumerate() {
+        out.push(if i == 0 { '?' } else { '&' });
         out.push_str(&encode_www_form_component(k));
         if !v.is_empty() {
             out.push('=');
@@ -167,45 +275,134 @@ pub fn build_query_string(map: &HashMap<String, String>) -> String {
     out
 }
 
+/// Decode a percent-encoded path segment (RFC 3986). Unlike query values, `+` is kept literally.
+/// Malformed escapes (`50%`, `%zz`) are kept as written.
+///
+/// Returns a borrowed slice when the segment contains no escapes.
+pub fn decode_path_segment(input: &str) -> Cow<'_, str> {
+    if !input.contains('%') {
+        return Cow::Borrowed(input);
+    }
+    Cow::Owned(percent_decode(input, false))
+}
+
+/// Decode one segment of a `**` capture, keeping `%2F` and `%25` escaped so the captured
+/// segments can be told apart when the value is formatted again.
+pub(crate) fn decode_tail_segment(input: &str) -> Cow<'_, str> {
+    if !input.contains('%') {
+        return Cow::Borrowed(input);
+    }
+    Cow::Owned(percent_decode_except(input, false, |b| {
+        b == b'/' || b == b'%'
+    }))
+}
+
+/// Like [`encode_path_segment`], but leaves valid `%XX` escapes as they are.
+pub(crate) fn encode_path_segment_keep_escapes(input: &str) -> Cow<'_, str> {
+    if input.bytes().all(is_path_segment_byte) {
+        return Cow::Borrowed(input);
+    }
+    let bytes = input.as_bytes();
+    let mut out = String::with_capacity(input.len());
+    for (i, &b) in bytes.iter().enumerate() {
+        if is_path_segment_byte(b) || escape_at(bytes, i).is_some() {
+            out.push(b as char);
+        } else {
+            push_escaped(&mut out, b);
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Encode a value as a single path segment, escaping every byte outside RFC 3986 `pchar`
+/// (so `/`, `?`, `#`, `%` and spaces are always escaped).
+///
+/// Returns a borrowed slice when no escaping is needed.
+pub fn encode_path_segment(input: &str) -> Cow<'_, str> {
+    if input.bytes().all(is_path_segment_byte) {
+        return Cow::Borrowed(input);
+    }
+    Cow::Owned(percent_encode(input, is_path_segment_byte, false))
+}
+
 fn decode_www_form_component(input: &str) -> String {
+    percent_decode(input, true)
+}
+
+fn encode_www_form_component(input: &str) -> String {
+    percent_encode(input, is_unreserved_byte, true)
+}
+
+fn percent_decode(input: &str, plus_as_space: bool) -> String {
+    percent_decode_except(input, plus_as_space, |_| false)
+}
+
+/// Decode `%XX` escapes except those whose byte `keep_escaped` accepts. Malformed escapes are
+/// kept literally.
+fn percent_decode_except(input: &str, plus_as_space: bool, keep_escaped: fn(u8) -> bool) -> String {
+    let input_bytes = input.as_bytes();
     let mut bytes = Vec::<u8>::with_capacity(input.len());
-    let mut iter = input.as_bytes().iter().copied().peekable();
-    while let Some(b) = iter.next() {
-        match b {
-            b'+' => bytes.push(b' '),
-            b'%' => {
-                let hi = iter.next();
-                let lo = iter.next();
-                if let (Some(hi), Some(lo)) = (hi, lo) {
-                    if let (Some(hi), Some(lo)) = (hex_val(hi), hex_val(lo)) {
-                        bytes.push((hi << 4) | lo);
-                    }
+    let mut i = 0;
+    while i < input_bytes.len() {
+        let b = input_bytes[i];
+        match (b, escape_at(input_bytes, i)) {
+            (b'+', _) if plus_as_space => bytes.push(b' '),
+            (b'%', Some(decoded)) => {
+                if keep_escaped(decoded) {
+                    bytes.extend_from_slice(&input_bytes[i..i + 3]);
+                } else {
+                    bytes.push(decoded);
                 }
+                i += 3;
+                continue;
             }
             _ => bytes.push(b),
         }
+        i += 1;
     }
     String::from_utf8(bytes).unwrap_or_else(|_| input.to_string())
 }
 
-fn encode_www_form_component(input: &str) -> String {
-    let mut out = String::new();
+/// The byte encoded by a well-formed `%XX` escape starting at `bytes[i]`.
+fn escape_at(bytes: &[u8], i: usize) -> Option<u8> {
+    if bytes.get(i) != Some(&b'%') {
+        return None;
+    }
+    let hi = hex_val(*bytes.get(i + 1)?)?;
+    let lo = hex_val(*bytes.get(i + 2)?)?;
+    Some((hi << 4) | lo)
+}
+
+fn percent_encode(input: &str, keep: fn(u8) -> bool, space_as_plus: bool) -> String {
+    let mut out = String::with_capacity(input.len());
     for &b in input.as_bytes() {
-        match b {
-            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
-                out.push(b as char)
-            }
-            b' ' => out.push('+'),
-            _ => {
-                out.push('%');
-                out.push(hex_char((b >> 4) & 0x0f));
-                out.push(hex_char(b & 0x0f));
-            }
+        if keep(b) {
+            out.push(b as char);
+        } else if b == b' ' && space_as_plus {
+            out.push('+');
+        } else {
+            push_escaped(&mut out, b);
         }
     }
     out
 }
 
+fn push_escaped(out: &mut String, b: u8) {
+    out.push('%');
+    out.push(hex_char((b >> 4) & 0x0f));
+    out.push(hex_char(b & 0x0f));
+}
+
+/// RFC 3986 `unreserved`.
+fn is_unreserved_byte(b: u8) -> bool {
+    matches!(b, b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~')
+}
+
+/// RFC 3986 `pchar` (minus `pct-encoded`): unreserved, sub-delims, `:` and `@`.
+fn is_path_segment_byte(b: u8) -> bool {
+    is_unreserved_byte(b) || b"!$&'()*+,;=:@".contains(&b)
+}
+
 fn hex_val(b: u8) -> Option<u8> {
     match b {
         b'0'..=b'9' => Some(b - b'0'),
@@ -0,0 +1,131 @@
+use makepad_router_core::makepad_live_id::*;
+use makepad_router_core::url::{decode_path_segment, encode_path_segment};
+use makepad_router_core::{RouteParams, RoutePattern, RouteRegistry};
+
+fn param(value: &str) -> LiveId {
+    LiveId::from_str(value)
+}
+
+#[test]
+fn path_segment_codec_follows_rfc3986() {
+    assert_eq!(decode_path_segment("John%20Doe"), "John Doe");
+    assert_eq!(decode_path_segment("a+b"), "a+b");
+    assert_eq!(decode_path_segment("caf%C3%A9"), "café");
+    assert!(matches!(
+        decode_path_segment("plain"),
+        std::borrow::Cow::Borrowed(_)
+    ));
+
+    assert_eq!(encode_path_segment("John Doe"), "John%20Doe");
+    assert_eq!(encode_path_segment("a/b?c#d"), "a%2Fb%3Fc%23d");
+    assert_eq!(encode_path_segment("100%"), "100%25");
+    assert_eq!(encode_path_segment("user@host:8080"), "user@host:8080");
+    assert!(matches!(
+        encode_path_segment("plain-value"),
+        std::borrow::Cow::Borrowed(_)
+    ));
+}
+
+#[test]
+fn pattern_match_decodes_param_segments() {
+    let pattern = RoutePattern::parse("/user/:name").unwrap();
+    let params = pattern.matches("/user/John%20Doe").unwrap();
+    assert_eq!(params.get(live_id!(name)), Some(param("John Doe")));
+
+    let params = pattern.matches("/user/a%2Fb").unwrap();
+    assert_eq!(params.get(live_id!(name)), Some(param("a/b")));
+
+    let docs = RoutePattern::parse("/docs/**rest").unwrap();
+    let params = docs.matches("/docs/getting%20started/intro").unwrap();
+    assert_eq!(
+        params.get(live_id!(rest)),
+        Some(param("getting started/intro"))
+    );
+}
+
+#[test]
+fn pattern_match_checks_constraints_on_decoded_values() {
+    let pattern = RoutePattern::parse("/tag/:name<(a b|c)>").unwrap();
+    assert!(pattern.matches("/tag/a%20b").is_some());
+    assert!(pattern.matches("/tag/a%2Bb").is_none());
+}
+
+#[test]
+fn format_path_encodes_param_values() {
+    let pattern = RoutePattern::parse("/user/:name").unwrap();
+    let mut params = RouteParams::new();
+    params.add_string(live_id!(name), "a/b c");
+    let path = pattern.format_path(&params).unwrap();
+    assert_eq!(path, "/user/a%2Fb%20c");
+
+    // Round-trips through matching.
+    let matched = pattern.matches(&path).unwrap();
+    assert_eq!(matched.get(live_id!(name)), Some(param("a/b c")));
+
+    let docs = RoutePattern::parse("/docs/**rest").unwrap();
+    let mut params = RouteParams::new();
+    params.add_string(live_id!(rest), "guide/hello world");
+    assert_eq!(
+        docs.format_path(&params).as_deref(),
+        Some("/docs/guide/hello%20world")
+    );
+}
+
+#[test]
+fn registry_resolves_encoded_paths() {
+    let mut registry = RouteRegistry::new();
+    registry
+        .register_pattern("/café/:id", live_id!(cafe))
+        .unwrap();
+
+    let route = registry.resolve_path("/caf%C3%A9/John%20Doe").unwrap();
+    assert_eq!(route.id, live_id!(cafe));
+    assert_eq!(
+        route.get_param_string(live_id!(id)).as_deref(),
+        Some("John Doe")
+    );
+}
+
+#[test]
+fn malformed_escapes_are_kept_literally() {
+    assert_eq!(decode_path_segment("50%"), "50%");
+    assert_eq!(decode_path_segment("%zz1"), "%zz1");
+    assert_eq!(decode_path_segment("a%2"), "a%2");
+    assert_eq!(decode_path_segment("100%25"), "100%");
+
+    let pattern = RoutePattern::parse("/user/:id").unwrap();
+    let params = pattern.matches("/user/50%").unwrap();
+    assert_eq!(params.get_str(live_id!(id)).as_deref(), Some("50%"));
+    assert_eq!(pattern.format_path(&params).as_deref(), Some("/user/50%25"));
+}
+
+#[test]
+fn multi_wildcard_round_trips_encoded_segments() {
+    let docs = RoutePattern::parse("/docs/**rest").unwrap();
+    for path in [
+        "/docs/a%2Fb/c",
+        "/docs/guide/hello%20world",
+        "/docs/100%25/x",
+        "/docs/caf%C3%A9",
+    ] {
+        let params = docs.matches(path).unwrap();
+        assert_eq!(docs.format_path(&params).as_deref(), Some(path));
+    }
+    let params = docs.matches("/docs/a%2Fb/c").unwrap();
+    assert_eq!(params.get_str(live_id!(rest)).as_deref(), Some("a%2Fb/c"));
+
+    let (params, tail) = docs.matches_prefix_with_tail("/docs/a%2Fb/c").unwrap();
+    assert_eq!(params.get_str(live_id!(rest)).as_deref(), Some("a%2Fb/c"));
+    assert_eq!(tail, "/a%2Fb/c");
+}
+
+#[test]
+fn format_path_encodes_static_segments() {
+    let pattern = RoutePattern::parse("/café/:id").unwrap();
+    let mut params = RouteParams::new();
+    params.add_string(live_id!(id), "7");
+    let path = pattern.format_path(&params).unwrap();
+    assert_eq!(path, "/caf%C3%A9/7");
+    assert!(pattern.matches(&path).is_some());
+    assert_eq!(pattern.format_base_path(&params), "/caf%C3%A9/7");
+}
@@ -0,0 +1,130 @@
+use makepad_router_core::makepad_live_id::*;
+use makepad_router_core::{RegistryError, RouteArgs, RouteArgsError, RouteRegistry};
+
+#[derive(RouteArgs, Debug, PartialEq)]
+struct UserArgs {
+    id: u64,
+    #[route(query)]
+    tab: Option<String>,
+    #[route(query = "tag")]
+    tags: Vec<String>,
+}
+
+#[derive(RouteArgs, Debug, PartialEq)]
+pub struct PostArgs {
+    #[route(param = "postId")]
+    pub post_id: String,
+    pub page: Option<u32>,
+    #[route(query)]
+    pub draft: bool,
+}
+
+#[test]
+fn route_args_read_params_and_query() {
+    let mut registry = RouteRegistry::new();
+    registry
+        .register_args::<UserArgs>("/user/:id<u64>", live_id!(user_profile))
+        .unwrap();
+
+    let mut route = registry.resolve_path("/user/42").unwrap();
+    route.query.append("tab", "posts");
+    route.query.append("tag", "a");
+    route.query.append("tag", "b c");
+
+    let args: UserArgs = route.args().unwrap();
+    assert_eq!(
+        args,
+        UserArgs {
+            id: 42,
+            tab: Some("posts".to_string()),
+            tags: vec!["a".to_string(), "b c".to_string()],
+        }
+    );
+
+    let route = registry.resolve_path("/user/7").unwrap();
+    let args: UserArgs = route.args().unwrap();
+    assert_eq!(args.tab, None);
+    assert!(args.tags.is_empty());
+}
+
+#[test]
+fn route_args_report_missing_and_invalid_values() {
+    let mut registry = RouteRegistry::new();
+    registry
+        .register_args::<PostArgs>("/post/:postId/:page?", live_id!(post))
+        .unwrap();
+
+    let route = registry.resolve_path("/post/hello%20world").unwrap();
+    assert_eq!(
+        route.args::<PostArgs>(),
+        Err(RouteArgsError::MissingQuery {
+            key: "draft".to_string()
+        })
+    );
+
+    let mut route = registry.resolve_path("/post/hello/two").unwrap();
+    route.query.append("draft", "yes");
+    assert_eq!(
+        route.args::<PostArgs>(),
+        Err(RouteArgsError::InvalidParam {
+            name: "page".to_string(),
+            value: "two".to_string()
+        })
+    );
+
+    let mut route = registry.resolve_path("/post/hello%20world/3").unwrap();
+    route.query.append("draft", "yes");
+    assert_eq!(
+        route.args::<PostArgs>().unwrap(),
+        PostArgs {
+            post_id: "hello world".to_string(),
+            page: Some(3),
+            draft: true,
+        }
+    );
+}
+
+#[test]
+fn route_args_validate_pattern_on_register() {
+    let mut registry = RouteRegistry::new();
+    assert_eq!(
+        registry.register_args::<UserArgs>("/user/:name", live_id!(user_profile)),
+        Err(RegistryError::Args(RouteArgsError::UnknownParam {
+            name: "id".to_string()
+        }))
+    );
+    assert_eq!(
+        registry.register_args::<UserArgs>("/user/:id?", live_id!(user_profile)),
+        Err(RegistryError::Args(RouteArgsError::OptionalParam {
+            name: "id".to_string()
+        }))
+    );
+    assert!(registry
+        .register_args::<UserArgs>("/user/:id=1", live_id!(user_profile))
+        .is_ok());
+    assert!(!registry.has_route(live_id!(post)));
+}
+
+#[test]
+fn route_args_build_urls() {
+    let mut registry = RouteRegistry::new();
+    registry
+        .register_args::<UserArgs>("/user/:id", live_id!(user_profile))
+        .unwrap();
+
+    let args = UserArgs {
+        id: 42,
+        tab: Some("posts".to_string()),
+        tags: vec!["a".to_string(), "b".to_string()],
+    };
+    assert_eq!(
+        registry
+            .url_for_args(live_id!(user_profile), &args, "bio")
+            .unwrap(),
+        "/user/42?tab=posts&tag=a&tag=b#bio"
+    );
+
+    // Round-trips through a route built from the args.
+    let route = args.to_route(live_id!(user_profile));
+    assert_eq!(route.args::<UserArgs>().unwrap(), args);
+}
@@ -1,7 +1,8 @@
 use makepad_router_core::makepad_live_id::*;
-use makepad_router_core::makepad_micro_serde::{DeRon, SerRon};
+use makepad_router_core::makepad_micro_serde::{DeBin, DeRon, SerBin, SerRon};
 use makepad_router_core::{
-    NavigationHistory, Route, RouteParams, RoutePattern, RoutePatternRef, RouteQuery, RouterState,
+    HistoryStacks, NavigationHistory, Route, RouteParams, RoutePattern, RoutePatternRef,
+    RouteQuery, RouterState,
 };
 
 #[test]
@@ -12,6 +13,8 @@ fn router_state_ron_roundtrip_preserves_history_and_query() {
     let mut query = RouteQuery::default();
     query.set("tab", "settings");
     query.set("empty", "");
+    query.append("tag", "a");
+    query.append("tag", "b");
 
     let route = Route {
         id: live_id!(user_profile),
@@ -27,9 +30,144 @@ fn router_state_ron_roundtrip_preserves_history_and_query() {
     let state = RouterState {
         history,
         url_path_override: Some("/admin/dashboard".to_string()),
+        stacks: None,
     };
 
     let ron = state.serialize_ron();
     let de = RouterState::deserialize_ron(&ron).unwrap();
     assert_eq!(de, state);
 }
+
+#[test]
+fn router_state_ron_roundtrip_preserves_named_stacks() {
+    let mut stacks = HistoryStacks::default();
+    let mut search = NavigationHistory::new(Route::new(live_id!(search)));
+    search.push(Route::new(live_id!(search_results)));
+    assert!(stacks.insert(live_id!(search), search));
+    let mut active = NavigationHistory::new(Route::new(live_id!(home)));
+    assert!(stacks.switch(live_id!(search), &mut active));
+
+    let state = RouterState {
+        history: active,
+        url_path_override: None,
+        stacks: Some(stacks),
+    };
+
+    let ron = state.serialize_ron();
+    let de = RouterState::deserialize_ron(&ron).unwrap();
+    assert_eq!(de, state);
+    assert_eq!(de.history.current().unwrap().id, live_id!(search_results));
+}
+
+/// `RoutePattern::parse("/user/:id/*/**")` as the baseline release wrote it, before wildcards
+/// and dynamic segments gained fields.
+fn baseline_pattern_ron() -> String {
+    format!(
+        r#"(segments:[Static("user", ), Dynamic(name:"id", key:{}), WildcardSingle, WildcardMulti, ])"#,
+        live_id!(id).0
+    )
+}
+
+fn baseline_pattern_bin() -> Vec<u8> {
+    let mut bin = Vec::new();
+    bin.extend(4u64.to_le_bytes());
+    bin.extend(0u16.to_le_bytes());
+    bin.extend(4u64.to_le_bytes());
+    bin.extend(b"user");
+    bin.extend(1u16.to_le_bytes());
+    bin.extend(2u64.to_le_bytes());
+    bin.extend(b"id");
+    bin.extend(live_id!(id).0.to_le_bytes());
+    bin.extend(2u16.to_le_bytes());
+    bin.extend(3u16.to_le_bytes());
+    bin
+}
+
+#[test]
+fn route_pattern_from_baseline_payloads_loads_old_segment_shapes() {
+    let expected = RoutePattern::parse("/user/:id/*/**").unwrap();
+    assert_eq!(
+        RoutePattern::deserialize_ron(&baseline_pattern_ron()).unwrap(),
+        expected
+    );
+    assert_eq!(
+        RoutePattern::deserialize_bin(&baseline_pattern_bin()).unwrap(),
+        expected
+    );
+
+    let current = RoutePattern::parse("/user/:id<u64>/*file/:tab=info/**rest").unwrap();
+    assert_eq!(
+        RoutePattern::deserialize_ron(&current.serialize_ron()).unwrap(),
+        current
+    );
+    assert_eq!(
+        RoutePattern::deserialize_bin(&current.serialize_bin()).unwrap(),
+        current
+    );
+}
+
+fn baseline_route_ron() -> String {
+    format!(
+        r#"(id:{}, params:{{}}, query:(data:{{}}), hash:"", pattern:Some({}))"#,
+        live_id!(user).0,
+        baseline_pattern_ron()
+    )
+}
+
+#[test]
+fn route_from_baseline_ron_loads_its_pattern() {
+    let route = Route::deserialize_ron(&baseline_route_ron()).unwrap();
+    assert_eq!(route.id, live_id!(user));
+    assert_eq!(
+        route.pattern.as_deref(),
+        Some(&RoutePattern::parse("/user/:id/*/**").unwrap())
+    );
+}
+
+#[test]
+fn router_state_from_baseline_payloads_loads_without_stacks() {
+    let mut route = Route::new(live_id!(user));
+    route.pattern = Some(RoutePatternRef::new(
+        RoutePattern::parse("/user/:id/*/**").unwrap(),
+    ));
+    let expected = RouterState {
+        history: NavigationHistory::from_parts(vec![route], 0),
+        url_path_override: Some("/user/7".to_string()),
+        stacks: None,
+    };
+
+    let ron = format!(
+        r#"(history:(stack:[{}], current_index:0), url_path_override:Some("/user/7"))"#,
+        baseline_route_ron()
+    );
+    assert_eq!(RouterState::deserialize_ron(&ron).unwrap(), expected);
+
+    // history: one route (id, empty params/query/hash, pattern), current index 0; then the
+    // override. Nothing follows it.
+    let mut bin = Vec::new();
+    bin.extend(1u64.to_le_bytes());
+    bin.extend(live_id!(user).0.to_le_bytes());
+    bin.extend([0u8; 24]);
+    bin.push(1);
+    bin.extend(baseline_pattern_bin());
+    bin.extend(0u64.to_le_bytes());
+    bin.push(1);
+    bin.extend(7u64.to_le_bytes());
+    bin.extend(b"/user/7");
+    assert_eq!(RouterState::deserialize_bin(&bin).unwrap(), expected);
+
+    let mut stacked = expected.clone();
+    let mut stacks = HistoryStacks::default();
+    assert!(stacks.insert(
+        live_id!(search),
+        NavigationHistory::new(Route::new(live_id!(search)))
+    ));
+    stacked.stacks = Some(stacks);
+    let bin = stacked.serialize_bin();
+    assert_eq!(bin[..8], u64::MAX.to_le_bytes());
+    assert_eq!(RouterState::deserialize_bin(&bin).unwrap(), stacked);
+    assert_eq!(
+        RouterState::deserialize_bin(&expected.serialize_bin()).unwrap(),
+        expected
+    );
+}
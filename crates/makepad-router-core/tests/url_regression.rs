@@ -1,5 +1,6 @@
-use makepad_router_core::url::{build_query_string, normalize_path_cow, parse_query_map, RouterUrl};
-use std::collections::HashMap;
+use makepad_router_core::url::{
+    build_query_string, build_query_string_sorted, normalize_path_cow, parse_query_map, RouterUrl,
+};
 
 #[test]
 fn router_url_parses_path_query_hash() {
@@ -26,15 +27,34 @@ fn router_url_normalizes_empty_and_missing_slash() {
 #[test]
 fn query_map_decodes_and_builds() {
     let map = parse_query_map("?q=hello+world&x=%2F&empty=&flag");
-    let mut expected = HashMap::new();
-    expected.insert("q".to_string(), "hello world".to_string());
-    expected.insert("x".to_string(), "/".to_string());
-    expected.insert("empty".to_string(), "".to_string());
-    expected.insert("flag".to_string(), "".to_string());
+    let expected = vec![
+        ("q".to_string(), "hello world".to_string()),
+        ("x".to_string(), "/".to_string()),
+        ("empty".to_string(), "".to_string()),
+        ("flag".to_string(), "".to_string()),
+    ];
     assert_eq!(map, expected);
 
     let rebuilt = build_query_string(&map);
-    assert_eq!(rebuilt, "?empty&flag&q=hello+world&x=%2F");
+    assert_eq!(rebuilt, "?q=hello+world&x=%2F&empty&flag");
+
+    let sorted = build_query_string_sorted(&map);
+    assert_eq!(sorted, "?empty&flag&q=hello+world&x=%2F");
+}
+
+#[test]
+fn query_map_keeps_repeated_keys_in_order() {
+    let map = parse_query_map("?tag=b&page=2&tag=a");
+    assert_eq!(
+        map,
+        vec![
+            ("tag".to_string(), "b".to_string()),
+            ("page".to_string(), "2".to_string()),
+            ("tag".to_string(), "a".to_string()),
+        ]
+    );
+    assert_eq!(build_query_string(&map), "?tag=b&page=2&tag=a");
+    assert_eq!(build_query_string_sorted(&map), "?page=2&tag=b&tag=a");
 }
 
 #[test]
@@ -0,0 +1,360 @@
+//! `#[derive(RouteArgs)]` for typed route arguments.
+//!
+//! The derive reads a struct with named fields from a `Route` (path params + query) and builds
+//! params/query back from it. See `makepad_router_core::args` for the runtime side.
+//!
+//! Field attributes:
+//! - (none): path param named after the field (`id: u64` reads `:id`)
+//! - `#[route(param = "postId")]`: path param with a different name
+//! - `#[route(query)]` / `#[route(query = "q")]`: query value (by field name or explicit key)
+//!
+//! Field types are `T`, `Option<T>` (absent is allowed) or, for query values, `Vec<T>`
+//! (all values of a repeated key), where `T: RouteArgValue`.
+//!
+//! The generated code only names items under `makepad_router_core::__private`. Struct attribute
+//! `#[route(crate = "path")]` sets the core crate path (default `::makepad_router_core`), e.g.
+//! `#[route(crate = "makepad_router::makepad_router_core")]`.
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+#[proc_macro_derive(RouteArgs, attributes(route))]
+pub fn derive_route_args(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(out) => out,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+/// Expansion error, reported at `span` (the derive call site unless a token is at fault).
+struct Error {
+    msg: String,
+    span: Span,
+}
+
+impl Error {
+    fn spanned(msg: impl Into<String>, span: Span) -> Self {
+        Self {
+            msg: msg.into(),
+            span,
+        }
+    }
+
+    fn to_compile_error(&self) -> TokenStream {
+        let mut msg = Literal::string(&self.msg);
+        msg.set_span(self.span);
+        let tokens = [
+            TokenTree::Ident(Ident::new("compile_error", self.span)),
+            TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+            TokenTree::Group(Group::new(
+                Delimiter::Parenthesis,
+                TokenTree::Literal(msg).into(),
+            )),
+            TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+        ];
+        tokens
+            .into_iter()
+            .map(|mut token| {
+                token.set_span(self.span);
+                token
+            })
+            .collect()
+    }
+}
+
+impl From<String> for Error {
+    fn from(msg: String) -> Self {
+        Self::spanned(msg, Span::call_site())
+    }
+}
+
+impl From<&str> for Error {
+    fn from(msg: &str) -> Self {
+        Self::spanned(msg, Span::call_site())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum FieldKind {
+    Plain,
+    Option,
+    Vec,
+}
+
+enum Source {
+    Param(String),
+    Query(String),
+}
+
+struct Field {
+    /// Field name as written (may be a raw identifier such as `r#type`).
+    ident: String,
+    kind: FieldKind,
+    source: Source,
+}
+
+/// A parsed `#[route(...)]` entry: `key` or `key = "value"`.
+struct RouteAttr {
+    key: String,
+    value: Option<String>,
+}
+
+fn expand(input: TokenStream) -> Result<TokenStream, Error> {
+    let mut tokens = input.into_iter().peekable();
+    let mut krate = "::makepad_router_core".to_string();
+
+    // Struct attributes and visibility.
+    loop {
+        match tokens.peek() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '#' => {
+                tokens.next();
+                let Some(TokenTree::Group(group)) = tokens.next() else {
+                    return Err("RouteArgs: malformed attribute".into());
+                };
+                for attr in parse_route_attr(&group)? {
+                    match (attr.key.as_str(), attr.value) {
+                        ("crate", Some(path)) => krate = path,
+                        (key, _) => {
+                            return Err(
+                                format!("RouteArgs: unknown struct attribute `{}`", key).into()
+                            )
+                        }
+                    }
+                }
+            }
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "pub" => {
+                tokens.next();
+                if matches!(tokens.peek(), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis)
+                {
+                    tokens.next();
+                }
+            }
+            _ => break,
+        }
+    }
+
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "struct" => {}
+        _ => return Err("RouteArgs can only be derived for structs".into()),
+    }
+    let (name, name_span) = match tokens.next() {
+        Some(TokenTree::Ident(ident)) => (ident.to_string(), ident.span()),
+        _ => return Err("RouteArgs: expected struct name".into()),
+    };
+    let body = match tokens.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+        Some(TokenTree::Punct(p)) if p.as_char() == '<' => {
+            return Err(Error::spanned(
+                format!("RouteArgs cannot be derived for generic struct `{}`", name),
+                name_span,
+            ))
+        }
+        _ => return Err("RouteArgs requires a struct with named fields".into()),
+    };
+
+    let fields = parse_fields(body)?;
+    Ok(generate(&krate, &name, &fields).parse().unwrap())
+}
+
+/// Parse the contents of `#[...]`; returns no entries for attributes other than `route`.
+fn parse_route_attr(group: &Group) -> Result<Vec<RouteAttr>, String> {
+    let mut inner = group.stream().into_iter();
+    match inner.next() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "route" => {}
+        _ => return Ok(Vec::new()),
+    }
+    let Some(TokenTree::Group(args)) = inner.next() else {
+        return Err("RouteArgs: expected `#[route(...)]`".into());
+    };
+
+    let mut out = Vec::new();
+    let mut args = args.stream().into_iter().peekable();
+    while let Some(token) = args.next() {
+        let key = match token {
+            TokenTree::Ident(ident) => ident.to_string(),
+            TokenTree::Punct(p) if p.as_char() == ',' => continue,
+            other => return Err(format!("RouteArgs: unexpected `{}` in #[route]", other)),
+        };
+        let mut value = None;
+        if matches!(args.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '=') {
+            args.next();
+            match args.next() {
+                Some(TokenTree::Literal(lit)) => value = Some(unquote(&lit.to_string())?),
+                _ => return Err(format!("RouteArgs: expected a string after `{} =`", key)),
+            }
+        }
+        out.push(RouteAttr { key, value });
+    }
+    Ok(out)
+}
+
+fn unquote(literal: &str) -> Result<String, String> {
+    literal
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .map(|s| s.to_string())
+        .ok_or_else(|| format!("RouteArgs: expected a string literal, found {}", literal))
+}
+
+fn parse_fields(body: Group) -> Result<Vec<Field>, String> {
+    let mut fields = Vec::new();
+    let mut tokens = body.stream().into_iter().peekable();
+    while tokens.peek().is_some() {
+        let mut source: Option<Source> = None;
+
+        // Attributes and visibility.
+        loop {
+            match tokens.peek() {
+                Some(TokenTree::Punct(p)) if p.as_char() == '#' => {
+                    tokens.next();
+                    let Some(TokenTree::Group(group)) = tokens.next() else {
+                        return Err("RouteArgs: malformed attribute".into());
+                    };
+                    for attr in parse_route_attr(&group)? {
+                        source = Some(match (attr.key.as_str(), attr.value) {
+                            ("param", Some(name)) => Source::Param(name),
+                            ("query", key) => Source::Query(key.unwrap_or_default()),
+                            (key, _) => {
+                                return Err(format!("RouteArgs: unknown field attribute `{}`", key))
+                            }
+                        });
+                    }
+                }
+                Some(TokenTree::Ident(ident)) if ident.to_string() == "pub" => {
+                    tokens.next();
+                    if matches!(tokens.peek(), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis)
+                    {
+                        tokens.next();
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        let ident = match tokens.next() {
+            Some(TokenTree::Ident(ident)) => ident.to_string(),
+            _ => return Err("RouteArgs: expected a field name".into()),
+        };
+        match tokens.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ':' => {}
+            _ => return Err(format!("RouteArgs: expected `:` after `{}`", ident)),
+        }
+
+        // Type tokens up to the next top-level comma.
+        let mut depth = 0usize;
+        let mut outer_ident: Option<String> = None;
+        let mut last_ident: Option<String> = None;
+        for token in tokens.by_ref() {
+            match &token {
+                TokenTree::Punct(p) if p.as_char() == ',' && depth == 0 => break,
+                TokenTree::Punct(p) if p.as_char() == '<' => {
+                    if depth == 0 {
+                        outer_ident = last_ident.clone();
+                    }
+                    depth += 1;
+                }
+                TokenTree::Punct(p) if p.as_char() == '>' => depth = depth.saturating_sub(1),
+                TokenTree::Ident(ident) if depth == 0 => last_ident = Some(ident.to_string()),
+                _ => {}
+            }
+        }
+        let kind = match outer_ident.as_deref() {
+            Some("Option") => FieldKind::Option,
+            Some("Vec") => FieldKind::Vec,
+            _ => FieldKind::Plain,
+        };
+
+        let plain_name = ident.trim_start_matches("r#").to_string();
+        let source = match source {
+            Some(Source::Param(name)) => Source::Param(name),
+            Some(Source::Query(key)) if key.is_empty() => Source::Query(plain_name),
+            Some(Source::Query(key)) => Source::Query(key),
+            None => Source::Param(plain_name),
+        };
+        if kind == FieldKind::Vec && matches!(source, Source::Param(_)) {
+            return Err(format!(
+                "RouteArgs: `{}`: Vec fields are only supported for query values",
+                ident
+            ));
+        }
+        fields.push(Field {
+            ident,
+            kind,
+            source,
+        });
+    }
+    Ok(fields)
+}
+
+fn generate(krate: &str, name: &str, fields: &[Field]) -> String {
+    let private = format!("{}::__private", krate);
+    let mut route_params = String::new();
+    let mut from_route = String::new();
+    let mut to_params = String::new();
+    let mut to_query = String::new();
+
+    for field in fields {
+        let ident = &field.ident;
+        match &field.source {
+            Source::Param(key) => {
+                route_params.push_str(&format!(
+                    "{}::RouteArgParam {{ name: {:?}, required: {} }},",
+                    private,
+                    key,
+                    field.kind == FieldKind::Plain
+                ));
+                let (read, write) = match field.kind {
+                    FieldKind::Option => ("param_opt", "add_param_opt"),
+                    _ => ("param", "add_param"),
+                };
+                from_route.push_str(&format!(
+                    "{}: {}::{}(route, {:?})?,",
+                    ident, private, read, key
+                ));
+                to_params.push_str(&format!(
+                    "{}::{}(&mut params, {:?}, &self.{});",
+                    private, write, key, ident
+                ));
+            }
+            Source::Query(key) => {
+                let (read, write) = match field.kind {
+                    FieldKind::Plain => ("query", "append_query"),
+                    FieldKind::Option => ("query_opt", "append_query_opt"),
+                    FieldKind::Vec => ("query_all", "append_query_all"),
+                };
+                from_route.push_str(&format!(
+                    "{}: {}::{}(route, {:?})?,",
+                    ident, private, read, key
+                ));
+                to_query.push_str(&format!(
+                    "{}::{}(&mut query, {:?}, &self.{});",
+                    private, write, key, ident
+                ));
+            }
+        }
+    }
+
+    format!(
+        "impl {private}::RouteArgs for {name} {{
+            fn route_params() -> &'static [{private}::RouteArgParam] {{
+                &[{route_params}]
+            }}
+            fn from_route(
+                route: &{private}::Route,
+            ) -> ::std::result::Result<Self, {private}::RouteArgsError> {{
+                ::std::result::Result::Ok(Self {{ {from_route} }})
+            }}
+            #[allow(unused_mut)]
+            fn to_params(&self) -> {private}::RouteParams {{
+                let mut params = {private}::RouteParams::new();
+                {to_params}
+                params
+            }}
+            #[allow(unused_mut)]
+            fn to_query(&self) -> {private}::RouteQuery {{
+                let mut query = {private}::RouteQuery::new();
+                {to_query}
+                query
+            }}
+        }}"
+    )
+}
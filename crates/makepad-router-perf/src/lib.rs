@@ -88,6 +88,47 @@ pub fn build_mixed_route_table(route_count: usize) -> PerfRouteTable {
     }
 }
 
+/// Routes whose first segment is a param (`/:org/d42/:id`), which a first-segment index cannot
+/// narrow; lookups depend on the segment trie. Matching paths go in `dynamic_paths`.
+pub fn build_dynamic_first_route_table(route_count: usize) -> PerfRouteTable {
+    let mut registry = RouteRegistry::new();
+    let mut route_ids = Vec::with_capacity(route_count);
+    let mut dynamic_paths = Vec::with_capacity(route_count);
+    let mut miss_paths = Vec::with_capacity(route_count);
+    let mut mixed_paths = Vec::with_capacity(route_count * 2);
+
+    for i in 0..route_count {
+        let route_id = LiveId::from_str(&format!("route_{}", i));
+        route_ids.push(route_id);
+
+        let pattern = format!("/:org/d{}/:id", i);
+        registry.register_pattern(&pattern, route_id).unwrap();
+        let concrete = format!("/org{}/d{}/{}", i % 13, i, i.saturating_mul(5));
+        dynamic_paths.push(concrete.clone());
+        mixed_paths.push(concrete);
+
+        let miss = format!("/org{}/x{}/nope", i % 13, i);
+        miss_paths.push(miss.clone());
+        if i % 8 == 0 {
+            mixed_paths.push(miss);
+        }
+    }
+
+    if mixed_paths.is_empty() {
+        mixed_paths.push("/".to_string());
+    }
+
+    PerfRouteTable {
+        registry,
+        route_ids,
+        static_paths: Vec::new(),
+        dynamic_paths,
+        wildcard_paths: Vec::new(),
+        miss_paths,
+        mixed_paths,
+    }
+}
+
 pub fn router_from_table(table: &PerfRouteTable) -> Router {
     let mut router = Router::new(Route::new(LiveId::from_str("perf_home")));
     router.route_registry = table.registry.clone();
@@ -115,7 +156,7 @@ pub fn run_lookup_samples(registry: &RouteRegistry, paths: &[String], iterations
         let path = &paths[i % paths.len()];
         let start = Instant::now();
         let _ = registry.resolve_path(path);
-        out.push(start.elapsed().as_nanos() as u128);
+        out.push(start.elapsed().as_nanos());
     }
     out
 }
@@ -130,7 +171,7 @@ pub fn run_dispatch_go_to_path_samples(router: &Router, paths: &[String], iterat
         let path = &paths[i % paths.len()];
         let start = Instant::now();
         let _ = router.navigate_by_path(path);
-        out.push(start.elapsed().as_nanos() as u128);
+        out.push(start.elapsed().as_nanos());
 
         if router.depth() > 64 {
             router.clear_history();
@@ -168,7 +209,7 @@ pub fn run_stack_ops_samples(route_ids: &[LiveId], iterations: usize) -> Vec<u12
                 router.set_stack(build_stack(route_ids, 12));
             }
         }
-        out.push(start.elapsed().as_nanos() as u128);
+        out.push(start.elapsed().as_nanos());
     }
 
     out
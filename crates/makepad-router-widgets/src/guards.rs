@@ -1,6 +1,11 @@
+use crate::meta::{RouteMeta, RouteMetaValue};
 use crate::route::Route;
-use makepad_live_id::LiveId;
+use crate::url;
+use makepad_live_id::*;
 use makepad_widgets::{Cx, ToUIReceiver};
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
 
 // Guard and before-leave types for RouterWidget.
 
@@ -19,6 +24,8 @@ pub enum RouterNavKind {
     Pop,
     PopTo,
     PopToRoot,
+    /// Data preloaded ahead of navigation (only resolvers see this).
+    Preload,
 }
 
 /// Context passed to route guards and before-leave hooks.
@@ -28,6 +35,10 @@ pub struct RouterNavContext {
     pub from: Option<Route>,
     pub to: Option<Route>,
     pub to_path: Option<String>,
+    /// Metadata of the route being left (empty if none was declared).
+    pub from_meta: RouteMeta,
+    /// Metadata of the target route (empty if none was declared).
+    pub to_meta: RouteMeta,
 }
 
 /// Target used by a guard to redirect navigation.
@@ -37,6 +48,43 @@ pub enum RouterRedirectTarget {
     Path(String),
 }
 
+/// Where a scoped guard or before-leave hook applies.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RouterGuardScope {
+    /// One route id. Guards also run for navigations inside a nested router hosted by the route.
+    Route(LiveId),
+    /// Every path under a prefix, matched by whole segments (`/admin` covers `/admin/users` but
+    /// not `/administrator`).
+    Prefix(String),
+}
+
+impl RouterGuardScope {
+    /// Whether `path` lies under this scope's prefix (always `false` for route scopes).
+    pub fn covers_path(&self, path: &str) -> bool {
+        let Self::Prefix(prefix) = self else {
+            return false;
+        };
+        let prefix = url::normalize_path_cow(prefix);
+        let path = url::normalize_path_cow(path);
+        prefix == "/"
+            || path
+                .strip_prefix(prefix.as_ref())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    }
+}
+
+impl From<LiveId> for RouterGuardScope {
+    fn from(route_id: LiveId) -> Self {
+        Self::Route(route_id)
+    }
+}
+
+impl From<&str> for RouterGuardScope {
+    fn from(prefix: &str) -> Self {
+        Self::Prefix(prefix.to_string())
+    }
+}
+
 /// Redirect instruction returned by a guard.
 #[derive(Clone, Debug)]
 pub struct RouterRedirect {
@@ -59,12 +107,148 @@ pub enum RouterBeforeLeaveDecision {
     Block,
 }
 
+/// Identity consulted by [`RouterAuthGuard`] on every guarded navigation.
+///
+/// Implementations usually read shared session state, so login/logout needs no re-registration.
+pub trait RouterPrincipal: Send + Sync {
+    fn is_authenticated(&self) -> bool;
+    /// Whether the principal holds `role` (an entry of the route's `requires` metadata).
+    fn has_role(&self, role: &str) -> bool;
+    /// Whether the principal holds a role listed as an id (`requires: @admin`).
+    ///
+    /// Ids cannot be turned back into names, so the default denies; compare against
+    /// `LiveId::from_str(name)` of the principal's roles when routes list ids.
+    fn has_role_id(&self, role: LiveId) -> bool {
+        let _ = role;
+        false
+    }
+}
+
+/// Why [`RouterAuthGuard`] rejected a navigation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RouterAuthFailure {
+    /// The route requires a signed-in principal.
+    Unauthenticated,
+    /// The principal lacks a role listed in `requires`.
+    Forbidden,
+}
+
+/// Built-in guard driven by route metadata.
+///
+/// A route is protected when its metadata sets `requires_auth: true` or has a `requires` entry
+/// listing roles (all of them are needed); a `requires` entry naming no role still needs a
+/// signed-in principal. Unauthenticated navigations are redirected to `login`
+/// with the original URL in the `return_param` query parameter; a missing role redirects to
+/// `forbidden`, or blocks when it is `None`. It runs before the other route guards.
+pub struct RouterAuthGuard {
+    pub principal: Arc<dyn RouterPrincipal>,
+    pub login: RouterRedirectTarget,
+    pub forbidden: Option<RouterRedirectTarget>,
+    /// Query parameter carrying the original URL (default `return_to`).
+    pub return_param: String,
+    /// Replace the current history entry when redirecting (default `true`).
+    pub replace: bool,
+    /// Metadata flag marking routes that need a signed-in principal (default `requires_auth`).
+    pub requires_auth_key: LiveId,
+    /// Metadata entry listing required roles (default `requires`).
+    pub requires_key: LiveId,
+}
+
+impl RouterAuthGuard {
+    pub fn new(principal: impl RouterPrincipal + 'static, login: RouterRedirectTarget) -> Self {
+        Self {
+            principal: Arc::new(principal),
+            login,
+            forbidden: None,
+            return_param: "return_to".to_string(),
+            replace: true,
+            requires_auth_key: live_id!(requires_auth),
+            requires_key: live_id!(requires),
+        }
+    }
+
+    pub fn with_forbidden(mut self, target: RouterRedirectTarget) -> Self {
+        self.forbidden = Some(target);
+        self
+    }
+
+    pub fn with_return_param(mut self, name: impl Into<String>) -> Self {
+        self.return_param = name.into();
+        self
+    }
+
+    /// Check the target route's metadata against the principal.
+    pub fn check(&self, meta: &RouteMeta) -> Result<(), RouterAuthFailure> {
+        let requires = meta.get(self.requires_key);
+        if requires.is_none() && !meta.flag(self.requires_auth_key) {
+            return Ok(());
+        }
+        if !self.principal.is_authenticated() {
+            return Err(RouterAuthFailure::Unauthenticated);
+        }
+        let mut roles = Vec::new();
+        if let Some(requires) = requires {
+            collect_required_roles(requires, &mut roles);
+        }
+        let has_role = |role: &RequiredRole| match role {
+            RequiredRole::Name(name) => self.principal.has_role(name),
+            RequiredRole::Id(id) => self.principal.has_role_id(*id),
+        };
+        if roles.iter().all(has_role) {
+            Ok(())
+        } else {
+            Err(RouterAuthFailure::Forbidden)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum RequiredRole<'a> {
+    Name(&'a str),
+    Id(LiveId),
+}
+
+/// Roles named by a `requires` value: a comma separated string, an id, or a list of those.
+fn collect_required_roles<'a>(value: &'a RouteMetaValue, roles: &mut Vec<RequiredRole<'a>>) {
+    match value {
+        RouteMetaValue::Str(value) => roles.extend(
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|role| !role.is_empty())
+                .map(RequiredRole::Name),
+        ),
+        RouteMetaValue::Id(id) => roles.push(RequiredRole::Id(*id)),
+        RouteMetaValue::List(values) => {
+            for value in values {
+                collect_required_roles(value, roles);
+            }
+        }
+        RouteMetaValue::Bool(_) | RouteMetaValue::Number(_) => {}
+    }
+}
+
 /// Async decision type used by async guards/before-leave hooks.
 pub enum RouterAsyncDecision<T> {
     Immediate(T),
     Pending(ToUIReceiver<T>),
 }
 
+/// Decision used when an async guard or before-leave hook does not reply within `seconds`.
+///
+/// A timed-out `Block` is reported as `RouterBlockReason::Timeout`.
+#[derive(Clone, Debug)]
+pub struct RouterAsyncTimeout<T> {
+    pub seconds: f64,
+    pub decision: T,
+}
+
+impl<T> RouterAsyncTimeout<T> {
+    pub fn new(seconds: f64, decision: T) -> Self {
+        Self { seconds, decision }
+    }
+}
+
 /// Sync guard hook. Return `Allow`, `Block`, or `Redirect`.
 pub type RouterSyncGuard =
     Box<dyn Fn(&mut Cx, &RouterNavContext) -> RouterGuardDecision + Send + Sync>;
@@ -73,6 +257,13 @@ pub type RouterAsyncGuard = Box<
     dyn Fn(&mut Cx, &RouterNavContext) -> RouterAsyncDecision<RouterGuardDecision> + Send + Sync,
 >;
 
+/// Sync guard attached to a scope; shared with the nested routers that inherit it.
+pub type RouterScopedGuard =
+    Arc<dyn Fn(&mut Cx, &RouterNavContext) -> RouterGuardDecision + Send + Sync>;
+/// Sync before-leave hook attached to a scope.
+pub type RouterScopedBeforeLeave =
+    Arc<dyn Fn(&mut Cx, &RouterNavContext) -> RouterBeforeLeaveDecision + Send + Sync>;
+
 /// Sync before-leave hook.
 pub type RouterBeforeLeaveSync =
     Box<dyn Fn(&mut Cx, &RouterNavContext) -> RouterBeforeLeaveDecision + Send + Sync>;
@@ -82,3 +273,146 @@ pub type RouterBeforeLeaveAsync = Box<
         + Send
         + Sync,
 >;
+
+/// Data loaded by a route resolver (read it back typed with `route_data::<T>`).
+pub type RouterRouteData = Arc<dyn Any + Send + Sync>;
+/// Outcome of a route resolver: the route's data or why it has none.
+pub type RouterResolveResult = Result<RouterRouteData, RouterResolveError>;
+/// Per-route data resolver, run after guards pass and before the navigation commits.
+pub type RouterResolver = Box<
+    dyn Fn(&mut Cx, &RouterNavContext) -> RouterAsyncDecision<RouterResolveResult> + Send + Sync,
+>;
+
+/// Why a route resolver produced no data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RouterResolveError {
+    /// The resolver reported a failure.
+    Failed(String),
+    /// No reply within `resolver_timeout`.
+    TimedOut,
+    /// The resolver dropped its sender without replying.
+    Dropped,
+}
+
+impl fmt::Display for RouterResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Failed(message) => write!(f, "{}", message),
+            Self::TimedOut => write!(f, "resolver timed out"),
+            Self::Dropped => write!(f, "resolver dropped without replying"),
+        }
+    }
+}
+
+impl std::error::Error for RouterResolveError {}
+
+impl From<String> for RouterResolveError {
+    fn from(message: String) -> Self {
+        Self::Failed(message)
+    }
+}
+
+impl From<&str> for RouterResolveError {
+    fn from(message: &str) -> Self {
+        Self::Failed(message.to_string())
+    }
+}
+
+/// Load state of a route's resolver.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum RouterLoadState {
+    /// No resolver ran (or the pending one was cancelled).
+    #[default]
+    Idle,
+    Loading,
+    Loaded,
+    /// The resolver failed; `retry_navigation` runs the navigation again.
+    Failed(RouterResolveError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestPrincipal {
+        signed_in: bool,
+        roles: &'static [&'static str],
+    }
+
+    impl RouterPrincipal for TestPrincipal {
+        fn is_authenticated(&self) -> bool {
+            self.signed_in
+        }
+        fn has_role(&self, role: &str) -> bool {
+            self.roles.contains(&role)
+        }
+        fn has_role_id(&self, role: LiveId) -> bool {
+            self.roles.iter().any(|name| LiveId::from_str(name) == role)
+        }
+    }
+
+    fn guard(signed_in: bool, roles: &'static [&'static str]) -> RouterAuthGuard {
+        RouterAuthGuard::new(
+            TestPrincipal { signed_in, roles },
+            RouterRedirectTarget::Route(live_id!(login)),
+        )
+    }
+
+    #[test]
+    fn auth_guard_checks_string_list_and_id_roles() {
+        let admin = guard(true, &["admin", "editor"]);
+        let viewer = guard(true, &["viewer"]);
+        let requires = [
+            RouteMeta::new().with(live_id!(requires), "admin, editor"),
+            RouteMeta::new().with(live_id!(requires), vec!["admin", "editor"]),
+            RouteMeta::new().with(live_id!(requires), live_id!(admin)),
+            RouteMeta::new().with(
+                live_id!(requires),
+                RouteMetaValue::List(vec![
+                    RouteMetaValue::Id(live_id!(admin)),
+                    RouteMetaValue::from("editor"),
+                ]),
+            ),
+        ];
+        for meta in &requires {
+            assert_eq!(admin.check(meta), Ok(()));
+            assert_eq!(viewer.check(meta), Err(RouterAuthFailure::Forbidden));
+            assert_eq!(
+                guard(false, &[]).check(meta),
+                Err(RouterAuthFailure::Unauthenticated)
+            );
+        }
+        assert_eq!(guard(false, &[]).check(&RouteMeta::new()), Ok(()));
+    }
+
+    #[test]
+    fn auth_guard_fails_closed_without_usable_roles() {
+        let signed_out = guard(false, &[]);
+        for value in [
+            RouteMetaValue::from(""),
+            RouteMetaValue::List(Vec::new()),
+            RouteMetaValue::Bool(false),
+            RouteMetaValue::Number(1.0),
+        ] {
+            let meta = RouteMeta::new().with(live_id!(requires), value);
+            assert_eq!(
+                signed_out.check(&meta),
+                Err(RouterAuthFailure::Unauthenticated)
+            );
+            assert_eq!(guard(true, &[]).check(&meta), Ok(()));
+        }
+
+        struct NameOnly;
+        impl RouterPrincipal for NameOnly {
+            fn is_authenticated(&self) -> bool {
+                true
+            }
+            fn has_role(&self, _role: &str) -> bool {
+                true
+            }
+        }
+        let name_only = RouterAuthGuard::new(NameOnly, RouterRedirectTarget::Path("/login".into()));
+        let meta = RouteMeta::new().with(live_id!(requires), live_id!(admin));
+        assert_eq!(name_only.check(&meta), Err(RouterAuthFailure::Forbidden));
+    }
+}
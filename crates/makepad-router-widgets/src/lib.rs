@@ -4,6 +4,18 @@ pub use makepad_micro_serde;
 pub use makepad_router_core;
 pub use makepad_widgets;
 
+pub mod args {
+    pub use makepad_router_core::args::*;
+}
+pub mod constraint {
+    pub use makepad_router_core::constraint::*;
+}
+pub mod error {
+    pub use makepad_router_core::error::*;
+}
+pub mod meta {
+    pub use makepad_router_core::meta::*;
+}
 pub mod navigation {
     pub use makepad_router_core::navigation::*;
 }
@@ -30,31 +42,46 @@ pub mod guards;
 pub mod widget;
 
 // Public API (explicit re-exports only; no wildcard exports).
+pub use crate::args::{RouteArgParam, RouteArgValue};
+pub use crate::constraint::RouteParamConstraint;
+pub use crate::error::{NavigateError, PatternError, RegistryError, RouteArgsError, UrlForError};
 pub use crate::guards::{
-    RouterAsyncDecision, RouterAsyncGuard, RouterBeforeLeaveAsync, RouterBeforeLeaveDecision,
-    RouterBeforeLeaveSync, RouterGuardDecision, RouterNavContext, RouterNavKind, RouterRedirect,
-    RouterRedirectTarget, RouterSyncGuard,
+    RouterAsyncDecision, RouterAsyncGuard, RouterAsyncTimeout, RouterAuthFailure, RouterAuthGuard,
+    RouterBeforeLeaveAsync, RouterBeforeLeaveDecision, RouterBeforeLeaveSync, RouterGuardDecision,
+    RouterGuardScope, RouterLoadState, RouterNavContext, RouterNavKind, RouterPrincipal,
+    RouterRedirect, RouterRedirectTarget, RouterResolveError, RouterResolveResult, RouterResolver,
+    RouterRouteData, RouterScopedBeforeLeave, RouterScopedGuard, RouterSyncGuard,
+};
+pub use crate::meta::{RouteMeta, RouteMetaValue};
+pub use crate::navigation::{
+    HistoryEviction, HistoryLimit, HistoryStack, HistoryStacks, NavigationHistory,
 };
-pub use crate::navigation::NavigationHistory;
-pub use crate::pattern::{RouteParams, RoutePattern, RouteSegment};
-pub use crate::registry::RouteRegistry;
+pub use crate::pattern::{RouteParamValue, RouteParams, RoutePattern, RouteSegment};
+pub use crate::registry::{RouteDiagnostic, RouteDiagnosticKind, RouteRegistry};
 pub use crate::route::{Route, RouteQuery};
 pub use crate::router::{Router, RouterAction};
 pub use crate::state::RouterState;
-pub use crate::url::{build_query_string, parse_query_map, RouterUrl};
+pub use crate::url::{
+    build_query_string, build_query_string_sorted, parse_query_map, DuplicateSlashes, MatchPolicy,
+    RouterUrl, TrailingSlash,
+};
 pub use crate::widget::script_mod;
 pub use crate::widget::{
     RouterBlockReason, RouterCapabilities, RouterCommand, RouterConfig, RouterDispatchResult,
-    RouterRoute, RouterTransitionPreset, RouterTransitionSpec, RouterWidget, RouterWidgetRef,
-    RouterWidgetWidgetRefExt,
+    RouterGuardKind, RouterKeepAlive, RouterNavEvent, RouterPreload, RouterRoute,
+    RouterTransitionPreset, RouterTransitionSpec, RouterWidget, RouterWidgetError,
+    RouterWidgetRef, RouterWidgetWidgetRefExt,
 };
+pub use makepad_router_core::RouteArgs;
 
 /// Convenience re-exports for common usage patterns.
 pub mod prelude {
     pub use crate::guards::{
-        RouterAsyncDecision, RouterBeforeLeaveDecision, RouterGuardDecision, RouterNavContext,
+        RouterAsyncDecision, RouterAsyncTimeout, RouterAuthGuard, RouterBeforeLeaveDecision,
+        RouterGuardDecision, RouterGuardScope, RouterLoadState, RouterNavContext, RouterPrincipal,
         RouterRedirect, RouterRedirectTarget,
     };
+    pub use crate::meta::RouteMeta;
     pub use crate::pattern::{RouteParams, RoutePattern, RouteSegment};
     pub use crate::route::{Route, RouteQuery};
     pub use crate::router::{Router, RouterAction};
@@ -62,7 +89,8 @@ pub mod prelude {
     pub use crate::url::RouterUrl;
     pub use crate::widget::{
         RouterBlockReason, RouterCapabilities, RouterCommand, RouterConfig, RouterDispatchResult,
-        RouterRoute, RouterTransitionPreset, RouterTransitionSpec, RouterWidget, RouterWidgetRef,
-        RouterWidgetWidgetRefExt,
+        RouterNavEvent, RouterRoute, RouterTransitionPreset, RouterTransitionSpec, RouterWidget,
+        RouterWidgetRef, RouterWidgetWidgetRefExt,
     };
+    pub use makepad_router_core::RouteArgs;
 }
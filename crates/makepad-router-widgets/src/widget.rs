@@ -1,33 +1,51 @@
 //! Router widget implementation and subsystem wiring.
 
 use crate::{
+    args::RouteArgs,
+    error::RegistryError,
     guards::{
-        RouterAsyncDecision, RouterBeforeLeaveDecision, RouterGuardDecision, RouterNavContext,
+        RouterAsyncDecision, RouterAsyncTimeout, RouterAuthGuard, RouterBeforeLeaveDecision,
+        RouterGuardDecision, RouterGuardScope, RouterLoadState, RouterNavContext,
+        RouterResolveResult,
     },
-    route::Route,
+    meta::RouteMeta,
+    navigation::{HistoryLimit, NavigationHistory},
+    pattern::{RouteParams, RoutePattern},
+    registry::RouteDiagnostic,
+    route::{Route, RouteQuery},
     router::{Router, RouterAction},
     state::RouterState,
+    url::MatchPolicy,
 };
 use makepad_draw::draw_list_2d::DrawListExt;
 use makepad_widgets::*;
+use std::any::Any;
+use std::sync::Arc;
 
 mod actions;
 mod api;
 mod callbacks;
 mod commands;
 mod engine;
+mod error;
+mod events;
 mod features;
 mod fields;
 mod guard_flow;
 mod guards;
 mod inspector;
+mod keep_alive;
 mod live_apply;
 mod nested;
+mod overlays;
 mod path_nav;
 mod persistence;
+mod preload;
+mod resolvers;
 mod route_defs;
 mod route_render;
 mod route_widgets;
+mod stacks;
 mod transitions;
 mod url_cache;
 mod url_state;
@@ -35,11 +53,15 @@ mod url_state;
 pub use commands::{
     RouterBlockReason, RouterCapabilities, RouterCommand, RouterConfig, RouterDispatchResult,
 };
+pub use error::RouterWidgetError;
+pub use events::{RouterGuardKind, RouterNavEvent};
 use fields::{
-    PointerCleanup, RouterCaches, RouterCallbacks, RouterDrawLists, RouterGuards, RouterRouteMaps,
-    TransitionRuntime,
+    PointerCleanup, PreloadRuntime, RouterCaches, RouterCallbacks, RouterDrawLists, RouterGuards,
+    RouterResolvers, RouterRouteMaps, TransitionRuntime,
 };
 use guard_flow::PendingNavigation;
+pub use keep_alive::RouterKeepAlive;
+pub use preload::RouterPreload;
 use transitions::{RouterActionKind, RouterTransitionDirection, RouterTransitionState};
 pub use transitions::{RouterTransitionPreset, RouterTransitionSpec};
 
@@ -68,6 +90,19 @@ script_mod! {
         replace_transition: @none
         transition_duration: 0.25
         debug_inspector: false
+        strict_route_patterns: false
+        case_insensitive_routes: false
+        strict_trailing_slash: false
+        reject_duplicate_slashes: false
+        canonical_redirects: false
+        async_guard_timeout: 0.0
+        resolver_timeout: 0.0
+        supersede_pending_navigation: false
+        emit_nav_events: false
+        max_history_depth: 0
+        history_eviction: @drop_oldest
+        keep_alive: @always
+        max_route_widgets: 0
         inspector_bg +: {draw_depth: 10.0, color: #x00000012}
         inspector_text +: {
             text_style: theme.font_regular{font_size: 9}
@@ -147,6 +182,8 @@ pub(super) enum ResolvedPathKind {
 #[derive(Clone, Debug)]
 pub(super) struct ResolvedPathIntent {
     pub path: String,
+    /// The requested path when redirects or canonicalization changed it.
+    pub rewritten_from: Option<String>,
     pub route: Route,
     pub kind: ResolvedPathKind,
     pub clear_extras: bool,
@@ -165,9 +202,25 @@ pub struct RouterRoute {
     #[live]
     route_pattern: String,
     #[live]
+    route_redirect: String,
+    #[live]
+    route_aliases: String,
+    /// Arbitrary metadata (`route_meta: {title: "Settings" requires_auth: true}`).
+    #[live]
+    route_meta: ScriptValue,
+    #[live]
     route_transition: LiveId,
     #[live(0.0)]
     route_transition_duration: f64,
+    /// Present above the page beneath it (modal, sheet, dialog) instead of replacing it.
+    #[live(false)]
+    route_overlay: bool,
+    /// Keep-alive policy for this route's widget (`always`, `in_history`, `lru`).
+    #[live]
+    route_keep_alive: LiveId,
+    /// Instantiate the route's widget before its first visit (`eager`, `on_idle`, `on_hover`).
+    #[live]
+    route_preload: LiveId,
 }
 
 impl Widget for RouterRoute {
@@ -216,6 +269,50 @@ pub struct RouterWidget {
     /// Shows a small debug overlay with current route/stack/params (dev tool).
     #[live(false)]
     debug_inspector: bool,
+    /// Reject duplicate, ambiguous or unreachable route patterns instead of only reporting them.
+    #[live(false)]
+    strict_route_patterns: bool,
+    /// Compare static path segments case-insensitively.
+    #[live(false)]
+    case_insensitive_routes: bool,
+    /// Paths with a trailing slash (other than `/`) match no route.
+    #[live(false)]
+    strict_trailing_slash: bool,
+    /// Paths containing `//` match no route instead of being collapsed.
+    #[live(false)]
+    reject_duplicate_slashes: bool,
+    /// Replace incoming paths with their canonical form (see `RouteRegistry::canonical_path`).
+    #[live(false)]
+    canonical_redirects: bool,
+    /// Seconds to wait for an async guard/hook reply before blocking (`0.0` waits forever).
+    #[live(0.0)]
+    async_guard_timeout: f64,
+    /// Seconds to wait for a route resolver before blocking with `ResolveFailed` (`0.0` waits
+    /// forever).
+    #[live(0.0)]
+    resolver_timeout: f64,
+    /// A new navigation cancels a pending one instead of being rejected.
+    #[live(false)]
+    supersede_pending_navigation: bool,
+    /// Emit `RouterNavEvent` lifecycle events as widget actions.
+    #[live(false)]
+    emit_nav_events: bool,
+    /// Maximum history depth (`0` = unbounded).
+    #[live(0)]
+    max_history_depth: usize,
+    /// `drop_oldest`, `collapse_duplicates` or `keep_root` (see `HistoryEviction`).
+    #[live]
+    history_eviction: LiveId,
+    /// `always`, `in_history` or `lru` for routes without `route_keep_alive` (see
+    /// `RouterKeepAlive`).
+    #[live]
+    keep_alive: LiveId,
+    /// Route widgets kept under the `lru` policy (`0` = unbounded).
+    #[live(0)]
+    max_route_widgets: usize,
+    /// Seconds a preload lasts if its route is not shown (`0.0` lasts until it is).
+    #[live(30.0)]
+    preload_ttl: f64,
     #[live(false)]
     cap_guards_sync: bool,
     #[live(false)]
@@ -239,10 +336,20 @@ pub struct RouterWidget {
     #[rust]
     pending_navigation: Option<PendingNavigation>,
     #[rust]
+    resolvers: RouterResolvers,
+    /// Navigation whose resolver failed, re-run by `retry_navigation`.
+    #[rust]
+    failed_navigation: Option<RouterNavRequest>,
+    /// Fires when the pending navigation's async guard/hook times out.
+    #[rust]
+    pending_timer: Timer,
+    #[rust]
     guard_bypass: bool,
     #[rust]
     pending_actions: Vec<RouterAction>,
     #[rust]
+    pending_nav_events: Vec<RouterNavEvent>,
+    #[rust]
     url_path_override: Option<String>,
     #[rust]
     caches: RouterCaches,
@@ -257,7 +364,11 @@ pub struct RouterWidget {
     #[rust]
     transition_rt: TransitionRuntime,
     #[rust]
+    preload_rt: PreloadRuntime,
+    #[rust]
     last_blocked_reason: Option<RouterBlockReason>,
+    #[rust]
+    route_diagnostics: Vec<RouteDiagnostic>,
 }
 
 impl RouterWidget {
@@ -275,15 +386,131 @@ impl RouterWidget {
         &mut self,
         pattern: &str,
         route_id: LiveId,
-    ) -> Result<(), String> {
+    ) -> Result<(), RegistryError> {
         self.router.register_route_pattern(pattern, route_id)?;
         self.routes.patterns.insert(route_id, pattern.to_string());
+        self.invalidate_route_registry_caches();
+        self.collect_route_diagnostics();
+        Ok(())
+    }
+
+    /// Register a route pattern for a typed `RouteArgs` route, checking that the pattern
+    /// captures the params `T` reads.
+    pub fn register_route_args<T: RouteArgs>(
+        &mut self,
+        pattern: &str,
+        route_id: LiveId,
+    ) -> Result<(), RegistryError> {
+        T::validate_pattern(&RoutePattern::parse(pattern)?).map_err(RegistryError::Args)?;
+        self.register_route_pattern(pattern, route_id)
+    }
+
+    /// Register an extra pattern resolving to `route_id` (see `RouteRegistry::register_alias`).
+    pub fn register_route_alias(
+        &mut self,
+        pattern: &str,
+        route_id: LiveId,
+    ) -> Result<(), RegistryError> {
+        self.router.register_route_alias(pattern, route_id)?;
+        self.invalidate_route_registry_caches();
+        self.collect_route_diagnostics();
+        Ok(())
+    }
+
+    /// Redirect paths matching `from` to `to` (see `RouteRegistry::register_redirect`).
+    pub fn register_route_redirect(&mut self, from: &str, to: &str) -> Result<(), RegistryError> {
+        self.router.register_route_redirect(from, to)?;
+        self.invalidate_route_registry_caches();
+        Ok(())
+    }
+
+    /// Set how incoming paths are matched (case, trailing and duplicate slashes).
+    ///
+    /// Live properties (`case_insensitive_routes`, ...) override this on the next reload.
+    pub fn set_match_policy(&mut self, policy: MatchPolicy) {
+        self.router.route_registry.set_match_policy(policy);
+        self.invalidate_route_registry_caches();
+    }
+
+    /// Bound the history depth (`max_history_depth` / `history_eviction` override this on the
+    /// next reload).
+    pub fn set_history_limit(&mut self, limit: HistoryLimit) {
+        self.router.set_history_limit(limit);
+    }
+
+    /// Metadata declared for a route (`route_meta`) or set with `set_route_meta`.
+    pub fn route_meta(&self, route_id: LiveId) -> Option<&RouteMeta> {
+        self.router.route_meta(route_id)
+    }
+
+    /// Attach metadata to a route, replacing what was declared in the DSL.
+    pub fn set_route_meta(&mut self, route_id: LiveId, meta: RouteMeta) {
+        self.router.set_route_meta(route_id, meta);
+    }
+
+    /// Pattern conflicts (duplicate ids, ambiguous or unreachable patterns) found so far.
+    pub fn route_diagnostics(&self) -> &[RouteDiagnostic] {
+        &self.route_diagnostics
+    }
+
+    /// Move diagnostics recorded by the registry into `route_diagnostics`, logging each one.
+    fn collect_route_diagnostics(&mut self) {
+        for diagnostic in self.router.route_registry.take_diagnostics() {
+            log!("Router: {}", diagnostic);
+            self.route_diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Unregister a route and all of its patterns
+    pub fn unregister_route(&mut self, route_id: LiveId) -> bool {
+        let removed = self.router.unregister_route(route_id);
+        forget_route_diagnostics(&mut self.route_diagnostics, route_id);
+        self.routes.patterns.remove(&route_id);
+        self.invalidate_route_registry_caches();
+        removed
+    }
+
+    /// Unregister a route pattern, returning the route ids that used it
+    pub fn unregister_route_pattern(
+        &mut self,
+        pattern: &str,
+    ) -> Result<Vec<LiveId>, RegistryError> {
+        let affected = self.router.unregister_route_pattern(pattern)?;
+        forget_pattern_diagnostics(&mut self.route_diagnostics, pattern);
+        for (route_id, pattern) in remaining_route_patterns(&self.router, &affected) {
+            match pattern {
+                Some(pattern) => {
+                    self.routes.patterns.insert(route_id, pattern);
+                }
+                None => {
+                    self.routes.patterns.remove(&route_id);
+                }
+            }
+        }
+        self.invalidate_route_registry_caches();
+        Ok(affected)
+    }
+
+    /// Replace the pattern(s) of a route with a new pattern
+    pub fn replace_route_pattern(
+        &mut self,
+        route_id: LiveId,
+        pattern: &str,
+    ) -> Result<(), RegistryError> {
+        self.router.replace_route_pattern(route_id, pattern)?;
+        forget_route_diagnostics(&mut self.route_diagnostics, route_id);
+        self.routes.patterns.insert(route_id, pattern.to_string());
+        self.invalidate_route_registry_caches();
+        self.collect_route_diagnostics();
+        Ok(())
+    }
+
+    fn invalidate_route_registry_caches(&mut self) {
         self.caches.route_registry_epoch = self.caches.route_registry_epoch.wrapping_add(1);
         self.caches.nested_prefix_cache_epoch = 0;
         self.caches.nested_prefix_cache_path.clear();
         self.caches.nested_prefix_cache_result = None;
         self.caches.child_router_scan_widget_count = 0;
-        Ok(())
     }
 }
 
@@ -310,6 +537,7 @@ impl WidgetNode for RouterWidget {
     }
 
     fn redraw(&mut self, cx: &mut Cx) {
+        self.draw_lists.underlay.redraw(cx);
         self.draw_lists.from.redraw(cx);
         self.draw_lists.to.redraw(cx);
         self.draw_lists.inspector.redraw(cx);
@@ -322,6 +550,10 @@ impl Widget for RouterWidget {
         if let Some(ne) = self.transition_rt.next_frame.is_event(event) {
             self.update_transition(cx, ne.time);
         }
+        if self.preload_rt.next_frame.is_event(event).is_some() {
+            self.preload_idle_step(cx);
+        }
+        self.expire_preloads(cx, event);
         self.flush_router_actions(cx, scope);
         let uid = self.widget_uid();
 
@@ -348,7 +580,9 @@ impl Widget for RouterWidget {
             }
         }
 
-        self.poll_pending_navigation(cx);
+        let timed_out = self.pending_timer.is_event(event).is_some();
+        self.poll_pending_navigation(cx, timed_out);
+        self.poll_preloads();
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
@@ -430,6 +664,79 @@ impl RouterWidgetRef {
         }
     }
 
+    pub fn route_meta(&self, route_id: LiveId) -> Option<RouteMeta> {
+        self.borrow()?.route_meta(route_id).cloned()
+    }
+
+    /// Metadata of the current route.
+    pub fn current_route_meta(&self) -> Option<RouteMeta> {
+        let inner = self.borrow()?;
+        let route_id = inner.router.current_route_id()?;
+        inner.route_meta(route_id).cloned()
+    }
+
+    pub fn set_route_meta(&self, route_id: LiveId, meta: RouteMeta) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_route_meta(route_id, meta);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn set_route_resolver<F>(&self, route_id: LiveId, resolver: F) -> bool
+    where
+        F: Fn(&mut Cx, &RouterNavContext) -> RouterAsyncDecision<RouterResolveResult>
+            + Send
+            + Sync
+            + 'static,
+    {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_route_resolver(route_id, resolver);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn clear_route_resolver(&self, route_id: LiveId) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.clear_route_resolver(route_id);
+        }
+    }
+
+    /// Data resolved for `route_id`, if it has type `T`.
+    pub fn route_data<T: Any + Send + Sync>(&self, route_id: LiveId) -> Option<Arc<T>> {
+        self.borrow()?.route_data_as::<T>(route_id)
+    }
+
+    /// Data resolved for the current route (with its current params), if it has type `T`.
+    pub fn current_route_data<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        let inner = self.borrow()?;
+        let route = inner.router.current_route()?;
+        inner.route_data_for(route)?.downcast::<T>().ok()
+    }
+
+    pub fn set_resolver_timeout(&self, seconds: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_resolver_timeout(seconds);
+        }
+    }
+
+    pub fn route_load_state(&self, route_id: LiveId) -> RouterLoadState {
+        self.borrow()
+            .map(|inner| inner.route_load_state(route_id))
+            .unwrap_or_default()
+    }
+
+    pub fn retry_navigation(&self, cx: &mut Cx) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.retry_navigation(cx)
+        } else {
+            false
+        }
+    }
+
     pub fn get_query_string(&self, key: &str) -> Option<String> {
         self.current_route()?.query_get_string(key)
     }
@@ -465,12 +772,8 @@ impl RouterWidgetRef {
     /// Get a route parameter as a string
     /// Returns None if the parameter doesn't exist or the route is not active
     pub fn get_param_string(&self, param_name: &str) -> Option<String> {
-        if let Some(route) = self.current_route() {
-            if let Some(param_value) = route.get_param(LiveId::from_str(param_name)) {
-                return param_value.as_string(|id_str| id_str.map(|s| s.to_string()));
-            }
-        }
-        None
+        self.current_route()?
+            .get_param_string(LiveId::from_str(param_name))
     }
 
     /// Bind a route parameter to a label widget
@@ -517,6 +820,26 @@ impl RouterWidgetRef {
         }
     }
 
+    /// Register a callback run before a route widget is dropped by its keep-alive policy.
+    pub fn on_route_dispose<F>(&self, callback: F)
+    where
+        F: Fn(&mut Cx, LiveId, &WidgetRef) + Send + Sync + 'static,
+    {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.on_route_dispose(callback);
+        }
+    }
+
+    /// Register a navigation lifecycle callback (see `RouterNavEvent`).
+    pub fn on_nav_event<F>(&self, callback: F)
+    where
+        F: Fn(&mut Cx, &RouterNavEvent) + Send + Sync + 'static,
+    {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.on_nav_event(callback);
+        }
+    }
+
     pub fn add_route_guard<F>(&self, guard: F) -> Result<(), RouterBlockReason>
     where
         F: Fn(&mut Cx, &RouterNavContext) -> RouterGuardDecision + Send + Sync + 'static,
@@ -528,6 +851,34 @@ impl RouterWidgetRef {
         }
     }
 
+    pub fn cancel_pending_navigation(&self, cx: &mut Cx) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.cancel_pending_navigation(cx)
+        } else {
+            false
+        }
+    }
+
+    pub fn has_pending_navigation(&self) -> bool {
+        self.borrow()
+            .map(|inner| inner.has_pending_navigation())
+            .unwrap_or(false)
+    }
+
+    pub fn set_auth_guard(&self, guard: RouterAuthGuard) -> Result<(), RouterBlockReason> {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_auth_guard(guard)
+        } else {
+            Err(RouterBlockReason::CapabilityDisabled)
+        }
+    }
+
+    pub fn clear_auth_guard(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.clear_auth_guard();
+        }
+    }
+
     pub fn add_route_guard_async<F>(&self, guard: F) -> Result<(), RouterBlockReason>
     where
         F: Fn(&mut Cx, &RouterNavContext) -> RouterAsyncDecision<RouterGuardDecision>
@@ -542,6 +893,63 @@ impl RouterWidgetRef {
         }
     }
 
+    pub fn add_route_guard_async_with_timeout<F>(
+        &self,
+        timeout: RouterAsyncTimeout<RouterGuardDecision>,
+        guard: F,
+    ) -> Result<(), RouterBlockReason>
+    where
+        F: Fn(&mut Cx, &RouterNavContext) -> RouterAsyncDecision<RouterGuardDecision>
+            + Send
+            + Sync
+            + 'static,
+    {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.add_route_guard_async_with_timeout(timeout, guard)
+        } else {
+            Err(RouterBlockReason::CapabilityDisabled)
+        }
+    }
+
+    pub fn set_async_guard_timeout(
+        &self,
+        timeout: Option<RouterAsyncTimeout<RouterGuardDecision>>,
+    ) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_async_guard_timeout(timeout);
+        }
+    }
+
+    pub fn add_route_guard_for<F>(
+        &self,
+        scope: impl Into<RouterGuardScope>,
+        guard: F,
+    ) -> Result<(), RouterBlockReason>
+    where
+        F: Fn(&mut Cx, &RouterNavContext) -> RouterGuardDecision + Send + Sync + 'static,
+    {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.add_route_guard_for(scope, guard)
+        } else {
+            Err(RouterBlockReason::CapabilityDisabled)
+        }
+    }
+
+    pub fn add_before_leave_hook_for<F>(
+        &self,
+        scope: impl Into<RouterGuardScope>,
+        hook: F,
+    ) -> Result<(), RouterBlockReason>
+    where
+        F: Fn(&mut Cx, &RouterNavContext) -> RouterBeforeLeaveDecision + Send + Sync + 'static,
+    {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.add_before_leave_hook_for(scope, hook)
+        } else {
+            Err(RouterBlockReason::CapabilityDisabled)
+        }
+    }
+
     pub fn add_before_leave_hook<F>(&self, hook: F) -> Result<(), RouterBlockReason>
     where
         F: Fn(&mut Cx, &RouterNavContext) -> RouterBeforeLeaveDecision + Send + Sync + 'static,
@@ -567,11 +975,407 @@ impl RouterWidgetRef {
         }
     }
 
-    pub fn register_route_pattern(&self, pattern: &str, route_id: LiveId) -> Result<(), String> {
+    pub fn add_before_leave_hook_async_with_timeout<F>(
+        &self,
+        timeout: RouterAsyncTimeout<RouterBeforeLeaveDecision>,
+        hook: F,
+    ) -> Result<(), RouterBlockReason>
+    where
+        F: Fn(&mut Cx, &RouterNavContext) -> RouterAsyncDecision<RouterBeforeLeaveDecision>
+            + Send
+            + Sync
+            + 'static,
+    {
         if let Some(mut inner) = self.borrow_mut() {
-            inner.register_route_pattern(pattern, route_id)
+            inner.add_before_leave_hook_async_with_timeout(timeout, hook)
         } else {
-            Err("Cannot borrow router widget".to_string())
+            Err(RouterBlockReason::CapabilityDisabled)
+        }
+    }
+
+    pub fn set_async_before_leave_timeout(
+        &self,
+        timeout: Option<RouterAsyncTimeout<RouterBeforeLeaveDecision>>,
+    ) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_async_before_leave_timeout(timeout);
         }
     }
+
+    pub fn register_route_pattern(
+        &self,
+        pattern: &str,
+        route_id: LiveId,
+    ) -> Result<(), RouterWidgetError> {
+        if let Some(mut inner) = self.borrow_mut() {
+            Ok(inner.register_route_pattern(pattern, route_id)?)
+        } else {
+            Err(RouterWidgetError::Unavailable)
+        }
+    }
+
+    pub fn register_route_args<T: RouteArgs>(
+        &self,
+        pattern: &str,
+        route_id: LiveId,
+    ) -> Result<(), RouterWidgetError> {
+        if let Some(mut inner) = self.borrow_mut() {
+            Ok(inner.register_route_args::<T>(pattern, route_id)?)
+        } else {
+            Err(RouterWidgetError::Unavailable)
+        }
+    }
+
+    pub fn register_route_alias(
+        &self,
+        pattern: &str,
+        route_id: LiveId,
+    ) -> Result<(), RouterWidgetError> {
+        if let Some(mut inner) = self.borrow_mut() {
+            Ok(inner.register_route_alias(pattern, route_id)?)
+        } else {
+            Err(RouterWidgetError::Unavailable)
+        }
+    }
+
+    pub fn register_route_redirect(&self, from: &str, to: &str) -> Result<(), RouterWidgetError> {
+        if let Some(mut inner) = self.borrow_mut() {
+            Ok(inner.register_route_redirect(from, to)?)
+        } else {
+            Err(RouterWidgetError::Unavailable)
+        }
+    }
+
+    pub fn set_match_policy(&self, policy: MatchPolicy) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_match_policy(policy);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn set_history_limit(&self, limit: HistoryLimit) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_history_limit(limit);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn history_limit(&self) -> HistoryLimit {
+        self.borrow()
+            .map(|inner| inner.router.history_limit())
+            .unwrap_or_default()
+    }
+
+    pub fn set_keep_alive(&self, cx: &mut Cx, policy: RouterKeepAlive) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_keep_alive(cx, policy);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn set_route_keep_alive(
+        &self,
+        cx: &mut Cx,
+        route_id: LiveId,
+        policy: RouterKeepAlive,
+    ) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_route_keep_alive(cx, route_id, policy);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn set_max_route_widgets(&self, cx: &mut Cx, max: usize) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_max_route_widgets(cx, max);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn live_route_widgets(&self) -> Vec<LiveId> {
+        self.borrow()
+            .map(|inner| inner.live_route_widgets())
+            .unwrap_or_default()
+    }
+
+    pub fn evict_route_widget(&self, cx: &mut Cx, route_id: LiveId) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.evict_route_widget(cx, route_id)
+        } else {
+            false
+        }
+    }
+
+    pub fn preload(&self, cx: &mut Cx, route_id: LiveId) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.preload(cx, route_id)
+        } else {
+            false
+        }
+    }
+
+    pub fn preload_path(&self, cx: &mut Cx, path: &str) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.preload_path(cx, path)
+        } else {
+            false
+        }
+    }
+
+    pub fn hover_preload(&self, cx: &mut Cx, route_id: LiveId) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.hover_preload(cx, route_id)
+        } else {
+            false
+        }
+    }
+
+    pub fn hover_preload_path(&self, cx: &mut Cx, path: &str) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.hover_preload_path(cx, path)
+        } else {
+            false
+        }
+    }
+
+    pub fn set_route_preload(&self, cx: &mut Cx, route_id: LiveId, preload: RouterPreload) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_route_preload(cx, route_id, preload);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn is_overlay_route(&self, route_id: LiveId) -> bool {
+        self.borrow()
+            .map(|inner| inner.is_overlay_route(route_id))
+            .unwrap_or(false)
+    }
+
+    pub fn present_overlay(&self, cx: &mut Cx, route_id: LiveId) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.present_overlay(cx, route_id)
+        } else {
+            false
+        }
+    }
+
+    pub fn dismiss_overlay(&self, cx: &mut Cx) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.dismiss_overlay(cx)
+        } else {
+            false
+        }
+    }
+
+    pub fn switch_stack(&self, cx: &mut Cx, stack_id: LiveId) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.switch_stack(cx, stack_id)
+        } else {
+            false
+        }
+    }
+
+    pub fn add_stack(&self, stack_id: LiveId, root_route_id: LiveId) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.add_stack(stack_id, root_route_id)
+        } else {
+            false
+        }
+    }
+
+    pub fn remove_stack(&self, stack_id: LiveId) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.remove_stack(stack_id)
+        } else {
+            false
+        }
+    }
+
+    pub fn active_stack(&self) -> LiveId {
+        self.borrow()
+            .map(|inner| inner.active_stack())
+            .unwrap_or_default()
+    }
+
+    pub fn stack_ids(&self) -> Vec<LiveId> {
+        self.borrow()
+            .map(|inner| inner.stack_ids())
+            .unwrap_or_default()
+    }
+
+    pub fn stack_history(&self, stack_id: LiveId) -> Option<NavigationHistory> {
+        self.borrow()?.stack_history(stack_id).cloned()
+    }
+
+    pub fn route_diagnostics(&self) -> Vec<RouteDiagnostic> {
+        if let Some(inner) = self.borrow() {
+            inner.route_diagnostics().to_vec()
+        } else {
+            Vec::new()
+        }
+    }
+
+    pub fn unregister_route(&self, route_id: LiveId) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.unregister_route(route_id)
+        } else {
+            false
+        }
+    }
+
+    pub fn unregister_route_pattern(
+        &self,
+        pattern: &str,
+    ) -> Result<Vec<LiveId>, RouterWidgetError> {
+        if let Some(mut inner) = self.borrow_mut() {
+            Ok(inner.unregister_route_pattern(pattern)?)
+        } else {
+            Err(RouterWidgetError::Unavailable)
+        }
+    }
+
+    pub fn replace_route_pattern(
+        &self,
+        route_id: LiveId,
+        pattern: &str,
+    ) -> Result<(), RouterWidgetError> {
+        if let Some(mut inner) = self.borrow_mut() {
+            Ok(inner.replace_route_pattern(route_id, pattern)?)
+        } else {
+            Err(RouterWidgetError::Unavailable)
+        }
+    }
+
+    /// Build a URL for a route in this router or any nested child router.
+    pub fn url_for(
+        &self,
+        route_id: LiveId,
+        params: &RouteParams,
+        query: &RouteQuery,
+        hash: &str,
+    ) -> Result<String, RouterWidgetError> {
+        if let Some(inner) = self.borrow() {
+            Ok(inner.url_for(route_id, params, query, hash)?)
+        } else {
+            Err(RouterWidgetError::Unavailable)
+        }
+    }
+
+    /// Build a URL for a route from typed `RouteArgs`; see [`Self::url_for`].
+    pub fn url_for_args<T: RouteArgs>(
+        &self,
+        route_id: LiveId,
+        args: &T,
+        hash: &str,
+    ) -> Result<String, RouterWidgetError> {
+        self.url_for(route_id, &args.to_params(), &args.to_query(), hash)
+    }
+}
+
+/// Pattern each of `route_ids` now resolves by id (`None` once it has no pattern left).
+fn remaining_route_patterns(
+    router: &Router,
+    route_ids: &[LiveId],
+) -> Vec<(LiveId, Option<String>)> {
+    route_ids
+        .iter()
+        .map(|route_id| {
+            let pattern = router
+                .route_registry
+                .get_pattern(*route_id)
+                .map(|pattern| pattern.to_string());
+            (*route_id, pattern)
+        })
+        .collect()
+}
+
+/// Drop diagnostics involving `route_id`, on either side of the conflict.
+fn forget_route_diagnostics(diagnostics: &mut Vec<RouteDiagnostic>, route_id: LiveId) {
+    diagnostics.retain(|d| d.route_id != route_id && d.conflicting_route_id != route_id);
+}
+
+/// Drop diagnostics involving `pattern` (compared in its normalized form).
+fn forget_pattern_diagnostics(diagnostics: &mut Vec<RouteDiagnostic>, pattern: &str) {
+    let Ok(target) = crate::pattern::RoutePattern::parse(pattern) else {
+        return;
+    };
+    let target = target.to_string();
+    diagnostics.retain(|d| d.pattern != target && d.conflicting_pattern != target);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use makepad_live_id::live_id;
+
+    #[test]
+    fn unregistering_a_pattern_reports_the_remaining_ones() {
+        let mut router = Router::new(Route::new(live_id!(home)));
+        router
+            .register_route_pattern("/user/:id", live_id!(user))
+            .unwrap();
+        router
+            .register_route_pattern("/profile/:id", live_id!(user))
+            .unwrap();
+        router
+            .register_route_pattern("/about", live_id!(about))
+            .unwrap();
+
+        let affected = router.unregister_route_pattern("/profile/:id").unwrap();
+        assert_eq!(
+            remaining_route_patterns(&router, &affected),
+            vec![(live_id!(user), Some("/user/:id".to_string()))]
+        );
+
+        let affected = router.unregister_route_pattern("/about").unwrap();
+        assert_eq!(
+            remaining_route_patterns(&router, &affected),
+            vec![(live_id!(about), None)]
+        );
+    }
+
+    fn diagnostics_for(patterns: &[(&str, LiveId)]) -> Vec<RouteDiagnostic> {
+        let mut router = Router::new(Route::new(live_id!(home)));
+        for (pattern, route_id) in patterns {
+            router.register_route_pattern(pattern, *route_id).unwrap();
+        }
+        router.route_registry.take_diagnostics()
+    }
+
+    #[test]
+    fn diagnostics_are_forgotten_with_their_routes_and_patterns() {
+        let patterns = [
+            ("/user/:id", live_id!(user)),
+            ("/user/:name", live_id!(user_name)),
+            ("/docs/:page", live_id!(docs_page)),
+            ("/docs/:slug", live_id!(docs_slug)),
+        ];
+        let all = diagnostics_for(&patterns);
+        assert_eq!(all.len(), 2);
+
+        let mut diagnostics = all.clone();
+        forget_route_diagnostics(&mut diagnostics, live_id!(user));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].route_id, live_id!(docs_slug));
+
+        // Patterns are compared normalized; unparsable ones change nothing.
+        let mut diagnostics = all.clone();
+        forget_pattern_diagnostics(&mut diagnostics, "docs/:slug/");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].route_id, live_id!(user_name));
+        forget_pattern_diagnostics(&mut diagnostics, "/user/:");
+        assert_eq!(diagnostics.len(), 1);
+    }
 }
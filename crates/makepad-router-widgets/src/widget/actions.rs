@@ -14,6 +14,16 @@ impl RouterWidget {
         if let Some(primary_action) = primary_action {
             self.pending_actions.push(primary_action);
         }
+        if let Some(old_route_id) = old_route_id.filter(|id| *id != new_route.id) {
+            if self.is_overlay_route(old_route_id) {
+                self.pending_actions
+                    .push(RouterAction::OverlayDismissed(old_route_id));
+            }
+        }
+        if old_route_id != Some(new_route.id) && self.is_overlay_route(new_route.id) {
+            self.pending_actions
+                .push(RouterAction::OverlayPresented(new_route.id));
+        }
         self.pending_actions.push(RouterAction::RouteChanged {
             from: old_route_id,
             to: new_route.id,
@@ -21,10 +31,13 @@ impl RouterWidget {
     }
 
     pub(super) fn flush_router_actions(&mut self, cx: &mut Cx, _scope: &mut Scope) {
-        if self.pending_actions.is_empty() {
+        if self.pending_actions.is_empty() && self.pending_nav_events.is_empty() {
             return;
         }
         let uid = self.widget_uid();
+        for event in self.pending_nav_events.drain(..) {
+            cx.widget_action(uid, event);
+        }
         for action in self.pending_actions.drain(..) {
             cx.widget_action(uid, action);
         }
@@ -1,7 +1,7 @@
 use crate::route::Route;
-use makepad_widgets::Cx;
+use makepad_widgets::{Cx, LiveId, WidgetRef};
 
-use super::RouterWidget;
+use super::{RouterNavEvent, RouterTransitionState, RouterWidget};
 
 impl RouterWidget {
     pub fn on_route_change<F>(&mut self, callback: F)
@@ -11,8 +11,22 @@ impl RouterWidget {
         self.callbacks.route_change.push(Box::new(callback));
     }
 
+    pub fn on_nav_event<F>(&mut self, callback: F)
+    where
+        F: Fn(&mut Cx, &RouterNavEvent) + Send + Sync + 'static,
+    {
+        self.callbacks.nav_event.push(Box::new(callback));
+    }
+
+    pub fn on_route_dispose<F>(&mut self, callback: F)
+    where
+        F: Fn(&mut Cx, LiveId, &WidgetRef) + Send + Sync + 'static,
+    {
+        self.callbacks.route_dispose.push(Box::new(callback));
+    }
+
     pub(super) fn dispatch_route_change(
-        &self,
+        &mut self,
         cx: &mut Cx,
         old_route: Option<Route>,
         new_route: Route,
@@ -20,5 +34,86 @@ impl RouterWidget {
         for callback in &self.callbacks.route_change {
             callback(cx, old_route.clone(), new_route.clone());
         }
+        if self.nav_events_enabled() {
+            let from = old_route.map(|route| route.id);
+            let transition = self.transition_rt.state.as_mut();
+            for event in commit_events(from, new_route.id, transition) {
+                self.emit_nav_event(cx, event);
+            }
+        }
+        self.evict_route_widgets(cx);
+    }
+}
+
+/// Events reporting a commit: `NavigationCommitted`, then `TransitionStarted` for the transition
+/// the commit started.
+fn commit_events(
+    from: Option<LiveId>,
+    to: LiveId,
+    transition: Option<&mut RouterTransitionState>,
+) -> Vec<RouterNavEvent> {
+    let mut events = vec![RouterNavEvent::NavigationCommitted { from, to }];
+    events.extend(transition.and_then(RouterTransitionState::take_started_event));
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::{RouterTransitionDirection, RouterTransitionPreset};
+    use makepad_live_id::live_id;
+
+    fn transition(from: LiveId, to: LiveId) -> RouterTransitionState {
+        RouterTransitionState {
+            from_route: from,
+            to_route: to,
+            preset: RouterTransitionPreset::Fade,
+            direction: RouterTransitionDirection::Forward,
+            start_time: None,
+            duration: 0.3,
+            progress: 0.0,
+            announced: false,
+        }
+    }
+
+    #[test]
+    fn commit_is_reported_before_the_transition_it_starts() {
+        let mut state = transition(live_id!(home), live_id!(settings));
+        let events = commit_events(Some(live_id!(home)), live_id!(settings), Some(&mut state));
+        assert!(matches!(
+            events.as_slice(),
+            [
+                RouterNavEvent::NavigationCommitted { from: Some(from), to },
+                RouterNavEvent::TransitionStarted {
+                    from: t_from,
+                    to: t_to,
+                    preset: RouterTransitionPreset::Fade,
+                },
+            ] if *from == live_id!(home)
+                && *to == live_id!(settings)
+                && *t_from == live_id!(home)
+                && *t_to == live_id!(settings)
+        ));
+        assert!(state.announced);
+    }
+
+    #[test]
+    fn transition_is_reported_once() {
+        let mut state = transition(live_id!(home), live_id!(settings));
+        commit_events(Some(live_id!(home)), live_id!(settings), Some(&mut state));
+        let events = commit_events(Some(live_id!(settings)), live_id!(about), Some(&mut state));
+        assert!(matches!(
+            events.as_slice(),
+            [RouterNavEvent::NavigationCommitted { .. }]
+        ));
+    }
+
+    #[test]
+    fn commit_without_transition_only_reports_the_commit() {
+        let events = commit_events(None, live_id!(home), None);
+        assert!(matches!(
+            events.as_slice(),
+            [RouterNavEvent::NavigationCommitted { from: None, to }] if *to == live_id!(home)
+        ));
     }
 }
@@ -1,7 +1,8 @@
+use crate::navigation::HistoryLimit;
 use crate::route::Route;
 use crate::router::RouterAction;
 
-use super::RouterTransitionSpec;
+use super::{RouterKeepAlive, RouterTransitionSpec};
 use makepad_widgets::LiveId;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -12,6 +13,18 @@ pub enum RouterBlockReason {
     NoHistory,
     CapabilityDisabled,
     RedirectLimit,
+    /// Another navigation is waiting on an async guard or before-leave hook.
+    Pending,
+    /// An async guard or before-leave hook timed out with a `Block` decision.
+    Timeout,
+    /// An async guard or before-leave hook dropped its sender without replying.
+    HookDropped,
+    /// The target route's resolver failed (see `route_load_state`).
+    ResolveFailed,
+    /// The pending navigation was dropped by `cancel_pending_navigation` or `switch_stack`.
+    Cancelled,
+    /// The pending navigation was replaced by a newer one (`supersede_pending_navigation`).
+    Superseded,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -42,6 +55,20 @@ pub struct RouterConfig {
     pub not_found_route: LiveId,
     pub default_transition: LiveId,
     pub persist_state: bool,
+    /// Seconds to wait for an async guard/hook before blocking (`0.0` waits forever).
+    pub async_guard_timeout: f64,
+    /// Seconds to wait for a route resolver before blocking (`0.0` waits forever).
+    pub resolver_timeout: f64,
+    /// Let a new navigation cancel a pending one instead of being rejected.
+    pub supersede_pending: bool,
+    /// History depth limit (unbounded by default).
+    pub history_limit: HistoryLimit,
+    /// Keep-alive policy for routes without their own `route_keep_alive`.
+    pub keep_alive: RouterKeepAlive,
+    /// Route widgets kept under `RouterKeepAlive::Lru` (`0` = unbounded).
+    pub max_route_widgets: usize,
+    /// Seconds a preload lasts if its route is not shown (`0.0` lasts until it is).
+    pub preload_ttl: f64,
 }
 
 impl Default for RouterConfig {
@@ -52,6 +79,13 @@ impl Default for RouterConfig {
             not_found_route: LiveId(0),
             default_transition: LiveId(0),
             persist_state: false,
+            async_guard_timeout: 0.0,
+            resolver_timeout: 0.0,
+            supersede_pending: false,
+            history_limit: HistoryLimit::default(),
+            keep_alive: RouterKeepAlive::default(),
+            max_route_widgets: 0,
+            preload_ttl: 30.0,
         }
     }
 }
@@ -94,6 +128,23 @@ pub enum RouterCommand {
     SetStack {
         stack: Vec<Route>,
     },
+    /// Drop the navigation waiting on an async guard or hook (`changed` reports whether one was).
+    CancelPending,
+    /// Run the last navigation whose resolver failed again.
+    RetryNavigation,
+    /// Make a named history stack active (pops it to its root if it already is).
+    SwitchStack {
+        stack_id: LiveId,
+    },
+    AddStack {
+        stack_id: LiveId,
+        root_route_id: LiveId,
+    },
+    RemoveStack {
+        stack_id: LiveId,
+    },
+    /// Go back if the current route is an overlay.
+    DismissOverlay,
 }
 
 #[derive(Clone, Debug)]
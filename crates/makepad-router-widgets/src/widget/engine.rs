@@ -1,10 +1,11 @@
+use crate::navigation::HistoryEviction;
 use crate::route::Route;
 use crate::router::RouterAction;
 use makepad_widgets::*;
 
 use super::{
     RouterBlockReason, RouterCommand, RouterConfig, RouterDispatchResult, RouterWidget,
-    RouterWidgetRef,
+    RouterWidgetError, RouterWidgetRef,
 };
 
 impl RouterWidget {
@@ -28,6 +29,19 @@ impl RouterWidget {
         }
 
         self.persist_state = config.persist_state;
+        self.async_guard_timeout = config.async_guard_timeout;
+        self.resolver_timeout = config.resolver_timeout;
+        self.supersede_pending_navigation = config.supersede_pending;
+        self.max_history_depth = config.history_limit.max_depth;
+        self.history_eviction = match config.history_limit.eviction {
+            HistoryEviction::DropOldest => live_id!(drop_oldest),
+            HistoryEviction::CollapseDuplicates => live_id!(collapse_duplicates),
+            HistoryEviction::KeepRoot => live_id!(keep_root),
+        };
+        self.router.set_history_limit(config.history_limit);
+        self.keep_alive = config.keep_alive.live_id();
+        self.max_route_widgets = config.max_route_widgets;
+        self.preload_ttl = config.preload_ttl;
     }
 
     fn primary_action_for_command(
@@ -41,12 +55,19 @@ impl RouterWidget {
             RouterCommand::ReplaceRoute { .. } | RouterCommand::ReplacePath { .. } => {
                 to.cloned().map(RouterAction::Replace)
             }
-            RouterCommand::Back { .. } => Some(RouterAction::Back),
+            RouterCommand::Back { .. } | RouterCommand::DismissOverlay => Some(RouterAction::Back),
             RouterCommand::Forward { .. } => Some(RouterAction::Forward),
             RouterCommand::Reset { .. } | RouterCommand::SetStack { .. } => {
                 to.cloned().map(RouterAction::Reset)
             }
-            RouterCommand::Pop | RouterCommand::PopTo { .. } | RouterCommand::PopToRoot => None,
+            RouterCommand::SwitchStack { stack_id } => Some(RouterAction::StackSwitched(*stack_id)),
+            RouterCommand::Pop
+            | RouterCommand::PopTo { .. }
+            | RouterCommand::PopToRoot
+            | RouterCommand::CancelPending
+            | RouterCommand::RetryNavigation
+            | RouterCommand::AddStack { .. }
+            | RouterCommand::RemoveStack { .. } => None,
         }
     }
 
@@ -61,7 +82,9 @@ impl RouterWidget {
             | RouterCommand::Forward { .. }
             | RouterCommand::Pop
             | RouterCommand::PopTo { .. }
-            | RouterCommand::PopToRoot => {
+            | RouterCommand::PopToRoot
+            | RouterCommand::SwitchStack { .. }
+            | RouterCommand::DismissOverlay => {
                 if from == to {
                     Some(RouterBlockReason::NoHistory)
                 } else {
@@ -81,6 +104,10 @@ impl RouterWidget {
                     None
                 }
             }
+            RouterCommand::CancelPending
+            | RouterCommand::RetryNavigation
+            | RouterCommand::AddStack { .. }
+            | RouterCommand::RemoveStack { .. } => None,
         }
     }
 
@@ -127,6 +154,15 @@ impl RouterWidget {
             RouterCommand::PopTo { route_id } => self.pop_to(cx, *route_id),
             RouterCommand::PopToRoot => self.pop_to_root(cx),
             RouterCommand::SetStack { stack } => self.set_stack(cx, stack.clone()),
+            RouterCommand::CancelPending => self.cancel_pending_navigation(cx),
+            RouterCommand::RetryNavigation => self.retry_navigation(cx),
+            RouterCommand::SwitchStack { stack_id } => self.switch_stack(cx, *stack_id),
+            RouterCommand::AddStack {
+                stack_id,
+                root_route_id,
+            } => self.add_stack(*stack_id, *root_route_id),
+            RouterCommand::RemoveStack { stack_id } => self.remove_stack(*stack_id),
+            RouterCommand::DismissOverlay => self.dismiss_overlay(cx),
         };
 
         let to = self.router.current_route().cloned();
@@ -161,12 +197,12 @@ impl RouterWidgetRef {
         }
     }
 
-    pub fn apply_config(&self, config: RouterConfig) -> Result<(), String> {
+    pub fn apply_config(&self, config: RouterConfig) -> Result<(), RouterWidgetError> {
         if let Some(mut inner) = self.borrow_mut() {
             inner.apply_config(config);
             Ok(())
         } else {
-            Err("Cannot borrow router widget".to_string())
+            Err(RouterWidgetError::Unavailable)
         }
     }
 }
@@ -0,0 +1,71 @@
+//! Errors returned by `RouterWidgetRef` methods.
+
+use crate::error::{RegistryError, UrlForError};
+use std::fmt;
+
+/// Error produced by a `RouterWidgetRef` call: the core error of the underlying router, or no
+/// router to call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RouterWidgetError {
+    /// The ref does not point at a mounted router.
+    Unavailable,
+    /// Registering or unregistering a route pattern failed.
+    Registry(RegistryError),
+    /// Building a URL failed.
+    UrlFor(UrlForError),
+}
+
+impl fmt::Display for RouterWidgetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unavailable => write!(f, "Router is not available"),
+            Self::Registry(err) => write!(f, "{}", err),
+            Self::UrlFor(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for RouterWidgetError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Unavailable => None,
+            Self::Registry(err) => Some(err),
+            Self::UrlFor(err) => Some(err),
+        }
+    }
+}
+
+impl From<RegistryError> for RouterWidgetError {
+    fn from(err: RegistryError) -> Self {
+        Self::Registry(err)
+    }
+}
+
+impl From<UrlForError> for RouterWidgetError {
+    fn from(err: UrlForError) -> Self {
+        Self::UrlFor(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::PatternError;
+    use std::error::Error;
+
+    #[test]
+    fn router_widget_error_wraps_core_errors() {
+        assert_eq!(
+            RouterWidgetError::Unavailable.to_string(),
+            "Router is not available"
+        );
+        assert!(RouterWidgetError::Unavailable.source().is_none());
+
+        let err = RouterWidgetError::from(RegistryError::from(PatternError::Empty));
+        assert_eq!(err.to_string(), "Pattern cannot be empty");
+        assert!(err.source().is_some());
+
+        let err = RouterWidgetError::from(UrlForError::UnnamedWildcard);
+        assert_eq!(err, RouterWidgetError::UrlFor(UrlForError::UnnamedWildcard));
+    }
+}
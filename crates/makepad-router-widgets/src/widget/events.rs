@@ -0,0 +1,118 @@
+//! Navigation lifecycle events (started, guard decisions, blocks, redirects, transitions, commit).
+
+use crate::guards::{
+    RouterBeforeLeaveDecision, RouterGuardDecision, RouterNavKind, RouterRedirect,
+};
+use makepad_widgets::*;
+
+use super::{RouterBlockReason, RouterTransitionPreset, RouterWidget};
+
+/// Which hook produced a `RouterNavEvent::GuardEvaluated`.
+///
+/// Indices are registration order for global hooks and evaluation order for scoped ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RouterGuardKind {
+    /// The built-in auth/role guard.
+    Auth,
+    Sync(usize),
+    Async(usize),
+    /// Parent-, prefix- or route-scoped guard.
+    Scoped(usize),
+    BeforeLeave(usize),
+    BeforeLeaveAsync(usize),
+    ScopedBeforeLeave(usize),
+}
+
+/// A step of a navigation, in the order it happens.
+///
+/// Delivered to `on_nav_event` callbacks as it happens, and as a widget action on the next event
+/// when `emit_nav_events` is set.
+#[derive(Clone, Debug)]
+pub enum RouterNavEvent {
+    /// A navigation request passed route resolution and is about to run guards.
+    ///
+    /// `switch_stack` and restoring persisted state do not run guards, so they report only
+    /// `NavigationCommitted`.
+    NavigationStarted {
+        kind: RouterNavKind,
+        from: Option<LiveId>,
+        to: Option<LiveId>,
+    },
+    /// A guard or before-leave hook decided (before-leave decisions map to `Allow`/`Block`).
+    GuardEvaluated {
+        guard: RouterGuardKind,
+        decision: RouterGuardDecision,
+    },
+    NavigationBlocked(RouterBlockReason),
+    /// A guard redirected the navigation; a new `GuardEvaluated` sequence follows.
+    Redirected(RouterRedirect),
+    /// Follows the `NavigationCommitted` of the navigation that started the transition.
+    TransitionStarted {
+        from: LiveId,
+        to: LiveId,
+        preset: RouterTransitionPreset,
+    },
+    /// The transition completed or was interrupted by a newer one.
+    TransitionFinished {
+        from: LiveId,
+        to: LiveId,
+    },
+    NavigationCommitted {
+        from: Option<LiveId>,
+        to: LiveId,
+    },
+}
+
+impl RouterWidget {
+    pub(super) fn nav_events_enabled(&self) -> bool {
+        self.emit_nav_events || !self.callbacks.nav_event.is_empty()
+    }
+
+    pub(super) fn emit_nav_event(&mut self, cx: &mut Cx, event: RouterNavEvent) {
+        for callback in &self.callbacks.nav_event {
+            callback(cx, &event);
+        }
+        if self.emit_nav_events {
+            self.pending_nav_events.push(event);
+        }
+    }
+
+    pub(super) fn emit_guard_evaluated(
+        &mut self,
+        cx: &mut Cx,
+        guard: RouterGuardKind,
+        decision: &RouterGuardDecision,
+    ) {
+        if self.nav_events_enabled() {
+            let decision = decision.clone();
+            self.emit_nav_event(cx, RouterNavEvent::GuardEvaluated { guard, decision });
+        }
+    }
+
+    pub(super) fn emit_before_leave_evaluated(
+        &mut self,
+        cx: &mut Cx,
+        guard: RouterGuardKind,
+        decision: RouterBeforeLeaveDecision,
+    ) {
+        let decision = match decision {
+            RouterBeforeLeaveDecision::Allow => RouterGuardDecision::Allow,
+            RouterBeforeLeaveDecision::Block => RouterGuardDecision::Block,
+        };
+        self.emit_guard_evaluated(cx, guard, &decision);
+    }
+
+    /// Record why a navigation did not happen and report it.
+    pub(super) fn block_navigation(&mut self, cx: &mut Cx, reason: RouterBlockReason) {
+        if self.nav_events_enabled() {
+            self.emit_nav_event(cx, RouterNavEvent::NavigationBlocked(reason.clone()));
+        }
+        self.last_blocked_reason = Some(reason);
+    }
+
+    pub(super) fn emit_redirected(&mut self, cx: &mut Cx, redirect: &RouterRedirect) {
+        if self.nav_events_enabled() {
+            self.emit_nav_event(cx, RouterNavEvent::Redirected(redirect.clone()));
+        }
+    }
+}
@@ -3,27 +3,47 @@ use crate::{
     url::RouterUrl,
 };
 use makepad_widgets::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use super::RouterTransitionState;
+use super::{RouterKeepAlive, RouterNavEvent, RouterPreload, RouterTransitionState};
 use crate::guards::{
-    RouterAsyncGuard, RouterBeforeLeaveAsync, RouterBeforeLeaveSync, RouterSyncGuard,
+    RouterAsyncGuard, RouterAsyncTimeout, RouterAuthGuard, RouterBeforeLeaveAsync,
+    RouterBeforeLeaveDecision, RouterBeforeLeaveSync, RouterGuardDecision, RouterGuardScope,
+    RouterLoadState, RouterResolveResult, RouterResolver, RouterRouteData, RouterScopedBeforeLeave,
+    RouterScopedGuard, RouterSyncGuard,
 };
 use crate::route::Route;
 
 type RouteChangeCallback = Box<dyn Fn(&mut Cx, Option<Route>, Route) + Send + Sync>;
+type NavEventCallback = Box<dyn Fn(&mut Cx, &RouterNavEvent) + Send + Sync>;
+type RouteDisposeCallback = Box<dyn Fn(&mut Cx, LiveId, &WidgetRef) + Send + Sync>;
 
 #[derive(Default)]
 pub(crate) struct RouterCallbacks {
     pub(crate) route_change: Vec<RouteChangeCallback>,
+    pub(crate) nav_event: Vec<NavEventCallback>,
+    pub(crate) route_dispose: Vec<RouteDisposeCallback>,
 }
 
 #[derive(Default)]
 pub(crate) struct RouterGuards {
     pub(crate) route_guards: Vec<RouterSyncGuard>,
     pub(crate) route_guards_async: Vec<RouterAsyncGuard>,
+    /// Per-guard timeouts, index-aligned with `route_guards_async`.
+    pub(crate) route_guard_timeouts: Vec<Option<RouterAsyncTimeout<RouterGuardDecision>>>,
     pub(crate) before_leave_hooks: Vec<RouterBeforeLeaveSync>,
     pub(crate) before_leave_hooks_async: Vec<RouterBeforeLeaveAsync>,
+    /// Per-hook timeouts, index-aligned with `before_leave_hooks_async`.
+    pub(crate) before_leave_timeouts: Vec<Option<RouterAsyncTimeout<RouterBeforeLeaveDecision>>>,
+    /// Timeout for async guards without their own (overrides `async_guard_timeout`).
+    pub(crate) guard_timeout: Option<RouterAsyncTimeout<RouterGuardDecision>>,
+    /// Timeout for async before-leave hooks without their own.
+    pub(crate) before_leave_timeout: Option<RouterAsyncTimeout<RouterBeforeLeaveDecision>>,
+    pub(crate) auth_guard: Option<RouterAuthGuard>,
+    pub(crate) scoped_guards: Vec<(RouterGuardScope, RouterScopedGuard)>,
+    pub(crate) scoped_before_leave_hooks: Vec<(RouterGuardScope, RouterScopedBeforeLeave)>,
+    /// Guards of the routes hosting this router in enclosing routers, outermost first.
+    pub(crate) inherited_guards: Vec<RouterScopedGuard>,
 }
 
 #[derive(Default)]
@@ -73,6 +93,28 @@ impl RouterUrlCache {
     }
 }
 
+#[derive(Default)]
+pub(crate) struct RouterResolvers {
+    pub(crate) resolvers: HashMap<LiveId, RouterResolver>,
+    /// Last data resolved for each route id, with the route it was resolved for.
+    pub(crate) data: HashMap<LiveId, (Route, RouterRouteData)>,
+    /// Routes without an entry are `Idle`.
+    pub(crate) states: HashMap<LiveId, RouterLoadState>,
+    /// Preloaded data by the exact route it was resolved for; moved into `data` when a
+    /// navigation to that route uses it.
+    pub(crate) preloaded: Vec<(Route, RouterRouteData)>,
+    pub(crate) preloading: Vec<(Route, ToUIReceiver<RouterResolveResult>)>,
+}
+
+impl RouterResolvers {
+    /// Drop the data, load state and preloaded data of `route_id` (its resolver stays).
+    pub(crate) fn clear_route_data(&mut self, route_id: LiveId) {
+        self.data.remove(&route_id);
+        self.states.remove(&route_id);
+        self.preloaded.retain(|(route, _)| route.id != route_id);
+    }
+}
+
 pub(crate) struct RouterCaches {
     pub(crate) route_registry_epoch: u64,
     pub(crate) nested_prefix_cache_epoch: u64,
@@ -104,6 +146,14 @@ pub(crate) struct RouterRouteMaps {
     pub(crate) patterns: ComponentMap<LiveId, String>,
     pub(crate) transition_overrides: ComponentMap<LiveId, LiveId>,
     pub(crate) transition_duration_overrides: ComponentMap<LiveId, f64>,
+    /// Routes declared with `route_overlay: true`.
+    pub(crate) overlays: HashSet<LiveId>,
+    pub(crate) keep_alive_overrides: ComponentMap<LiveId, RouterKeepAlive>,
+    /// Instantiated routes, least recently shown first.
+    pub(crate) lru: Vec<LiveId>,
+    pub(crate) preload: ComponentMap<LiveId, RouterPreload>,
+    /// Preloaded widgets whose route has not been shown yet.
+    pub(crate) preloaded_widgets: HashSet<LiveId>,
 }
 
 #[derive(Default)]
@@ -113,6 +163,8 @@ pub(crate) struct PointerCleanup {
 }
 
 pub(crate) struct RouterDrawLists {
+    /// Page drawn beneath an overlay route.
+    pub(crate) underlay: DrawList2d,
     pub(crate) from: DrawList2d,
     pub(crate) to: DrawList2d,
     pub(crate) inspector: DrawList2d,
@@ -121,6 +173,7 @@ pub(crate) struct RouterDrawLists {
 impl RouterDrawLists {
     pub(crate) fn new(cx: &mut Cx) -> Self {
         Self {
+            underlay: DrawList2d::new(cx),
             from: DrawList2d::new(cx),
             to: DrawList2d::new(cx),
             inspector: DrawList2d::new(cx),
@@ -137,6 +190,15 @@ impl ScriptNew for RouterDrawLists {
 impl ScriptApply for RouterDrawLists {}
 impl ScriptHook for RouterDrawLists {}
 
+#[derive(Default)]
+pub(crate) struct PreloadRuntime {
+    /// `on_idle` routes waiting to be preloaded.
+    pub(crate) queue: Vec<LiveId>,
+    pub(crate) next_frame: NextFrame,
+    /// When each route's preload (widget and data) expires, see `preload_ttl`.
+    pub(crate) expiry: HashMap<LiveId, Timer>,
+}
+
 #[derive(Default)]
 pub(crate) struct TransitionRuntime {
     pub(crate) state: Option<RouterTransitionState>,
@@ -2,26 +2,41 @@
 
 use crate::{
     guards::{
-        RouterAsyncDecision, RouterBeforeLeaveDecision, RouterGuardDecision, RouterNavContext,
-        RouterNavKind, RouterRedirectTarget,
+        RouterAsyncDecision, RouterBeforeLeaveDecision, RouterGuardDecision, RouterLoadState,
+        RouterNavContext, RouterNavKind, RouterRedirectTarget, RouterResolveError,
+        RouterResolveResult,
     },
+    meta::RouteMeta,
     route::Route,
+    router::Router,
 };
 use makepad_widgets::*;
 
-use super::{ResolvedPathIntent, RouterBlockReason, RouterNavRequest, RouterWidget};
+use super::{
+    ResolvedPathIntent, RouterBlockReason, RouterGuardKind, RouterNavEvent, RouterNavRequest,
+    RouterWidget,
+};
 
-pub(super) const ROUTER_MAX_REDIRECTS: u8 = 8;
+pub(super) const ROUTER_MAX_REDIRECTS: u8 = crate::registry::ROUTE_MAX_REDIRECTS;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum PendingNavPhase {
     BeforeLeaveAsync,
     GuardAsync,
+    Resolve,
 }
 
 enum PendingAsyncRx {
     BeforeLeave(ToUIReceiver<RouterBeforeLeaveDecision>),
     Guard(ToUIReceiver<RouterGuardDecision>),
+    Resolve(ToUIReceiver<RouterResolveResult>),
+}
+
+/// Decision applied when the pending hook's timer fires before it replies.
+enum PendingTimeout {
+    BeforeLeave(RouterBeforeLeaveDecision),
+    Guard(RouterGuardDecision),
+    Resolve(RouterResolveResult),
 }
 
 pub(super) struct PendingNavigation {
@@ -32,6 +47,7 @@ pub(super) struct PendingNavigation {
     async_index: usize,
     redirect_depth: u8,
     rx: PendingAsyncRx,
+    timeout: Option<PendingTimeout>,
 }
 
 impl RouterWidget {
@@ -50,27 +66,56 @@ impl RouterWidget {
             return self.apply_request_bypassing_guards(cx, request);
         }
         if self.pending_navigation.is_some() {
-            self.last_blocked_reason = Some(RouterBlockReason::NoHistory);
-            return false;
+            match pending_conflict(self.supersede_pending_navigation) {
+                Ok(reason) => {
+                    self.abandon_pending_navigation(cx, reason);
+                }
+                Err(reason) => {
+                    self.block_navigation(cx, reason);
+                    return false;
+                }
+            }
         }
         let Some((context, leaving, resolved_path)) = self.resolve_nav_context(cx, &request) else {
-            self.last_blocked_reason = Some(Self::infer_resolution_block_reason(&request));
+            self.block_navigation(cx, Self::infer_resolution_block_reason(&request));
             return false;
         };
+        if redirect_depth == 0 && self.nav_events_enabled() {
+            let event = RouterNavEvent::NavigationStarted {
+                kind: context.kind,
+                from: context.from.as_ref().map(|route| route.id),
+                to: context.to.as_ref().map(|route| route.id),
+            };
+            self.emit_nav_event(cx, event);
+        }
 
         if !skip_before_leave
             && self.before_leave_hooks().is_empty()
             && !self.has_async_before_leave_hooks()
             && self.route_guards().is_empty()
             && !self.has_async_route_guards()
+            && self.auth_guard().is_none()
+            && !self.has_scoped_guards()
         {
-            return self.apply_request_bypassing_guards_resolved(cx, request, resolved_path);
+            return self.resolve_and_commit(cx, request, context, resolved_path, redirect_depth);
         }
 
         if !skip_before_leave && leaving {
-            for hook in self.before_leave_hooks() {
-                if hook(cx, &context) == RouterBeforeLeaveDecision::Block {
-                    self.last_blocked_reason = Some(RouterBlockReason::BeforeLeaveBlocked);
+            for index in 0..self.before_leave_hooks().len() {
+                let decision = (self.before_leave_hooks()[index])(cx, &context);
+                self.emit_before_leave_evaluated(cx, RouterGuardKind::BeforeLeave(index), decision);
+                if decision == RouterBeforeLeaveDecision::Block {
+                    self.block_navigation(cx, RouterBlockReason::BeforeLeaveBlocked);
+                    return false;
+                }
+            }
+            let scoped = self.scoped_before_leave_hooks_for(&context);
+            for (index, hook) in scoped.iter().enumerate() {
+                let decision = hook(cx, &context);
+                let guard = RouterGuardKind::ScopedBeforeLeave(index);
+                self.emit_before_leave_evaluated(cx, guard, decision);
+                if decision == RouterBeforeLeaveDecision::Block {
+                    self.block_navigation(cx, RouterBlockReason::BeforeLeaveBlocked);
                     return false;
                 }
             }
@@ -177,12 +222,17 @@ impl RouterWidget {
             _ => false,
         };
 
+        let from_meta = route_meta_or_empty(&self.router, from.as_ref());
+        let to_meta = route_meta_or_empty(&self.router, to.as_ref());
+
         Some((
             RouterNavContext {
                 kind,
                 from,
                 to,
                 to_path,
+                from_meta,
+                to_meta,
             },
             leaving,
             resolved_path,
@@ -219,20 +269,34 @@ impl RouterWidget {
     ) -> bool {
         loop {
             let mut redirected = None;
-            for guard in self.route_guards() {
-                match guard(cx, &context) {
+            // Global guards (the built-in auth guard first), then parent, prefix and route guards.
+            let auth = self
+                .auth_guard()
+                .is_some()
+                .then(|| self.evaluate_auth_guard(&context));
+            let scoped = self.scoped_guards_for(&context);
+            let guards = sync_guard_order(auth.is_some(), self.route_guards().len(), scoped.len());
+            for guard in guards {
+                let decision = match guard {
+                    RouterGuardKind::Sync(index) => (self.route_guards()[index])(cx, &context),
+                    RouterGuardKind::Scoped(index) => (scoped[index])(cx, &context),
+                    _ => auth.clone().unwrap_or(RouterGuardDecision::Allow),
+                };
+                self.emit_guard_evaluated(cx, guard, &decision);
+                match decision {
                     RouterGuardDecision::Allow => {}
                     RouterGuardDecision::Block => {
-                        self.last_blocked_reason = Some(RouterBlockReason::GuardBlocked);
+                        self.block_navigation(cx, RouterBlockReason::GuardBlocked);
                         return false;
                     }
                     RouterGuardDecision::Redirect(redirect) => {
                         if redirect_depth >= ROUTER_MAX_REDIRECTS {
-                            self.last_blocked_reason = Some(RouterBlockReason::RedirectLimit);
+                            self.block_navigation(cx, RouterBlockReason::RedirectLimit);
                             log!("Router: guard redirect limit reached");
                             return false;
                         }
                         redirect_depth += 1;
+                        self.emit_redirected(cx, &redirect);
                         request = Self::redirect_to_request(redirect.target, redirect.replace);
                         let Some((next_context, _, next_resolved_path)) =
                             self.resolve_nav_context(cx, &request)
@@ -261,7 +325,7 @@ impl RouterWidget {
                 );
             }
 
-            return self.apply_request_bypassing_guards_resolved(cx, request, resolved_path);
+            return self.resolve_and_commit(cx, request, context, resolved_path, redirect_depth);
         }
     }
 
@@ -275,17 +339,20 @@ impl RouterWidget {
         redirect_depth: u8,
     ) -> bool {
         let mut idx = start_index;
-        let hooks = self.before_leave_hooks_async();
-        while idx < hooks.len() {
-            match (hooks[idx])(cx, &context) {
-                RouterAsyncDecision::Immediate(RouterBeforeLeaveDecision::Allow) => {
+        while idx < self.before_leave_hooks_async().len() {
+            match (self.before_leave_hooks_async()[idx])(cx, &context) {
+                RouterAsyncDecision::Immediate(decision) => {
+                    let guard = RouterGuardKind::BeforeLeaveAsync(idx);
+                    self.emit_before_leave_evaluated(cx, guard, decision);
+                    if decision == RouterBeforeLeaveDecision::Block {
+                        self.block_navigation(cx, RouterBlockReason::BeforeLeaveBlocked);
+                        return false;
+                    }
                     idx += 1;
                 }
-                RouterAsyncDecision::Immediate(RouterBeforeLeaveDecision::Block) => {
-                    self.last_blocked_reason = Some(RouterBlockReason::BeforeLeaveBlocked);
-                    return false;
-                }
                 RouterAsyncDecision::Pending(rx) => {
+                    let timeout = self.before_leave_timeout(idx);
+                    self.arm_pending_timer(cx, timeout.as_ref().map(|t| t.seconds));
                     self.pending_navigation = Some(PendingNavigation {
                         request,
                         context,
@@ -294,6 +361,7 @@ impl RouterWidget {
                         async_index: idx,
                         redirect_depth,
                         rx: PendingAsyncRx::BeforeLeave(rx),
+                        timeout: timeout.map(|t| PendingTimeout::BeforeLeave(t.decision)),
                     });
                     return true;
                 }
@@ -313,20 +381,39 @@ impl RouterWidget {
         redirect_depth: u8,
     ) -> bool {
         let mut idx = start_index;
-        let guards = self.route_guards_async();
-        while idx < guards.len() {
-            match (guards[idx])(cx, &context) {
-                RouterAsyncDecision::Immediate(RouterGuardDecision::Allow) => idx += 1,
-                RouterAsyncDecision::Immediate(RouterGuardDecision::Block) => {
-                    self.last_blocked_reason = Some(RouterBlockReason::GuardBlocked);
+        while idx < self.route_guards_async().len() {
+            let decision = match (self.route_guards_async()[idx])(cx, &context) {
+                RouterAsyncDecision::Immediate(decision) => decision,
+                RouterAsyncDecision::Pending(rx) => {
+                    let timeout = self.route_guard_timeout(idx);
+                    self.arm_pending_timer(cx, timeout.as_ref().map(|t| t.seconds));
+                    self.pending_navigation = Some(PendingNavigation {
+                        request,
+                        context,
+                        resolved_path,
+                        phase: PendingNavPhase::GuardAsync,
+                        async_index: idx,
+                        redirect_depth,
+                        rx: PendingAsyncRx::Guard(rx),
+                        timeout: timeout.map(|t| PendingTimeout::Guard(t.decision)),
+                    });
+                    return true;
+                }
+            };
+            self.emit_guard_evaluated(cx, RouterGuardKind::Async(idx), &decision);
+            match decision {
+                RouterGuardDecision::Allow => idx += 1,
+                RouterGuardDecision::Block => {
+                    self.block_navigation(cx, RouterBlockReason::GuardBlocked);
                     return false;
                 }
-                RouterAsyncDecision::Immediate(RouterGuardDecision::Redirect(redirect)) => {
+                RouterGuardDecision::Redirect(redirect) => {
                     if redirect_depth >= ROUTER_MAX_REDIRECTS {
-                        self.last_blocked_reason = Some(RouterBlockReason::RedirectLimit);
+                        self.block_navigation(cx, RouterBlockReason::RedirectLimit);
                         log!("Router: guard redirect limit reached");
                         return false;
                     }
+                    self.emit_redirected(cx, &redirect);
                     let next_request = Self::redirect_to_request(redirect.target, redirect.replace);
                     return self.request_navigation_internal(
                         cx,
@@ -335,22 +422,86 @@ impl RouterWidget {
                         redirect_depth.saturating_add(1),
                     );
                 }
-                RouterAsyncDecision::Pending(rx) => {
-                    self.pending_navigation = Some(PendingNavigation {
-                        request,
-                        context,
-                        resolved_path,
-                        phase: PendingNavPhase::GuardAsync,
-                        async_index: idx,
-                        redirect_depth,
-                        rx: PendingAsyncRx::Guard(rx),
-                    });
-                    return true;
-                }
             }
         }
 
-        self.apply_request_bypassing_guards_resolved(cx, request, resolved_path)
+        self.resolve_and_commit(cx, request, context, resolved_path, redirect_depth)
+    }
+
+    /// Run the target route's resolver (if any), then commit the navigation.
+    fn resolve_and_commit(
+        &mut self,
+        cx: &mut Cx,
+        request: RouterNavRequest,
+        context: RouterNavContext,
+        resolved_path: Option<ResolvedPathIntent>,
+        redirect_depth: u8,
+    ) -> bool {
+        let Some(route) = context.to.clone() else {
+            return self.apply_request_bypassing_guards_resolved(cx, request, resolved_path);
+        };
+        let route_id = route.id;
+        // Data preloaded for exactly this route is used instead of resolving it again.
+        if self.take_preloaded_data(&route) {
+            return self.apply_request_bypassing_guards_resolved(cx, request, resolved_path);
+        }
+        let Some(resolver) = self.resolvers.resolvers.get(&route_id) else {
+            return self.apply_request_bypassing_guards_resolved(cx, request, resolved_path);
+        };
+        match resolver(cx, &context) {
+            RouterAsyncDecision::Immediate(result) => {
+                self.finish_resolve(cx, request, route, resolved_path, result)
+            }
+            RouterAsyncDecision::Pending(rx) => {
+                self.resolvers
+                    .states
+                    .insert(route_id, RouterLoadState::Loading);
+                let seconds = (self.resolver_timeout > 0.0).then_some(self.resolver_timeout);
+                self.arm_pending_timer(cx, seconds);
+                self.pending_navigation = Some(PendingNavigation {
+                    request,
+                    context,
+                    resolved_path,
+                    phase: PendingNavPhase::Resolve,
+                    async_index: 0,
+                    redirect_depth,
+                    rx: PendingAsyncRx::Resolve(rx),
+                    timeout: seconds
+                        .map(|_| PendingTimeout::Resolve(Err(RouterResolveError::TimedOut))),
+                });
+                true
+            }
+        }
+    }
+
+    fn finish_resolve(
+        &mut self,
+        cx: &mut Cx,
+        request: RouterNavRequest,
+        route: Route,
+        resolved_path: Option<ResolvedPathIntent>,
+        result: RouterResolveResult,
+    ) -> bool {
+        let route_id = route.id;
+        match result {
+            Ok(data) => {
+                self.resolvers.data.insert(route_id, (route, data));
+                self.resolvers
+                    .states
+                    .insert(route_id, RouterLoadState::Loaded);
+                self.failed_navigation = None;
+                self.apply_request_bypassing_guards_resolved(cx, request, resolved_path)
+            }
+            Err(error) => {
+                log!("Router: resolver for {:?} failed: {}", route_id, error);
+                self.resolvers
+                    .states
+                    .insert(route_id, RouterLoadState::Failed(error));
+                self.failed_navigation = Some(request);
+                self.block_navigation(cx, RouterBlockReason::ResolveFailed);
+                false
+            }
+        }
     }
 
     fn redirect_to_request(target: RouterRedirectTarget, replace: bool) -> RouterNavRequest {
@@ -427,7 +578,54 @@ impl RouterWidget {
         }
     }
 
-    pub(super) fn poll_pending_navigation(&mut self, cx: &mut Cx) {
+    /// Whether a navigation is waiting on an async guard or before-leave hook.
+    pub fn has_pending_navigation(&self) -> bool {
+        self.pending_navigation.is_some()
+    }
+
+    /// Drop the pending navigation (if any); its hook's late reply is ignored.
+    ///
+    /// The dropped navigation ends with `NavigationBlocked(RouterBlockReason::Cancelled)`.
+    /// Returns `true` if a navigation was pending.
+    pub fn cancel_pending_navigation(&mut self, cx: &mut Cx) -> bool {
+        self.abandon_pending_navigation(cx, RouterBlockReason::Cancelled)
+    }
+
+    /// Drop the pending navigation (if any), reporting it as blocked with `reason`.
+    pub(super) fn abandon_pending_navigation(
+        &mut self,
+        cx: &mut Cx,
+        reason: RouterBlockReason,
+    ) -> bool {
+        cx.stop_timer(self.pending_timer);
+        let Some(pending) = self.pending_navigation.take() else {
+            return false;
+        };
+        if let (PendingNavPhase::Resolve, Some(route)) = (pending.phase, &pending.context.to) {
+            self.resolvers.states.remove(&route.id);
+        }
+        self.block_navigation(cx, reason);
+        true
+    }
+
+    /// Run the last navigation whose resolver failed again (guards included).
+    pub fn retry_navigation(&mut self, cx: &mut Cx) -> bool {
+        match self.failed_navigation.take() {
+            Some(request) => self.request_navigation(cx, request),
+            None => false,
+        }
+    }
+
+    fn arm_pending_timer(&mut self, cx: &mut Cx, seconds: Option<f64>) {
+        cx.stop_timer(self.pending_timer);
+        self.pending_timer = match seconds {
+            Some(seconds) => cx.start_timeout(seconds),
+            None => Timer::empty(),
+        };
+    }
+
+    /// Poll the pending async hook; `timed_out` is set when its timer fired this event.
+    pub(super) fn poll_pending_navigation(&mut self, cx: &mut Cx, timed_out: bool) {
         let Some(pending) = self.pending_navigation.take() else {
             return;
         };
@@ -441,10 +639,16 @@ impl RouterWidget {
                 async_index,
                 redirect_depth,
                 rx: PendingAsyncRx::BeforeLeave(rx),
+                timeout,
             } => {
-                let decision = match rx.try_recv_flush() {
-                    Ok(v) => v,
-                    Err(std::sync::mpsc::TryRecvError::Empty) => {
+                let timeout = match timeout {
+                    Some(PendingTimeout::BeforeLeave(decision)) => Some(decision),
+                    _ => None,
+                };
+                let (decision, expired) = match poll_reply(rx.try_recv_flush(), timeout, timed_out)
+                {
+                    PendingReply::Decided { value, expired } => (value, expired),
+                    PendingReply::Waiting(timeout) => {
                         self.pending_navigation = Some(PendingNavigation {
                             request,
                             context,
@@ -453,14 +657,27 @@ impl RouterWidget {
                             async_index,
                             redirect_depth,
                             rx: PendingAsyncRx::BeforeLeave(rx),
+                            timeout: timeout.map(PendingTimeout::BeforeLeave),
                         });
                         return;
                     }
-                    Err(std::sync::mpsc::TryRecvError::Disconnected) => return,
+                    PendingReply::Dropped => {
+                        log!("Router: async before-leave hook dropped without replying");
+                        cx.stop_timer(self.pending_timer);
+                        self.block_navigation(cx, RouterBlockReason::HookDropped);
+                        return;
+                    }
                 };
+                if expired {
+                    log!("Router: async before-leave hook timed out");
+                }
+                cx.stop_timer(self.pending_timer);
+                let guard = RouterGuardKind::BeforeLeaveAsync(async_index);
+                self.emit_before_leave_evaluated(cx, guard, decision);
 
                 if decision != RouterBeforeLeaveDecision::Allow {
-                    self.last_blocked_reason = Some(RouterBlockReason::BeforeLeaveBlocked);
+                    let reason = block_reason(expired, RouterBlockReason::BeforeLeaveBlocked);
+                    self.block_navigation(cx, reason);
                     return;
                 }
                 let _ = self.run_before_leave_async(
@@ -480,10 +697,16 @@ impl RouterWidget {
                 async_index,
                 redirect_depth,
                 rx: PendingAsyncRx::Guard(rx),
+                timeout,
             } => {
-                let decision = match rx.try_recv_flush() {
-                    Ok(v) => v,
-                    Err(std::sync::mpsc::TryRecvError::Empty) => {
+                let timeout = match timeout {
+                    Some(PendingTimeout::Guard(decision)) => Some(decision),
+                    _ => None,
+                };
+                let (decision, expired) = match poll_reply(rx.try_recv_flush(), timeout, timed_out)
+                {
+                    PendingReply::Decided { value, expired } => (value, expired),
+                    PendingReply::Waiting(timeout) => {
                         self.pending_navigation = Some(PendingNavigation {
                             request,
                             context,
@@ -492,11 +715,22 @@ impl RouterWidget {
                             async_index,
                             redirect_depth,
                             rx: PendingAsyncRx::Guard(rx),
+                            timeout: timeout.map(PendingTimeout::Guard),
                         });
                         return;
                     }
-                    Err(std::sync::mpsc::TryRecvError::Disconnected) => return,
+                    PendingReply::Dropped => {
+                        log!("Router: async guard dropped without replying");
+                        cx.stop_timer(self.pending_timer);
+                        self.block_navigation(cx, RouterBlockReason::HookDropped);
+                        return;
+                    }
                 };
+                if expired {
+                    log!("Router: async guard timed out");
+                }
+                cx.stop_timer(self.pending_timer);
+                self.emit_guard_evaluated(cx, RouterGuardKind::Async(async_index), &decision);
 
                 match decision {
                     RouterGuardDecision::Allow => {
@@ -510,14 +744,16 @@ impl RouterWidget {
                         );
                     }
                     RouterGuardDecision::Block => {
-                        self.last_blocked_reason = Some(RouterBlockReason::GuardBlocked);
+                        let reason = block_reason(expired, RouterBlockReason::GuardBlocked);
+                        self.block_navigation(cx, reason);
                     }
                     RouterGuardDecision::Redirect(redirect) => {
                         if redirect_depth >= ROUTER_MAX_REDIRECTS {
-                            self.last_blocked_reason = Some(RouterBlockReason::RedirectLimit);
+                            self.block_navigation(cx, RouterBlockReason::RedirectLimit);
                             log!("Router: guard redirect limit reached");
                             return;
                         }
+                        self.emit_redirected(cx, &redirect);
                         let next_request =
                             Self::redirect_to_request(redirect.target, redirect.replace);
                         let _ = self.request_navigation_internal(
@@ -529,6 +765,48 @@ impl RouterWidget {
                     }
                 }
             }
+            PendingNavigation {
+                request,
+                context,
+                resolved_path,
+                phase: PendingNavPhase::Resolve,
+                async_index,
+                redirect_depth,
+                rx: PendingAsyncRx::Resolve(rx),
+                timeout,
+            } => {
+                let timeout = match timeout {
+                    Some(PendingTimeout::Resolve(result)) => Some(result),
+                    _ => None,
+                };
+                let result = match poll_reply(rx.try_recv_flush(), timeout, timed_out) {
+                    PendingReply::Decided { value, expired } => {
+                        if expired {
+                            log!("Router: route resolver timed out");
+                        }
+                        value
+                    }
+                    PendingReply::Waiting(timeout) => {
+                        self.pending_navigation = Some(PendingNavigation {
+                            request,
+                            context,
+                            resolved_path,
+                            phase: PendingNavPhase::Resolve,
+                            async_index,
+                            redirect_depth,
+                            rx: PendingAsyncRx::Resolve(rx),
+                            timeout: timeout.map(PendingTimeout::Resolve),
+                        });
+                        return;
+                    }
+                    PendingReply::Dropped => Err(RouterResolveError::Dropped),
+                };
+                cx.stop_timer(self.pending_timer);
+                let Some(route) = context.to else {
+                    return;
+                };
+                let _ = self.finish_resolve(cx, request, route, resolved_path, result);
+            }
             pending => {
                 // Mismatched pending state; keep it around.
                 self.pending_navigation = Some(pending);
@@ -536,3 +814,248 @@ impl RouterWidget {
         }
     }
 }
+
+/// How a new navigation treats one still pending: cancel the pending one with the `Ok`
+/// reason (`supersede_pending_navigation`), or reject the new one with the `Err` reason.
+fn pending_conflict(supersede: bool) -> Result<RouterBlockReason, RouterBlockReason> {
+    if supersede {
+        Ok(RouterBlockReason::Superseded)
+    } else {
+        Err(RouterBlockReason::Pending)
+    }
+}
+
+/// Outcome of polling a pending hook.
+#[derive(Debug, PartialEq)]
+enum PendingReply<T> {
+    /// The hook replied, or its timer fired and the timeout decision applies (`expired`).
+    Decided { value: T, expired: bool },
+    /// No reply yet; the timeout decision is kept for the next poll.
+    Waiting(Option<T>),
+    /// The hook dropped its sender without replying.
+    Dropped,
+}
+
+/// A reply that arrived wins over a timer that fired in the same event.
+fn poll_reply<T>(
+    reply: Result<T, std::sync::mpsc::TryRecvError>,
+    timeout: Option<T>,
+    timed_out: bool,
+) -> PendingReply<T> {
+    match (reply, timeout) {
+        (Ok(value), _) => PendingReply::Decided {
+            value,
+            expired: false,
+        },
+        (Err(std::sync::mpsc::TryRecvError::Empty), Some(value)) if timed_out => {
+            PendingReply::Decided {
+                value,
+                expired: true,
+            }
+        }
+        (Err(std::sync::mpsc::TryRecvError::Empty), timeout) => PendingReply::Waiting(timeout),
+        (Err(std::sync::mpsc::TryRecvError::Disconnected), _) => PendingReply::Dropped,
+    }
+}
+
+/// A timed-out block is reported as `Timeout` rather than the hook's own block reason.
+fn block_reason(expired: bool, blocked: RouterBlockReason) -> RouterBlockReason {
+    if expired {
+        RouterBlockReason::Timeout
+    } else {
+        blocked
+    }
+}
+
+/// Metadata of `route` for a guard context (empty when there is no route or none was declared).
+/// Sync guards in evaluation order: the auth guard, the `global` guards, then the `scoped` ones
+/// (see `scoped_guards_in_order`).
+fn sync_guard_order(
+    auth: bool,
+    global: usize,
+    scoped: usize,
+) -> impl Iterator<Item = RouterGuardKind> {
+    auth.then_some(RouterGuardKind::Auth)
+        .into_iter()
+        .chain((0..global).map(RouterGuardKind::Sync))
+        .chain((0..scoped).map(RouterGuardKind::Scoped))
+}
+
+pub(super) fn route_meta_or_empty(router: &Router, route: Option<&Route>) -> RouteMeta {
+    route
+        .and_then(|route| router.route_meta(route.id))
+        .cloned()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::guards::scoped_guards_in_order;
+    use super::*;
+    use crate::guards::RouterGuardScope;
+    use makepad_live_id::live_id;
+
+    /// Labels of the sync guards run for a navigation into `route_id` at `path`.
+    fn guard_run_order(
+        auth: bool,
+        global: &[&'static str],
+        inherited: &[&'static str],
+        scoped: &[(RouterGuardScope, &'static str)],
+        route_id: LiveId,
+        path: &str,
+    ) -> Vec<&'static str> {
+        let scoped = scoped_guards_in_order(scoped, inherited, route_id, path);
+        sync_guard_order(auth, global.len(), scoped.len())
+            .map(|guard| match guard {
+                RouterGuardKind::Auth => "auth",
+                RouterGuardKind::Sync(index) => global[index],
+                RouterGuardKind::Scoped(index) => scoped[index],
+                other => panic!("unexpected guard {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn guards_run_auth_global_inherited_prefixes_then_route() {
+        let scoped = [
+            (RouterGuardScope::Route(live_id!(user)), "route"),
+            (RouterGuardScope::from("/admin/users"), "/admin/users"),
+            (RouterGuardScope::Route(live_id!(other)), "other route"),
+            (RouterGuardScope::from("/admin"), "/admin"),
+            (RouterGuardScope::from("/settings"), "/settings"),
+        ];
+
+        assert_eq!(
+            guard_run_order(
+                true,
+                &["global 0", "global 1"],
+                &["parent"],
+                &scoped,
+                live_id!(user),
+                "/admin/users/42",
+            ),
+            [
+                "auth",
+                "global 0",
+                "global 1",
+                "parent",
+                "/admin",
+                "/admin/users",
+                "route"
+            ]
+        );
+        assert_eq!(
+            guard_run_order(false, &[], &[], &scoped, live_id!(other), "/settings"),
+            ["/settings", "other route"]
+        );
+    }
+
+    #[test]
+    fn prefix_guards_do_not_cover_longer_segments() {
+        let scoped = [
+            (RouterGuardScope::from("/admin"), "/admin"),
+            (RouterGuardScope::from("/"), "/"),
+        ];
+
+        assert_eq!(
+            guard_run_order(
+                false,
+                &["global"],
+                &[],
+                &scoped,
+                live_id!(administrator),
+                "/administrator",
+            ),
+            ["global", "/"]
+        );
+        assert_eq!(
+            guard_run_order(false, &[], &[], &scoped, live_id!(admin), "/admin/"),
+            ["/", "/admin"]
+        );
+    }
+
+    #[test]
+    fn guard_context_meta_defaults_to_empty() {
+        let mut router = Router::new(Route::new(live_id!(home)));
+        let meta = RouteMeta::new().with(live_id!(requires_auth), true);
+        router.set_route_meta(live_id!(admin), meta.clone());
+
+        assert_eq!(
+            route_meta_or_empty(&router, Some(&Route::new(live_id!(admin)))),
+            meta
+        );
+        assert!(route_meta_or_empty(&router, Some(&Route::new(live_id!(home)))).is_empty());
+        assert!(route_meta_or_empty(&router, None).is_empty());
+    }
+
+    #[test]
+    fn timed_out_hook_applies_its_timeout_decision() {
+        use std::sync::mpsc::TryRecvError;
+
+        let allow = RouterBeforeLeaveDecision::Allow;
+        assert_eq!(
+            poll_reply(Err(TryRecvError::Empty), Some(allow), true),
+            PendingReply::Decided {
+                value: allow,
+                expired: true
+            }
+        );
+        let block = RouterBeforeLeaveDecision::Block;
+        let PendingReply::Decided { value, expired } =
+            poll_reply(Err(TryRecvError::Empty), Some(block), true)
+        else {
+            panic!("a fired timer decides the hook");
+        };
+        assert_eq!(value, block);
+        assert_eq!(
+            block_reason(expired, RouterBlockReason::BeforeLeaveBlocked),
+            RouterBlockReason::Timeout
+        );
+    }
+
+    #[test]
+    fn pending_hook_waits_until_its_timer_fires() {
+        use std::sync::mpsc::TryRecvError;
+
+        let block = RouterBeforeLeaveDecision::Block;
+        assert_eq!(
+            poll_reply(Err(TryRecvError::Empty), Some(block), false),
+            PendingReply::Waiting(Some(block))
+        );
+        // Without a timeout the hook waits forever.
+        assert_eq!(
+            poll_reply::<RouterBeforeLeaveDecision>(Err(TryRecvError::Empty), None, true),
+            PendingReply::Waiting(None)
+        );
+        assert_eq!(
+            poll_reply::<RouterBeforeLeaveDecision>(Err(TryRecvError::Disconnected), None, true),
+            PendingReply::Dropped
+        );
+    }
+
+    #[test]
+    fn reply_wins_over_a_timer_firing_in_the_same_event() {
+        let reply = poll_reply(
+            Ok(RouterBeforeLeaveDecision::Allow),
+            Some(RouterBeforeLeaveDecision::Block),
+            true,
+        );
+        assert_eq!(
+            reply,
+            PendingReply::Decided {
+                value: RouterBeforeLeaveDecision::Allow,
+                expired: false
+            }
+        );
+        assert_eq!(
+            block_reason(false, RouterBlockReason::GuardBlocked),
+            RouterBlockReason::GuardBlocked
+        );
+    }
+
+    #[test]
+    fn pending_navigation_is_superseded_or_blocks_the_new_one() {
+        assert_eq!(pending_conflict(true), Ok(RouterBlockReason::Superseded));
+        assert_eq!(pending_conflict(false), Err(RouterBlockReason::Pending));
+    }
+}
@@ -1,10 +1,16 @@
 //! Public guard API plumbing for RouterWidget.
 
 use crate::guards::{
-    RouterAsyncDecision, RouterAsyncGuard, RouterBeforeLeaveAsync, RouterBeforeLeaveDecision,
-    RouterBeforeLeaveSync, RouterGuardDecision, RouterNavContext, RouterSyncGuard,
+    RouterAsyncDecision, RouterAsyncGuard, RouterAsyncTimeout, RouterAuthFailure, RouterAuthGuard,
+    RouterBeforeLeaveAsync, RouterBeforeLeaveDecision, RouterBeforeLeaveSync, RouterGuardDecision,
+    RouterGuardScope, RouterNavContext, RouterRedirect, RouterRedirectTarget,
+    RouterScopedBeforeLeave, RouterScopedGuard, RouterSyncGuard,
 };
-use makepad_widgets::Cx;
+use crate::pattern::RouteParams;
+use crate::route::RouteQuery;
+use crate::url::{self, RouterUrl};
+use makepad_widgets::{Cx, LiveId};
+use std::sync::Arc;
 
 use super::{RouterBlockReason, RouterWidget};
 
@@ -33,9 +39,37 @@ impl RouterWidget {
             return Err(RouterBlockReason::CapabilityDisabled);
         }
         self.guards.route_guards_async.push(Box::new(guard));
+        self.guards.route_guard_timeouts.push(None);
         Ok(())
     }
 
+    /// Add an async guard that resolves to `timeout.decision` if it does not reply in time.
+    pub fn add_route_guard_async_with_timeout<F>(
+        &mut self,
+        timeout: RouterAsyncTimeout<RouterGuardDecision>,
+        guard: F,
+    ) -> Result<(), RouterBlockReason>
+    where
+        F: Fn(&mut Cx, &RouterNavContext) -> RouterAsyncDecision<RouterGuardDecision>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.add_route_guard_async(guard)?;
+        if let Some(slot) = self.guards.route_guard_timeouts.last_mut() {
+            *slot = Some(timeout);
+        }
+        Ok(())
+    }
+
+    /// Timeout for async guards added without one; `None` falls back to `async_guard_timeout`.
+    pub fn set_async_guard_timeout(
+        &mut self,
+        timeout: Option<RouterAsyncTimeout<RouterGuardDecision>>,
+    ) {
+        self.guards.guard_timeout = timeout;
+    }
+
     pub fn add_before_leave_hook<F>(&mut self, hook: F) -> Result<(), RouterBlockReason>
     where
         F: Fn(&mut Cx, &RouterNavContext) -> RouterBeforeLeaveDecision + Send + Sync + 'static,
@@ -60,9 +94,283 @@ impl RouterWidget {
             return Err(RouterBlockReason::CapabilityDisabled);
         }
         self.guards.before_leave_hooks_async.push(Box::new(hook));
+        self.guards.before_leave_timeouts.push(None);
+        Ok(())
+    }
+
+    /// Add an async before-leave hook that resolves to `timeout.decision` if it does not reply
+    /// in time.
+    pub fn add_before_leave_hook_async_with_timeout<F>(
+        &mut self,
+        timeout: RouterAsyncTimeout<RouterBeforeLeaveDecision>,
+        hook: F,
+    ) -> Result<(), RouterBlockReason>
+    where
+        F: Fn(&mut Cx, &RouterNavContext) -> RouterAsyncDecision<RouterBeforeLeaveDecision>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.add_before_leave_hook_async(hook)?;
+        if let Some(slot) = self.guards.before_leave_timeouts.last_mut() {
+            *slot = Some(timeout);
+        }
         Ok(())
     }
 
+    /// Timeout for async before-leave hooks added without one; `None` falls back to
+    /// `async_guard_timeout`.
+    pub fn set_async_before_leave_timeout(
+        &mut self,
+        timeout: Option<RouterAsyncTimeout<RouterBeforeLeaveDecision>>,
+    ) {
+        self.guards.before_leave_timeout = timeout;
+    }
+
+    /// Timeout for the async guard at `index`: its own, the global one, or `async_guard_timeout`.
+    pub(super) fn route_guard_timeout(
+        &self,
+        index: usize,
+    ) -> Option<RouterAsyncTimeout<RouterGuardDecision>> {
+        hook_timeout(
+            self.guards
+                .route_guard_timeouts
+                .get(index)
+                .cloned()
+                .flatten(),
+            self.guards.guard_timeout.clone(),
+            self.async_guard_timeout,
+            RouterGuardDecision::Block,
+        )
+    }
+
+    /// Timeout for the async before-leave hook at `index` (see `route_guard_timeout`).
+    pub(super) fn before_leave_timeout(
+        &self,
+        index: usize,
+    ) -> Option<RouterAsyncTimeout<RouterBeforeLeaveDecision>> {
+        hook_timeout(
+            self.guards
+                .before_leave_timeouts
+                .get(index)
+                .cloned()
+                .flatten(),
+            self.guards.before_leave_timeout.clone(),
+            self.async_guard_timeout,
+            RouterBeforeLeaveDecision::Block,
+        )
+    }
+
+    /// Add a guard that only runs for navigations into `scope` (a route id or a path prefix).
+    ///
+    /// Guards run in order: global, then prefixes (shortest first), then the target route.
+    /// A nested router also runs the guards of the route hosting it, after its global guards.
+    pub fn add_route_guard_for<F>(
+        &mut self,
+        scope: impl Into<RouterGuardScope>,
+        guard: F,
+    ) -> Result<(), RouterBlockReason>
+    where
+        F: Fn(&mut Cx, &RouterNavContext) -> RouterGuardDecision + Send + Sync + 'static,
+    {
+        if !self.guards_sync_enabled() {
+            self.last_blocked_reason = Some(RouterBlockReason::CapabilityDisabled);
+            return Err(RouterBlockReason::CapabilityDisabled);
+        }
+        self.guards
+            .scoped_guards
+            .push((scope.into(), Arc::new(guard)));
+        self.sync_child_guards();
+        Ok(())
+    }
+
+    /// Add a before-leave hook that runs when navigation leaves `scope`.
+    ///
+    /// Hooks run in order: global, then prefixes left by the navigation (shortest first), then
+    /// the route being left.
+    pub fn add_before_leave_hook_for<F>(
+        &mut self,
+        scope: impl Into<RouterGuardScope>,
+        hook: F,
+    ) -> Result<(), RouterBlockReason>
+    where
+        F: Fn(&mut Cx, &RouterNavContext) -> RouterBeforeLeaveDecision + Send + Sync + 'static,
+    {
+        if !self.guards_sync_enabled() {
+            self.last_blocked_reason = Some(RouterBlockReason::CapabilityDisabled);
+            return Err(RouterBlockReason::CapabilityDisabled);
+        }
+        self.guards
+            .scoped_before_leave_hooks
+            .push((scope.into(), Arc::new(hook)));
+        Ok(())
+    }
+
+    /// Hand each nested router the guards of the route hosting it.
+    pub(super) fn sync_child_guards(&self) {
+        for (route_id, child_router) in self.child_routers.iter() {
+            let mut inherited = self.guards.inherited_guards.clone();
+            inherited.extend(
+                self.guards
+                    .scoped_guards
+                    .iter()
+                    .filter(|(scope, _)| *scope == RouterGuardScope::Route(*route_id))
+                    .map(|(_, guard)| guard.clone()),
+            );
+            if let Some(mut child) = child_router.borrow_mut() {
+                child.guards.inherited_guards = inherited;
+                child.sync_child_guards();
+            }
+        }
+    }
+
+    pub(super) fn has_scoped_guards(&self) -> bool {
+        self.guards_sync_enabled()
+            && !(self.guards.scoped_guards.is_empty()
+                && self.guards.scoped_before_leave_hooks.is_empty()
+                && self.guards.inherited_guards.is_empty())
+    }
+
+    /// Inherited, prefix and route guards that apply to `context`, in evaluation order.
+    pub(super) fn scoped_guards_for(&self, context: &RouterNavContext) -> Vec<RouterScopedGuard> {
+        if !self.guards_sync_enabled() {
+            return Vec::new();
+        }
+        let Some(to) = context.to.as_ref() else {
+            return Vec::new();
+        };
+        let path = self.nav_target_path(context);
+        scoped_guards_in_order(
+            &self.guards.scoped_guards,
+            &self.guards.inherited_guards,
+            to.id,
+            &path,
+        )
+    }
+
+    /// Before-leave hooks for the prefixes and route the navigation in `context` leaves.
+    pub(super) fn scoped_before_leave_hooks_for(
+        &self,
+        context: &RouterNavContext,
+    ) -> Vec<RouterScopedBeforeLeave> {
+        if !self.guards_sync_enabled() || self.guards.scoped_before_leave_hooks.is_empty() {
+            return Vec::new();
+        }
+        let Some(from) = context.from.as_ref() else {
+            return Vec::new();
+        };
+        let from_path = self.current_path_for_route(from);
+        let to_path = context.to.as_ref().map(|_| self.nav_target_path(context));
+        let mut prefixed: Vec<_> = self
+            .guards
+            .scoped_before_leave_hooks
+            .iter()
+            .filter_map(|(scope, hook)| match scope {
+                RouterGuardScope::Prefix(prefix)
+                    if scope.covers_path(&from_path)
+                        && !to_path.as_deref().is_some_and(|to| scope.covers_path(to)) =>
+                {
+                    Some((prefix.len(), hook))
+                }
+                _ => None,
+            })
+            .collect();
+        prefixed.sort_by_key(|(len, _)| *len);
+
+        let mut out: Vec<_> = prefixed.into_iter().map(|(_, hook)| hook.clone()).collect();
+        out.extend(
+            self.guards
+                .scoped_before_leave_hooks
+                .iter()
+                .filter(|(scope, _)| *scope == RouterGuardScope::Route(from.id))
+                .map(|(_, hook)| hook.clone()),
+        );
+        out
+    }
+
+    /// Path the navigation in `context` is heading to (without query and hash).
+    fn nav_target_path(&self, context: &RouterNavContext) -> String {
+        match (&context.to_path, &context.to) {
+            (Some(path), _) => path.clone(),
+            (None, Some(to)) => self.current_path_for_route(to),
+            (None, None) => "/".to_string(),
+        }
+    }
+
+    /// Install the built-in metadata-driven auth guard, replacing a previous one.
+    pub fn set_auth_guard(&mut self, guard: RouterAuthGuard) -> Result<(), RouterBlockReason> {
+        if !self.guards_sync_enabled() {
+            self.last_blocked_reason = Some(RouterBlockReason::CapabilityDisabled);
+            return Err(RouterBlockReason::CapabilityDisabled);
+        }
+        self.guards.auth_guard = Some(guard);
+        Ok(())
+    }
+
+    pub fn clear_auth_guard(&mut self) {
+        self.guards.auth_guard = None;
+    }
+
+    pub(super) fn auth_guard(&self) -> Option<&RouterAuthGuard> {
+        if !self.guards_sync_enabled() {
+            return None;
+        }
+        self.guards.auth_guard.as_ref()
+    }
+
+    /// Decision of the auth guard for `context` (`Allow` if none is installed).
+    pub(super) fn evaluate_auth_guard(&self, context: &RouterNavContext) -> RouterGuardDecision {
+        let Some(guard) = self.auth_guard() else {
+            return RouterGuardDecision::Allow;
+        };
+        match guard.check(&context.to_meta) {
+            Ok(()) => RouterGuardDecision::Allow,
+            Err(RouterAuthFailure::Unauthenticated) => {
+                let mut login = self.redirect_target_url(&guard.login);
+                if let Some(return_url) = self.nav_target_url(context) {
+                    login = Self::append_query_param(&login, &guard.return_param, &return_url);
+                }
+                RouterGuardDecision::Redirect(RouterRedirect {
+                    target: RouterRedirectTarget::Path(login),
+                    replace: guard.replace,
+                })
+            }
+            Err(RouterAuthFailure::Forbidden) => match &guard.forbidden {
+                Some(target) => RouterGuardDecision::Redirect(RouterRedirect {
+                    target: target.clone(),
+                    replace: guard.replace,
+                }),
+                None => RouterGuardDecision::Block,
+            },
+        }
+    }
+
+    /// URL of a redirect target (route targets are reverse-routed without params).
+    fn redirect_target_url(&self, target: &RouterRedirectTarget) -> String {
+        match target {
+            RouterRedirectTarget::Path(path) => path.clone(),
+            RouterRedirectTarget::Route(route_id) => self
+                .url_for(*route_id, &RouteParams::new(), &RouteQuery::new(), "")
+                .unwrap_or_else(|_| format!("/{}", route_id)),
+        }
+    }
+
+    /// Full URL (path, query and hash) the navigation in `context` is heading to.
+    fn nav_target_url(&self, context: &RouterNavContext) -> Option<String> {
+        let to = context.to.as_ref()?;
+        match &context.to_path {
+            Some(path) => Some(url::build_url(path, &to.query.data, &to.hash)),
+            None => self.url_for(to.id, &to.params, &to.query, &to.hash).ok(),
+        }
+    }
+
+    fn append_query_param(target: &str, key: &str, value: &str) -> String {
+        let parsed = RouterUrl::parse(target);
+        let mut query = RouteQuery::from_query_string(&parsed.query);
+        query.set(key, value);
+        url::build_url(&parsed.path, &query.data, &parsed.hash)
+    }
+
     pub(super) fn has_async_before_leave_hooks(&self) -> bool {
         if !self.guards_async_enabled() {
             return false;
@@ -105,3 +413,84 @@ impl RouterWidget {
         &self.guards.route_guards_async
     }
 }
+
+/// A hook's own timeout, else the global one, else `fallback` after `default_seconds` (`0.0`
+/// waits forever).
+fn hook_timeout<T>(
+    own: Option<RouterAsyncTimeout<T>>,
+    global: Option<RouterAsyncTimeout<T>>,
+    default_seconds: f64,
+    fallback: T,
+) -> Option<RouterAsyncTimeout<T>> {
+    own.or(global).or_else(|| {
+        (default_seconds > 0.0).then(|| RouterAsyncTimeout::new(default_seconds, fallback))
+    })
+}
+
+/// The `inherited` guards, then the `scoped` ones covering a navigation into `route_id` at
+/// `path`: prefixes (shortest first), then the route's own.
+pub(super) fn scoped_guards_in_order<T: Clone>(
+    scoped: &[(RouterGuardScope, T)],
+    inherited: &[T],
+    route_id: LiveId,
+    path: &str,
+) -> Vec<T> {
+    let mut prefixed: Vec<_> = scoped
+        .iter()
+        .filter_map(|(scope, guard)| match scope {
+            RouterGuardScope::Prefix(prefix) if scope.covers_path(path) => {
+                Some((prefix.len(), guard))
+            }
+            _ => None,
+        })
+        .collect();
+    prefixed.sort_by_key(|(len, _)| *len);
+
+    let mut out = inherited.to_vec();
+    out.extend(prefixed.into_iter().map(|(_, guard)| guard.clone()));
+    out.extend(
+        scoped
+            .iter()
+            .filter(|(scope, _)| *scope == RouterGuardScope::Route(route_id))
+            .map(|(_, guard)| guard.clone()),
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hook_timeout_defaults_to_block_after_async_guard_timeout() {
+        let timeout = hook_timeout(None, None, 2.0, RouterGuardDecision::Block).unwrap();
+        assert_eq!(timeout.seconds, 2.0);
+        assert!(matches!(timeout.decision, RouterGuardDecision::Block));
+        assert!(hook_timeout(None, None, 0.0, RouterGuardDecision::Block).is_none());
+    }
+
+    #[test]
+    fn hook_timeout_prefers_the_hook_then_the_global_timeout() {
+        let allow = RouterAsyncTimeout::new(1.0, RouterBeforeLeaveDecision::Allow);
+        let block = RouterAsyncTimeout::new(5.0, RouterBeforeLeaveDecision::Block);
+
+        let own = hook_timeout(
+            Some(allow),
+            Some(block.clone()),
+            9.0,
+            RouterBeforeLeaveDecision::Block,
+        )
+        .unwrap();
+        assert_eq!(
+            (own.seconds, own.decision),
+            (1.0, RouterBeforeLeaveDecision::Allow)
+        );
+
+        let global =
+            hook_timeout(None, Some(block), 9.0, RouterBeforeLeaveDecision::Allow).unwrap();
+        assert_eq!(
+            (global.seconds, global.decision),
+            (5.0, RouterBeforeLeaveDecision::Block)
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! Keep-alive policies deciding when instantiated route widgets are dropped.
+
+use makepad_widgets::*;
+use std::collections::HashSet;
+
+use super::RouterWidget;
+
+/// When an instantiated route widget may be dropped (it is recreated from its template on the
+/// next visit).
+///
+/// The active route, routes in a running transition, the page beneath an overlay and the current
+/// route of every history stack are never dropped. A preloaded route not yet shown is kept by
+/// `InHistory` until its preload expires and counts toward the `Lru` cap.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RouterKeepAlive {
+    /// Keep the widget for the router's lifetime.
+    #[default]
+    Always,
+    /// Drop the widget once its route is in no history stack.
+    InHistory,
+    /// Drop the least recently shown widgets once more than `max_route_widgets` use this policy.
+    Lru,
+}
+
+impl RouterKeepAlive {
+    /// Parse `@always`, `@in_history` or `@lru`.
+    pub fn from_live_id(id: LiveId) -> Option<Self> {
+        match id {
+            x if x == live_id!(always) => Some(RouterKeepAlive::Always),
+            x if x == live_id!(in_history) => Some(RouterKeepAlive::InHistory),
+            x if x == live_id!(lru) => Some(RouterKeepAlive::Lru),
+            _ => None,
+        }
+    }
+
+    pub(super) fn live_id(self) -> LiveId {
+        match self {
+            RouterKeepAlive::Always => live_id!(always),
+            RouterKeepAlive::InHistory => live_id!(in_history),
+            RouterKeepAlive::Lru => live_id!(lru),
+        }
+    }
+}
+
+impl RouterWidget {
+    /// Policy for routes without their own `route_keep_alive` (overrides `keep_alive` until the
+    /// next reload).
+    pub fn set_keep_alive(&mut self, cx: &mut Cx, policy: RouterKeepAlive) {
+        self.keep_alive = policy.live_id();
+        self.evict_route_widgets(cx);
+    }
+
+    pub fn set_route_keep_alive(&mut self, cx: &mut Cx, route_id: LiveId, policy: RouterKeepAlive) {
+        self.routes.keep_alive_overrides.insert(route_id, policy);
+        self.evict_route_widgets(cx);
+    }
+
+    /// Cap the number of widgets kept under the `Lru` policy (`0` = unbounded).
+    pub fn set_max_route_widgets(&mut self, cx: &mut Cx, max: usize) {
+        self.max_route_widgets = max;
+        self.evict_route_widgets(cx);
+    }
+
+    pub fn route_keep_alive(&self, route_id: LiveId) -> RouterKeepAlive {
+        self.routes
+            .keep_alive_overrides
+            .get(&route_id)
+            .copied()
+            .or_else(|| RouterKeepAlive::from_live_id(self.keep_alive))
+            .unwrap_or_default()
+    }
+
+    /// Ids of the route widgets currently instantiated.
+    pub fn live_route_widgets(&self) -> Vec<LiveId> {
+        self.routes.widgets.keys().copied().collect()
+    }
+
+    /// Drop a route's widget now, regardless of policy (`false` if it is protected or absent).
+    pub fn evict_route_widget(&mut self, cx: &mut Cx, route_id: LiveId) -> bool {
+        if !self.routes.widgets.contains_key(&route_id)
+            || self.protected_route_widgets().contains(&route_id)
+        {
+            return false;
+        }
+        self.dispose_route_widget(cx, route_id);
+        true
+    }
+
+    /// Mark `route_id` as the most recently shown route (this ends a preload's protection).
+    pub(super) fn touch_route_widget(&mut self, route_id: LiveId) {
+        self.routes.preloaded_widgets.remove(&route_id);
+        self.routes.lru.retain(|id| *id != route_id);
+        self.routes.lru.push(route_id);
+    }
+
+    /// Routes whose widgets must stay alive whatever their policy.
+    fn protected_route_widgets(&self) -> HashSet<LiveId> {
+        let mut protected = HashSet::new();
+        protected.insert(self.active_route);
+        if let Some(state) = &self.transition_rt.state {
+            protected.insert(state.from_route);
+            protected.insert(state.to_route);
+        }
+        protected.extend(self.overlay_base_route());
+        for stack_id in self.router.stack_ids() {
+            let current = self
+                .router
+                .stack_history(stack_id)
+                .and_then(|history| history.current());
+            protected.extend(current.map(|route| route.id));
+        }
+        protected
+    }
+
+    /// Drop widgets their keep-alive policy no longer covers.
+    pub(super) fn evict_route_widgets(&mut self, cx: &mut Cx) {
+        let protected = self.protected_route_widgets();
+        let mut in_history = HashSet::new();
+        for stack_id in self.router.stack_ids() {
+            if let Some(history) = self.router.stack_history(stack_id) {
+                in_history.extend(history.all_routes().iter().map(|route| route.id));
+            }
+        }
+
+        let live = self.live_route_widgets();
+        let evict = select_evictions(
+            &self.routes.lru,
+            &live,
+            &protected,
+            &in_history,
+            &self.routes.preloaded_widgets,
+            self.max_route_widgets,
+            |route_id| self.route_keep_alive(route_id),
+        );
+        for route_id in evict {
+            self.dispose_route_widget(cx, route_id);
+        }
+    }
+
+    /// Run dispose callbacks, then drop the widget and any child router found in it.
+    fn dispose_route_widget(&mut self, cx: &mut Cx, route_id: LiveId) {
+        let Some(widget) = self.routes.widgets.get(&route_id).cloned() else {
+            return;
+        };
+        for callback in &self.callbacks.route_dispose {
+            callback(cx, route_id, &widget);
+        }
+        self.routes.widgets.remove(&route_id);
+        self.routes.lru.retain(|id| *id != route_id);
+        self.forget_preload(route_id);
+        self.child_routers.remove(&route_id);
+        self.clear_route_data(route_id);
+        if self.pointer_cleanup.route == Some(route_id) {
+            self.pointer_cleanup.route = None;
+            self.pointer_cleanup.budget = 0;
+        }
+        self.caches.child_router_scan_widget_count = 0;
+    }
+}
+
+/// Widgets to drop, given `lru` (shown order, oldest first), the `live` widgets and the routes
+/// that are `protected`, `in_history` or `preloaded` and not shown yet. `max_lru` caps the live
+/// widgets under the `Lru` policy (`0` = unbounded); protected ones count toward the cap but are
+/// never dropped.
+fn select_evictions(
+    lru: &[LiveId],
+    live: &[LiveId],
+    protected: &HashSet<LiveId>,
+    in_history: &HashSet<LiveId>,
+    preloaded: &HashSet<LiveId>,
+    max_lru: usize,
+    policy: impl Fn(LiveId) -> RouterKeepAlive,
+) -> Vec<LiveId> {
+    let mut evict = Vec::new();
+    let mut lru_candidates = Vec::new();
+    for &route_id in lru {
+        if !live.contains(&route_id) || protected.contains(&route_id) {
+            continue;
+        }
+        match policy(route_id) {
+            RouterKeepAlive::Always => {}
+            RouterKeepAlive::InHistory
+                if !in_history.contains(&route_id) && !preloaded.contains(&route_id) =>
+            {
+                evict.push(route_id);
+            }
+            RouterKeepAlive::InHistory => {}
+            RouterKeepAlive::Lru => lru_candidates.push(route_id),
+        }
+    }
+    if max_lru > 0 {
+        let lru_count = live
+            .iter()
+            .filter(|id| policy(**id) == RouterKeepAlive::Lru)
+            .count();
+        // `lru_candidates` is oldest first and excludes protected routes.
+        let excess = lru_count.saturating_sub(max_lru);
+        evict.extend(lru_candidates.into_iter().take(excess));
+    }
+    evict
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use makepad_live_id::live_id;
+
+    #[test]
+    fn keep_alive_from_live_id_parses_known_values() {
+        assert_eq!(
+            RouterKeepAlive::from_live_id(live_id!(in_history)),
+            Some(RouterKeepAlive::InHistory)
+        );
+        assert_eq!(
+            RouterKeepAlive::from_live_id(live_id!(lru)),
+            Some(RouterKeepAlive::Lru)
+        );
+        assert_eq!(RouterKeepAlive::from_live_id(live_id!(nope)), None);
+    }
+
+    #[test]
+    fn evictions_follow_each_route_policy() {
+        let (a, b, c, d) = (live_id!(a), live_id!(b), live_id!(c), live_id!(d));
+        let policy = |id: LiveId| match id {
+            x if x == a => RouterKeepAlive::Always,
+            x if x == b => RouterKeepAlive::InHistory,
+            _ => RouterKeepAlive::Lru,
+        };
+        let live = [a, b, c, d];
+        let lru = [a, b, c, d];
+        let none = HashSet::new();
+
+        // Out of history and over the cap of one `Lru` widget: `b` and the older of `c`/`d`.
+        let evict = select_evictions(&lru, &live, &none, &none, &none, 1, policy);
+        assert_eq!(evict, vec![b, c]);
+
+        // Unbounded `Lru` and `b` still in history: nothing to drop.
+        let in_history: HashSet<_> = [b].into_iter().collect();
+        assert!(select_evictions(&lru, &live, &none, &in_history, &none, 0, policy).is_empty());
+
+        // Protected widgets count toward the cap but are skipped.
+        let protected: HashSet<_> = [b, c].into_iter().collect();
+        let evict = select_evictions(&lru, &live, &protected, &none, &none, 1, policy);
+        assert_eq!(evict, vec![d]);
+
+        // Widgets no longer live are ignored.
+        let evict = select_evictions(&lru, &[a, d], &none, &none, &none, 0, policy);
+        assert!(evict.is_empty());
+    }
+
+    #[test]
+    fn preloaded_widgets_are_kept_out_of_history_but_not_over_the_lru_cap() {
+        let (a, b, c) = (live_id!(a), live_id!(b), live_id!(c));
+        let policy = |id: LiveId| match id {
+            x if x == a => RouterKeepAlive::InHistory,
+            _ => RouterKeepAlive::Lru,
+        };
+        let live = [a, b, c];
+        let none = HashSet::new();
+        let preloaded: HashSet<_> = [a, c].into_iter().collect();
+
+        // `a` is not in history but preloaded; `c` is preloaded but the oldest `Lru` widget.
+        let lru = [c, a, b];
+        let evict = select_evictions(&lru, &live, &none, &none, &preloaded, 1, policy);
+        assert_eq!(evict, vec![c]);
+
+        // Once the preload is gone `a` follows its policy again.
+        let evict = select_evictions(&lru, &live, &none, &none, &none, 0, policy);
+        assert_eq!(evict, vec![a]);
+    }
+}
@@ -1,5 +1,7 @@
+use crate::navigation::{HistoryEviction, HistoryLimit};
 use crate::registry::RouteRegistry;
 use crate::route::Route;
+use crate::url::{DuplicateSlashes, MatchPolicy, TrailingSlash};
 use makepad_widgets::*;
 
 use super::route_defs::route_definition_from_template;
@@ -25,8 +27,13 @@ impl ScriptHook for RouterWidget {
             self.routes.patterns.clear();
             self.routes.transition_overrides.clear();
             self.routes.transition_duration_overrides.clear();
+            self.routes.overlays.clear();
+            self.routes.keep_alive_overrides.clear();
+            self.routes.preload.clear();
+            self.preload_rt.queue.clear();
             self.child_routers.clear();
             self.router.route_registry = RouteRegistry::default();
+            self.route_diagnostics.clear();
             self.transition_rt.state = None;
         }
     }
@@ -39,6 +46,21 @@ impl ScriptHook for RouterWidget {
         value: ScriptValue,
     ) {
         if !apply.is_eval() {
+            self.router
+                .route_registry
+                .set_strict(self.strict_route_patterns);
+            self.router.route_registry.set_match_policy(match_policy(
+                self.case_insensitive_routes,
+                self.strict_trailing_slash,
+                self.reject_duplicate_slashes,
+            ));
+            let eviction = match self.history_eviction {
+                x if x == live_id!(collapse_duplicates) => HistoryEviction::CollapseDuplicates,
+                x if x == live_id!(keep_root) => HistoryEviction::KeepRoot,
+                _ => HistoryEviction::DropOldest,
+            };
+            self.router
+                .set_history_limit(HistoryLimit::new(self.max_history_depth, eviction));
             if let Some(obj) = value.as_object() {
                 vm.vec_with(obj, |vm, vec| {
                     for kv in vec {
@@ -56,8 +78,21 @@ impl ScriptHook for RouterWidget {
                         self.routes.templates.insert(route_id, template_ref);
 
                         let route_def = route_definition_from_template(vm, template_obj);
+                        self.router.set_route_meta(route_id, route_def.meta.clone());
 
-                        if let Some(pattern) = route_def.pattern {
+                        if let (Some(pattern), Some(target)) =
+                            (route_def.pattern.as_ref(), route_def.redirect.as_ref())
+                        {
+                            // A redirect route only forwards its pattern; it never resolves itself.
+                            if let Err(err) = self.router.register_route_redirect(pattern, target) {
+                                log!(
+                                    "Failed to register route redirect {} -> {}: {}",
+                                    pattern,
+                                    target,
+                                    err
+                                );
+                            }
+                        } else if let Some(pattern) = route_def.pattern {
                             self.routes.patterns.insert(route_id, pattern.clone());
                             if let Err(err) = self.router.register_route_pattern(&pattern, route_id)
                             {
@@ -67,6 +102,14 @@ impl ScriptHook for RouterWidget {
                                     self.caches.route_registry_epoch.wrapping_add(1);
                                 self.caches.child_router_scan_widget_count = 0;
                             }
+                            self.collect_route_diagnostics();
+                        }
+
+                        for alias in &route_def.aliases {
+                            if let Err(err) = self.router.register_route_alias(alias, route_id) {
+                                log!("Failed to register route alias {}: {}", alias, err);
+                            }
+                            self.collect_route_diagnostics();
                         }
 
                         if let Some(transition) = route_def.transition {
@@ -83,6 +126,20 @@ impl ScriptHook for RouterWidget {
                                 .insert(route_id, duration);
                         }
 
+                        if route_def.overlay {
+                            self.routes.overlays.insert(route_id);
+                        }
+
+                        if let Some(preload) = route_def.preload {
+                            self.routes.preload.insert(route_id, preload);
+                        }
+
+                        if let Some(keep_alive) = route_def.keep_alive {
+                            self.routes
+                                .keep_alive_overrides
+                                .insert(route_id, keep_alive);
+                        }
+
                         if let Some(route_widget) = self.routes.widgets.get_mut(&route_id) {
                             route_widget.script_apply(vm, apply, scope, kv.value);
                         }
@@ -113,7 +170,52 @@ impl ScriptHook for RouterWidget {
                 }
 
                 self.detect_child_routers(cx);
+                self.start_route_preloads(cx);
             });
         }
     }
 }
+
+/// Match policy for the `case_insensitive_routes`, `strict_trailing_slash` and
+/// `reject_duplicate_slashes` live properties.
+fn match_policy(
+    case_insensitive: bool,
+    strict_trailing_slash: bool,
+    reject_duplicate_slashes: bool,
+) -> MatchPolicy {
+    MatchPolicy {
+        case_sensitive: !case_insensitive,
+        trailing_slash: if strict_trailing_slash {
+            TrailingSlash::Strict
+        } else {
+            TrailingSlash::Lenient
+        },
+        duplicate_slashes: if reject_duplicate_slashes {
+            DuplicateSlashes::Reject
+        } else {
+            DuplicateSlashes::Collapse
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_policy_follows_the_live_flags() {
+        assert_eq!(match_policy(false, false, false), MatchPolicy::default());
+        assert_eq!(
+            match_policy(true, false, false),
+            MatchPolicy::case_insensitive()
+        );
+        assert_eq!(
+            match_policy(false, true, true),
+            MatchPolicy {
+                trailing_slash: TrailingSlash::Strict,
+                duplicate_slashes: DuplicateSlashes::Reject,
+                ..MatchPolicy::default()
+            }
+        );
+    }
+}
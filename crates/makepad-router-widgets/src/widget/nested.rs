@@ -19,6 +19,7 @@ impl RouterWidget {
             return self.caches.nested_prefix_cache_result.clone();
         }
 
+        let policy = self.router.route_registry.match_policy();
         let mut best: Option<(LiveId, RouteParams, RoutePatternRef, String, usize)> = None;
 
         for route_id in self
@@ -31,7 +32,7 @@ impl RouterWidget {
             let Some(pattern_obj) = self.router.route_registry.get_pattern(route_id) else {
                 continue;
             };
-            let Some((params, tail)) = pattern_obj.matches_prefix_with_tail(path) else {
+            let Some((params, tail)) = pattern_obj.matches_prefix_with_policy(path, &policy) else {
                 continue;
             };
             let priority = pattern_obj.priority();
@@ -84,6 +85,7 @@ impl RouterWidget {
         {
             return;
         }
+        let mut found = false;
         for (route_id, route_widget) in self.routes.widgets.iter() {
             if self.child_routers.contains_key(route_id) {
                 continue;
@@ -91,8 +93,12 @@ impl RouterWidget {
 
             if let Some(child_router) = Self::find_first_child_router(route_widget) {
                 self.child_routers.insert(*route_id, child_router);
+                found = true;
             }
         }
+        if found {
+            self.sync_child_guards();
+        }
         self.caches.child_router_scan_epoch = self.caches.route_registry_epoch;
         self.caches.child_router_scan_widget_count = self.routes.widgets.len();
     }
@@ -134,6 +140,7 @@ impl RouterWidget {
                     super::RouterTransitionDirection::Forward,
                     None,
                 );
+                self.announce_transition(cx);
 
                 self.redraw(cx);
                 return true;
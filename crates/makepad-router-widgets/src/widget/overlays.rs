@@ -0,0 +1,108 @@
+//! Overlay routes (`route_overlay: true`) drawn above the page beneath them.
+
+use crate::navigation::NavigationHistory;
+use makepad_widgets::*;
+
+use super::{RouterBlockReason, RouterWidget};
+
+impl RouterWidget {
+    /// Whether `route_id` was declared with `route_overlay: true`.
+    pub fn is_overlay_route(&self, route_id: LiveId) -> bool {
+        self.routes.overlays.contains(&route_id)
+    }
+
+    /// Navigate to an overlay route; it gets its own history entry, so `back` dismisses it.
+    pub fn present_overlay(&mut self, cx: &mut Cx, route_id: LiveId) -> bool {
+        if !self.is_overlay_route(route_id) {
+            log!("Router: {:?} is not an overlay route", route_id);
+            self.last_blocked_reason = Some(RouterBlockReason::RouteMissing);
+            return false;
+        }
+        self.navigate(cx, route_id)
+    }
+
+    /// Go back from the current route if it is an overlay.
+    pub fn dismiss_overlay(&mut self, cx: &mut Cx) -> bool {
+        match self.router.current_route_id() {
+            Some(route_id) if self.is_overlay_route(route_id) => self.back(cx),
+            _ => false,
+        }
+    }
+
+    /// Page to draw beneath the active route (or a running transition) when an overlay is shown:
+    /// the closest non-overlay entry at or below the current history entry.
+    pub(super) fn overlay_base_route(&self) -> Option<LiveId> {
+        let mut shown = vec![self.active_route];
+        if let Some(state) = &self.transition_rt.state {
+            shown.extend([state.from_route, state.to_route]);
+        }
+        overlay_base(&shown, &self.router.history, |route_id| {
+            self.is_overlay_route(route_id)
+        })
+    }
+}
+
+/// Closest non-overlay entry at or below the current one in `history`, if any of the `shown`
+/// routes is an overlay.
+fn overlay_base(
+    shown: &[LiveId],
+    history: &NavigationHistory,
+    is_overlay: impl Fn(LiveId) -> bool,
+) -> Option<LiveId> {
+    if !shown.iter().any(|route_id| is_overlay(*route_id)) {
+        return None;
+    }
+    history
+        .all_routes()
+        .iter()
+        .take(history.current_index() + 1)
+        .rev()
+        .map(|route| route.id)
+        .find(|route_id| !is_overlay(*route_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::route::Route;
+    use makepad_live_id::live_id;
+
+    fn history(ids: &[LiveId], current: usize) -> NavigationHistory {
+        NavigationHistory::from_parts(ids.iter().map(|id| Route::new(*id)).collect(), current)
+    }
+
+    #[test]
+    fn overlay_base_is_the_closest_page_below_the_current_entry() {
+        let (home, list, sheet, dialog) = (
+            live_id!(home),
+            live_id!(list),
+            live_id!(sheet),
+            live_id!(dialog),
+        );
+        let is_overlay = |id: LiveId| id == sheet || id == dialog;
+        let stacked = history(&[home, list, sheet, dialog], 3);
+
+        assert_eq!(overlay_base(&[dialog], &stacked, is_overlay), Some(list));
+        // No overlay shown: nothing is drawn beneath.
+        assert_eq!(overlay_base(&[list], &stacked, is_overlay), None);
+        // A transition from an overlay back to a page still needs the base.
+        assert_eq!(
+            overlay_base(
+                &[list, sheet, list],
+                &history(&[home, list, sheet], 1),
+                is_overlay
+            ),
+            Some(list)
+        );
+        // Entries above the current one are ignored.
+        assert_eq!(
+            overlay_base(&[sheet], &history(&[home, sheet, list], 1), is_overlay),
+            Some(home)
+        );
+        // Only overlays in history.
+        assert_eq!(
+            overlay_base(&[sheet], &history(&[sheet], 0), is_overlay),
+            None
+        );
+    }
+}
@@ -1,8 +1,11 @@
 use crate::{
+    pattern::RoutePattern,
+    registry::RouteRegistry,
     route::{Route, RouteQuery},
     router::RouterAction,
 };
 use makepad_widgets::*;
+use std::borrow::Cow;
 
 use super::{
     ResolvedPathIntent, ResolvedPathKind, RouterActionKind, RouterTransitionDirection, RouterWidget,
@@ -18,15 +21,24 @@ impl RouterWidget {
         let parsed = self.parse_url_cached(path);
         let query = RouteQuery::from_query_string(&parsed.query);
         let hash = parsed.hash.clone();
-        let normalized_path = parsed.path;
+        let requested_path = parsed.path.clone();
+        // Declarative redirects rewrite the path before matching (query and hash are kept).
+        let Some(normalized_path) = redirected_path(&self.router.route_registry, parsed.path)
+        else {
+            log!("Router: Redirect loop for path: {}", path);
+            return None;
+        };
 
         // 1) Full match in this router.
-        if let Some(mut route) = self.router.route_registry.resolve_path(&normalized_path) {
+        let registry = &self.router.route_registry;
+        if let Some(mut route) = registry.resolve_redirected_path(&normalized_path) {
             if self.routes.templates.contains_key(&route.id) {
                 route.query = query.clone();
                 route.hash = hash.clone();
+                let path = self.canonical_intent_path(route.pattern.as_deref(), normalized_path);
                 return Some(ResolvedPathIntent {
-                    path: normalized_path,
+                    rewritten_from: (path != requested_path).then_some(requested_path),
+                    path,
                     route,
                     kind: ResolvedPathKind::FullMatch,
                     clear_extras,
@@ -39,8 +51,10 @@ impl RouterWidget {
         // 2) Prefix match for nested routing: activate a parent route and delegate the tail.
         if let Some((route_id, params, pattern, tail)) = self.resolve_nested_prefix(&normalized_path) {
             if self.routes.templates.contains_key(&route_id) {
+                let path = self.canonical_intent_path(Some(&*pattern), normalized_path);
                 return Some(ResolvedPathIntent {
-                    path: normalized_path,
+                    rewritten_from: (path != requested_path).then_some(requested_path),
+                    path,
                     route: Route {
                         id: route_id,
                         params,
@@ -65,6 +79,7 @@ impl RouterWidget {
             nf.query = query;
             nf.hash = hash;
             return Some(ResolvedPathIntent {
+                rewritten_from: (normalized_path != requested_path).then_some(requested_path),
                 path: normalized_path,
                 route: nf,
                 kind: ResolvedPathKind::NotFoundFallback,
@@ -76,6 +91,14 @@ impl RouterWidget {
         None
     }
 
+    /// `path` (already redirected) in canonical form when `canonical_redirects` is set.
+    fn canonical_intent_path(&self, pattern: Option<&RoutePattern>, path: String) -> String {
+        if !self.canonical_redirects {
+            return path;
+        }
+        canonical_path(&self.router.route_registry, pattern, path)
+    }
+
     pub(super) fn apply_resolved_path_intent(
         &mut self,
         cx: &mut Cx,
@@ -116,6 +139,12 @@ impl RouterWidget {
         );
 
         self.dispatch_route_change(cx, old_route.clone(), route.clone());
+        if let Some(from) = &intent.rewritten_from {
+            self.pending_actions.push(RouterAction::PathRewritten {
+                from: from.clone(),
+                to: intent.path.clone(),
+            });
+        }
         self.queue_route_actions(
             Some(if intent.replace {
                 RouterAction::Replace(route.clone())
@@ -133,7 +162,10 @@ impl RouterWidget {
             ResolvedPathKind::FullMatch => {
                 if self.child_routers.contains_key(&route.id) {
                     if let Some(pattern) = &route.pattern {
-                        if let Some((_params, tail)) = pattern.matches_prefix_with_tail(&intent.path) {
+                        let policy = self.router.route_registry.match_policy();
+                        if let Some((_params, tail)) =
+                            pattern.matches_prefix_with_policy(&intent.path, &policy)
+                        {
                             let _ = self.delegate_tail_to_child(cx, route.id, &tail);
                         }
                     }
@@ -173,3 +205,84 @@ impl RouterWidget {
         false
     }
 }
+
+/// `path` after the registry's redirects, or `None` if they loop.
+fn redirected_path(registry: &RouteRegistry, path: String) -> Option<String> {
+    match registry.follow_redirects(&path)? {
+        Cow::Owned(redirected) => Some(redirected),
+        Cow::Borrowed(_) => Some(path),
+    }
+}
+
+/// Canonical spelling of a redirected `path` matched by `pattern` (`path` if the match policy
+/// rejects it).
+fn canonical_path(
+    registry: &RouteRegistry,
+    pattern: Option<&RoutePattern>,
+    path: String,
+) -> String {
+    registry.canonical_spelling(pattern, &path).unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::url::MatchPolicy;
+    use makepad_live_id::live_id;
+
+    #[test]
+    fn redirected_path_follows_registry_redirects() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_redirect("/old-settings/**", "/settings/**")
+            .unwrap();
+        registry.register_redirect("/a", "/b").unwrap();
+        registry.register_redirect("/b", "/a").unwrap();
+
+        assert_eq!(
+            redirected_path(&registry, "/old-settings/privacy".to_string()).as_deref(),
+            Some("/settings/privacy")
+        );
+        assert_eq!(
+            redirected_path(&registry, "/home".to_string()).as_deref(),
+            Some("/home")
+        );
+        assert_eq!(redirected_path(&registry, "/a".to_string()), None);
+    }
+
+    #[test]
+    fn canonical_path_uses_the_registered_spelling() {
+        let mut registry = RouteRegistry::new();
+        registry
+            .register_pattern("/Settings/:tab", live_id!(settings))
+            .unwrap();
+        registry.set_match_policy(MatchPolicy::case_insensitive());
+
+        let pattern = registry.get_pattern(live_id!(settings)).cloned();
+        let pattern = pattern.as_deref();
+        assert_eq!(
+            canonical_path(&registry, pattern, "/settings//General/".to_string()),
+            "/Settings/General"
+        );
+        assert_eq!(
+            canonical_path(&registry, pattern, "/Settings/General".to_string()),
+            "/Settings/General"
+        );
+        assert_eq!(
+            canonical_path(&registry, None, "/missing/".to_string()),
+            "/missing"
+        );
+    }
+
+    #[test]
+    fn canonical_path_respells_a_nested_prefix_and_keeps_the_tail() {
+        let mut registry = RouteRegistry::new();
+        registry.set_match_policy(MatchPolicy::case_insensitive());
+        let prefix = RoutePattern::parse("/Admin").unwrap();
+
+        assert_eq!(
+            canonical_path(&registry, Some(&prefix), "/admin//Users/Edit".to_string()),
+            "/Admin/Users/Edit"
+        );
+    }
+}
@@ -1,5 +1,9 @@
-use crate::{route::Route, state::RouterState};
-use makepad_widgets::{Cx, WidgetNode};
+use crate::{
+    navigation::{HistoryStacks, NavigationHistory},
+    route::Route,
+    state::RouterState,
+};
+use makepad_widgets::{Cx, LiveId, WidgetNode};
 
 // Router state persistence helpers.
 
@@ -13,6 +17,7 @@ impl RouterWidget {
         RouterState {
             history: self.router.history.clone(),
             url_path_override: self.url_path_override.clone(),
+            stacks: (!self.router.stacks.is_single()).then(|| self.router.stacks.clone()),
         }
     }
 
@@ -22,27 +27,18 @@ impl RouterWidget {
             return false;
         }
         let old_route = self.router.current_route().cloned();
-        let (stack, current_index) = state.history.into_parts();
-
-        let mut filtered = Vec::<Route>::new();
-        let mut new_current = 0usize;
-        for (idx, route) in stack.into_iter().enumerate() {
-            if !self.routes.templates.contains_key(&route.id) {
-                continue;
-            }
-            if idx <= current_index {
-                new_current = filtered.len();
-            }
-            filtered.push(route);
-        }
-        if filtered.is_empty() {
+        let has_template = |route_id| self.routes.templates.contains_key(&route_id);
+        let Some(history) = filter_restored_history(state.history, has_template) else {
             return false;
-        }
+        };
+        let stacks = filter_restored_stacks(state.stacks.unwrap_or_default(), has_template);
 
         self.clear_url_extras();
         self.url_path_override = state.url_path_override;
-        self.router.history =
-            crate::navigation::NavigationHistory::from_parts(filtered, new_current);
+        let limit = self.router.history_limit();
+        self.router.history = history;
+        self.router.stacks = stacks;
+        self.router.set_history_limit(limit);
         let Some(new_route) = self.router.current_route().cloned() else {
             return false;
         };
@@ -61,3 +57,100 @@ impl RouterWidget {
         true
     }
 }
+
+/// Drop routes `keep` rejects, keeping the closest surviving current entry.
+fn filter_restored_history(
+    history: NavigationHistory,
+    keep: impl Fn(LiveId) -> bool,
+) -> Option<NavigationHistory> {
+    let (stack, current_index) = history.into_parts();
+
+    let mut filtered = Vec::<Route>::new();
+    let mut new_current = 0usize;
+    for (idx, route) in stack.into_iter().enumerate() {
+        if !keep(route.id) {
+            continue;
+        }
+        if idx <= current_index {
+            new_current = filtered.len();
+        }
+        filtered.push(route);
+    }
+    if filtered.is_empty() {
+        return None;
+    }
+    Some(NavigationHistory::from_parts(filtered, new_current))
+}
+
+/// Filter each inactive stack like `filter_restored_history`, dropping stacks left empty.
+fn filter_restored_stacks(
+    mut stacks: HistoryStacks,
+    keep: impl Fn(LiveId) -> bool,
+) -> HistoryStacks {
+    stacks.inactive = std::mem::take(&mut stacks.inactive)
+        .into_iter()
+        .filter_map(|mut stack| {
+            stack.history = filter_restored_history(stack.history, &keep)?;
+            Some(stack)
+        })
+        .collect();
+    stacks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use makepad_live_id::live_id;
+
+    fn history(ids: &[LiveId], current: usize) -> NavigationHistory {
+        NavigationHistory::from_parts(ids.iter().map(|id| Route::new(*id)).collect(), current)
+    }
+
+    fn ids(history: &NavigationHistory) -> Vec<LiveId> {
+        history.all_routes().iter().map(|route| route.id).collect()
+    }
+
+    #[test]
+    fn restored_history_keeps_the_closest_current_entry() {
+        let (home, gone, list, detail) = (
+            live_id!(home),
+            live_id!(gone),
+            live_id!(list),
+            live_id!(detail),
+        );
+        let keep = |id: LiveId| id != gone;
+
+        let restored =
+            filter_restored_history(history(&[home, list, gone, detail], 2), keep).unwrap();
+        assert_eq!(ids(&restored), vec![home, list, detail]);
+        assert_eq!(restored.current_index(), 1);
+
+        let restored = filter_restored_history(history(&[gone, home], 0), keep).unwrap();
+        assert_eq!(ids(&restored), vec![home]);
+        assert_eq!(restored.current_index(), 0);
+
+        assert!(filter_restored_history(history(&[gone], 0), keep).is_none());
+    }
+
+    #[test]
+    fn restored_stacks_drop_missing_routes_and_empty_stacks() {
+        let (gone, search, results) = (live_id!(gone), live_id!(search), live_id!(results));
+        let keep = |id: LiveId| id != gone;
+
+        let mut stacks = HistoryStacks::default();
+        assert!(stacks.insert(live_id!(search_tab), history(&[search, gone, results], 1)));
+        assert!(stacks.insert(live_id!(gone_tab), history(&[gone], 0)));
+        stacks.active = live_id!(home_tab);
+
+        let restored = filter_restored_stacks(stacks, keep);
+        assert_eq!(restored.active, live_id!(home_tab));
+        assert_eq!(
+            restored.ids(),
+            vec![live_id!(home_tab), live_id!(search_tab)]
+        );
+        let search_history = restored.get(live_id!(search_tab)).unwrap();
+        assert_eq!(ids(search_history), vec![search, results]);
+        assert_eq!(search_history.current_index(), 0);
+        assert!(!restored.contains(live_id!(gone_tab)));
+    }
+}
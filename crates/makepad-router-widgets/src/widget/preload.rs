@@ -0,0 +1,327 @@
+//! Instantiating route widgets (and resolving their data) ahead of navigation.
+
+use crate::guards::{
+    RouterAsyncDecision, RouterLoadState, RouterNavContext, RouterNavKind, RouterResolveError,
+    RouterResolveResult, RouterRouteData,
+};
+use crate::route::Route;
+use makepad_widgets::*;
+
+use super::guard_flow::route_meta_or_empty;
+use super::{ResolvedPathIntent, ResolvedPathKind, RouterWidget};
+
+/// When a route declared with `route_preload` is instantiated before it is visited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RouterPreload {
+    /// Only on first visit or an explicit `preload` (default).
+    #[default]
+    None,
+    /// As soon as the router's routes are applied.
+    Eager,
+    /// One route per frame while no transition or navigation is running.
+    OnIdle,
+    /// When the app reports a hover over a link to the route (`hover_preload`).
+    OnHover,
+}
+
+impl RouterPreload {
+    /// Parse `@eager`, `@on_idle` or `@on_hover`.
+    pub fn from_live_id(id: LiveId) -> Option<Self> {
+        match id {
+            x if x == live_id!(none) => Some(RouterPreload::None),
+            x if x == live_id!(eager) => Some(RouterPreload::Eager),
+            x if x == live_id!(on_idle) => Some(RouterPreload::OnIdle),
+            x if x == live_id!(on_hover) => Some(RouterPreload::OnHover),
+            _ => None,
+        }
+    }
+}
+
+impl RouterWidget {
+    /// Instantiate `route_id`'s widget and start its resolver without navigating.
+    ///
+    /// Until the route is shown or the preload expires after `preload_ttl` seconds, an
+    /// `in_history` policy keeps the widget; `lru` counts it like any other widget.
+    pub fn preload(&mut self, cx: &mut Cx, route_id: LiveId) -> bool {
+        self.preload_route(cx, Route::new(route_id), None)
+    }
+
+    /// Preload the route `path` resolves to, including nested routers' routes for the tail.
+    pub fn preload_path(&mut self, cx: &mut Cx, path: &str) -> bool {
+        let Some(intent) = self.resolve_path_intent(path, false, false) else {
+            return false;
+        };
+        self.preload_intent(cx, intent)
+    }
+
+    fn preload_intent(&mut self, cx: &mut Cx, intent: ResolvedPathIntent) -> bool {
+        match intent.kind {
+            ResolvedPathKind::FullMatch => self.preload_route(cx, intent.route, Some(intent.path)),
+            ResolvedPathKind::NestedPrefix { tail } => {
+                let route_id = intent.route.id;
+                if !self.preload_route(cx, intent.route, Some(intent.path)) {
+                    return false;
+                }
+                if tail.is_empty() {
+                    return true;
+                }
+                self.detect_child_routers(cx);
+                match self.child_routers.get(&route_id).cloned() {
+                    Some(child) => child.preload_path(cx, &tail),
+                    None => true,
+                }
+            }
+            ResolvedPathKind::NotFoundFallback => false,
+        }
+    }
+
+    /// Preload `route_id` if its `route_preload` asks for it; call when the pointer enters a link.
+    pub fn hover_preload(&mut self, cx: &mut Cx, route_id: LiveId) -> bool {
+        if self.route_preload(route_id) == RouterPreload::None {
+            return false;
+        }
+        self.preload(cx, route_id)
+    }
+
+    /// Path form of `hover_preload`.
+    pub fn hover_preload_path(&mut self, cx: &mut Cx, path: &str) -> bool {
+        let Some(intent) = self.resolve_path_intent(path, false, false) else {
+            return false;
+        };
+        if self.route_preload(intent.route.id) == RouterPreload::None {
+            return false;
+        }
+        self.preload_intent(cx, intent)
+    }
+
+    pub fn route_preload(&self, route_id: LiveId) -> RouterPreload {
+        self.routes
+            .preload
+            .get(&route_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn set_route_preload(&mut self, cx: &mut Cx, route_id: LiveId, preload: RouterPreload) {
+        self.routes.preload.insert(route_id, preload);
+        match preload {
+            RouterPreload::Eager => {
+                self.preload(cx, route_id);
+            }
+            RouterPreload::OnIdle => self.schedule_idle_preload(cx, route_id),
+            RouterPreload::None | RouterPreload::OnHover => {}
+        }
+    }
+
+    fn preload_route(&mut self, cx: &mut Cx, route: Route, to_path: Option<String>) -> bool {
+        if !self.routes.templates.contains_key(&route.id) {
+            log!("Router: Route template not found for {:?}", route.id);
+            return false;
+        }
+        self.ensure_route_widget(cx, route.id);
+        if route.id != self.active_route {
+            self.routes.preloaded_widgets.insert(route.id);
+        }
+        if self.preload_ttl > 0.0 {
+            let timer = cx.start_timeout(self.preload_ttl);
+            self.preload_rt.expiry.insert(route.id, timer);
+        }
+        self.preload_route_data(cx, route, to_path);
+        self.evict_route_widgets(cx);
+        true
+    }
+
+    /// Drop preloads whose `preload_ttl` ran out, then evict what their keep-alive policy no
+    /// longer covers.
+    pub(super) fn expire_preloads(&mut self, cx: &mut Cx, event: &Event) {
+        let expired: Vec<LiveId> = self
+            .preload_rt
+            .expiry
+            .iter()
+            .filter(|(_, timer)| timer.is_event(event).is_some())
+            .map(|(route_id, _)| *route_id)
+            .collect();
+        if expired.is_empty() {
+            return;
+        }
+        for route_id in expired {
+            self.forget_preload(route_id);
+        }
+        self.evict_route_widgets(cx);
+    }
+
+    /// Forget everything preloaded for `route_id`: its widget's preload mark, its preloaded and
+    /// in-flight data and its expiry timer.
+    pub(super) fn forget_preload(&mut self, route_id: LiveId) {
+        self.preload_rt.expiry.remove(&route_id);
+        self.routes.preloaded_widgets.remove(&route_id);
+        self.resolvers
+            .preloaded
+            .retain(|(route, _)| route.id != route_id);
+        self.resolvers
+            .preloading
+            .retain(|(route, _)| route.id != route_id);
+    }
+
+    /// Run the route's resolver; the result is kept apart from the route's current data until a
+    /// navigation to the same route uses it.
+    fn preload_route_data(&mut self, cx: &mut Cx, route: Route, to_path: Option<String>) {
+        if self.resolvers.preloading.iter().any(|(r, _)| *r == route)
+            || self.resolvers.preloaded.iter().any(|(r, _)| *r == route)
+        {
+            return;
+        }
+        let Some(resolver) = self.resolvers.resolvers.get(&route.id) else {
+            return;
+        };
+        let from = self.router.current_route().cloned();
+        let context = RouterNavContext {
+            kind: RouterNavKind::Preload,
+            from_meta: route_meta_or_empty(&self.router, from.as_ref()),
+            to_meta: route_meta_or_empty(&self.router, Some(&route)),
+            from,
+            to: Some(route.clone()),
+            to_path,
+        };
+        match resolver(cx, &context) {
+            RouterAsyncDecision::Immediate(result) => self.finish_preload(route, result),
+            RouterAsyncDecision::Pending(rx) => self.resolvers.preloading.push((route, rx)),
+        }
+    }
+
+    fn finish_preload(&mut self, route: Route, result: RouterResolveResult) {
+        match result {
+            Ok(data) => self.resolvers.preloaded.push((route, data)),
+            // The navigation to the route runs its resolver again and reports the failure.
+            Err(error) => log!(
+                "Router: preload resolver for {:?} failed: {}",
+                route.id,
+                error
+            ),
+        }
+    }
+
+    pub(super) fn poll_preloads(&mut self) {
+        if self.resolvers.preloading.is_empty() {
+            return;
+        }
+        for (route, rx) in std::mem::take(&mut self.resolvers.preloading) {
+            let result = match rx.try_recv_flush() {
+                Ok(result) => result,
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    self.resolvers.preloading.push((route, rx));
+                    continue;
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    Err(RouterResolveError::Dropped)
+                }
+            };
+            self.finish_preload(route, result);
+        }
+    }
+
+    /// Move data preloaded for exactly `route` into the route's data, so a navigation to it can
+    /// skip its resolver.
+    ///
+    /// A preload still in flight for the route is dropped; the navigation resolves on its own.
+    pub(super) fn take_preloaded_data(&mut self, route: &Route) -> bool {
+        self.resolvers
+            .preloading
+            .retain(|(preloading, _)| preloading != route);
+        let Some(data) = take_preloaded(&mut self.resolvers.preloaded, route) else {
+            return false;
+        };
+        self.resolvers.data.insert(route.id, (route.clone(), data));
+        self.resolvers
+            .states
+            .insert(route.id, RouterLoadState::Loaded);
+        true
+    }
+
+    /// Apply `route_preload` after routes are (re)loaded.
+    pub(super) fn start_route_preloads(&mut self, cx: &mut Cx) {
+        let routes: Vec<(LiveId, RouterPreload)> = self
+            .routes
+            .preload
+            .iter()
+            .map(|(route_id, preload)| (*route_id, *preload))
+            .collect();
+        for (route_id, preload) in routes {
+            match preload {
+                RouterPreload::Eager => {
+                    self.preload(cx, route_id);
+                }
+                RouterPreload::OnIdle => self.schedule_idle_preload(cx, route_id),
+                RouterPreload::None | RouterPreload::OnHover => {}
+            }
+        }
+    }
+
+    fn schedule_idle_preload(&mut self, cx: &mut Cx, route_id: LiveId) {
+        if self.routes.widgets.contains_key(&route_id) || self.preload_rt.queue.contains(&route_id)
+        {
+            return;
+        }
+        self.preload_rt.queue.push(route_id);
+        self.preload_rt.next_frame = cx.new_next_frame();
+    }
+
+    /// Preload one queued `on_idle` route, waiting while a transition or navigation runs.
+    pub(super) fn preload_idle_step(&mut self, cx: &mut Cx) {
+        if self.preload_rt.queue.is_empty() {
+            return;
+        }
+        if self.transition_rt.state.is_none() && self.pending_navigation.is_none() {
+            let route_id = self.preload_rt.queue.remove(0);
+            if !self.routes.widgets.contains_key(&route_id) {
+                self.preload(cx, route_id);
+            }
+        }
+        if !self.preload_rt.queue.is_empty() {
+            self.preload_rt.next_frame = cx.new_next_frame();
+        }
+    }
+}
+
+/// Remove and return the data preloaded for `route` (params, query and hash must all match).
+fn take_preloaded(
+    preloaded: &mut Vec<(Route, RouterRouteData)>,
+    route: &Route,
+) -> Option<RouterRouteData> {
+    let index = preloaded.iter().position(|(r, _)| r == route)?;
+    Some(preloaded.swap_remove(index).1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn preloaded_data_matches_the_exact_route() {
+        let user = |id: &str| Route::new(live_id!(user)).param_string(live_id!(id), id);
+        let mut preloaded: Vec<(Route, RouterRouteData)> =
+            vec![(user("1"), Arc::new(1u32)), (user("2"), Arc::new(2u32))];
+
+        assert!(take_preloaded(&mut preloaded, &user("3")).is_none());
+        assert!(take_preloaded(&mut preloaded, &Route::new(live_id!(user))).is_none());
+        let data = take_preloaded(&mut preloaded, &user("2")).unwrap();
+        assert_eq!(data.downcast::<u32>().ok().as_deref(), Some(&2));
+        assert!(take_preloaded(&mut preloaded, &user("2")).is_none());
+        assert_eq!(preloaded.len(), 1);
+        assert_eq!(preloaded[0].0, user("1"));
+    }
+
+    #[test]
+    fn preload_from_live_id_parses_known_values() {
+        assert_eq!(
+            RouterPreload::from_live_id(live_id!(on_hover)),
+            Some(RouterPreload::OnHover)
+        );
+        assert_eq!(
+            RouterPreload::from_live_id(live_id!(eager)),
+            Some(RouterPreload::Eager)
+        );
+        assert_eq!(RouterPreload::from_live_id(live_id!(sometimes)), None);
+    }
+}
@@ -0,0 +1,127 @@
+//! Per-route data resolvers and the data/load state they leave behind.
+
+use crate::guards::{
+    RouterAsyncDecision, RouterLoadState, RouterNavContext, RouterResolveResult, RouterRouteData,
+};
+use crate::route::Route;
+use makepad_widgets::*;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::RouterWidget;
+
+impl RouterWidget {
+    /// Load data for `route_id` before navigations to it commit (replaces any previous resolver).
+    ///
+    /// The navigation waits while the resolver is pending; an `Err` blocks it with
+    /// `RouterBlockReason::ResolveFailed` and can be retried with `retry_navigation`.
+    pub fn set_route_resolver<F>(&mut self, route_id: LiveId, resolver: F)
+    where
+        F: Fn(&mut Cx, &RouterNavContext) -> RouterAsyncDecision<RouterResolveResult>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.resolvers
+            .resolvers
+            .insert(route_id, Box::new(resolver));
+    }
+
+    pub fn clear_route_resolver(&mut self, route_id: LiveId) {
+        self.resolvers.resolvers.remove(&route_id);
+    }
+
+    /// Seconds to wait for a pending resolver before blocking with `ResolveFailed` (`0.0` waits
+    /// forever).
+    pub fn set_resolver_timeout(&mut self, seconds: f64) {
+        self.resolver_timeout = seconds;
+    }
+
+    /// Data last resolved for `route_id`, whatever params it was resolved with.
+    pub fn route_data(&self, route_id: LiveId) -> Option<RouterRouteData> {
+        self.resolvers
+            .data
+            .get(&route_id)
+            .map(|(_, data)| data.clone())
+    }
+
+    /// Data resolved for exactly `route` (same params, query and hash).
+    pub fn route_data_for(&self, route: &Route) -> Option<RouterRouteData> {
+        resolved_data_for(&self.resolvers.data, route)
+    }
+
+    /// Data last resolved for `route_id`, if it has type `T`.
+    pub fn route_data_as<T: Any + Send + Sync>(&self, route_id: LiveId) -> Option<Arc<T>> {
+        self.route_data(route_id)?.downcast::<T>().ok()
+    }
+
+    /// Drop the route's data, load state and any data preloaded for it.
+    pub fn clear_route_data(&mut self, route_id: LiveId) {
+        self.resolvers.clear_route_data(route_id);
+    }
+
+    pub fn route_load_state(&self, route_id: LiveId) -> RouterLoadState {
+        self.resolvers
+            .states
+            .get(&route_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+fn resolved_data_for(
+    data: &HashMap<LiveId, (Route, RouterRouteData)>,
+    route: &Route,
+) -> Option<RouterRouteData> {
+    data.get(&route.id)
+        .filter(|(resolved, _)| resolved == route)
+        .map(|(_, data)| data.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::fields::RouterResolvers;
+
+    #[test]
+    fn resolved_data_is_matched_by_the_whole_route() {
+        let user = |id: &str| Route::new(live_id!(user)).param_string(live_id!(id), id);
+        let mut data: HashMap<LiveId, (Route, RouterRouteData)> = HashMap::new();
+        data.insert(live_id!(user), (user("1"), Arc::new(1u32)));
+
+        let found = resolved_data_for(&data, &user("1")).unwrap();
+        assert_eq!(found.downcast::<u32>().ok().as_deref(), Some(&1));
+        assert!(resolved_data_for(&data, &user("2")).is_none());
+        assert!(resolved_data_for(&data, &Route::new(live_id!(other))).is_none());
+    }
+
+    #[test]
+    fn clearing_route_data_keeps_other_routes_and_the_resolver() {
+        let mut resolvers = RouterResolvers::default();
+        for id in [live_id!(user), live_id!(other)] {
+            let route = Route::new(id);
+            resolvers
+                .data
+                .insert(id, (route.clone(), Arc::new(1u32) as RouterRouteData));
+            resolvers.states.insert(id, RouterLoadState::Loaded);
+            resolvers.preloaded.push((route, Arc::new(2u32)));
+        }
+        resolvers.resolvers.insert(
+            live_id!(user),
+            Box::new(|_, _| RouterAsyncDecision::Immediate(Err("unused".into()))),
+        );
+
+        resolvers.clear_route_data(live_id!(user));
+
+        assert!(!resolvers.data.contains_key(&live_id!(user)));
+        assert!(!resolvers.states.contains_key(&live_id!(user)));
+        assert!(resolvers
+            .preloaded
+            .iter()
+            .all(|(r, _)| r.id != live_id!(user)));
+        assert!(resolvers.data.contains_key(&live_id!(other)));
+        assert_eq!(resolvers.preloaded.len(), 1);
+        assert!(resolvers.resolvers.contains_key(&live_id!(user)));
+    }
+}
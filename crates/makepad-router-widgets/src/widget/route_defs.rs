@@ -1,16 +1,96 @@
+use crate::meta::{RouteMeta, RouteMetaValue};
 use makepad_widgets::*;
 
+use super::{RouterKeepAlive, RouterPreload};
+
 #[derive(Clone, Debug, Default)]
 pub(super) struct RouteDefinition {
     pub(super) pattern: Option<String>,
+    /// Path the route's pattern redirects to (`route_redirect`).
+    pub(super) redirect: Option<String>,
+    /// Extra patterns resolving to the route (`route_aliases`, comma separated).
+    pub(super) aliases: Vec<String>,
     pub(super) transition: Option<LiveId>,
     pub(super) transition_duration: Option<f64>,
+    /// Entries of the `route_meta` object.
+    pub(super) meta: RouteMeta,
+    pub(super) overlay: bool,
+    pub(super) keep_alive: Option<RouterKeepAlive>,
+    pub(super) preload: Option<RouterPreload>,
 }
 
 pub(super) fn script_value_to_string(vm: &mut ScriptVm, value: ScriptValue) -> Option<String> {
     vm.string_with(value, |_vm, value| value.to_string())
 }
 
+/// Split a comma separated pattern list, ignoring commas inside constraints (`<[0-9]{2,4}>`).
+fn split_pattern_list(source: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in source.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                out.push(&source[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    out.push(&source[start..]);
+    out.into_iter()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Convert a scalar script value (bool, number, id or string) to a metadata value.
+fn route_meta_scalar(vm: &mut ScriptVm, value: ScriptValue) -> Option<RouteMetaValue> {
+    if let Some(value) = value.as_bool() {
+        Some(RouteMetaValue::Bool(value))
+    } else if let Some(value) = value.as_number() {
+        Some(RouteMetaValue::Number(value))
+    } else if let Some(value) = value.as_id() {
+        Some(RouteMetaValue::Id(value))
+    } else {
+        script_value_to_string(vm, value).map(RouteMetaValue::from)
+    }
+}
+
+/// Read the `route_meta` object: scalars and lists of scalars (`requires: ["admin"]`); other
+/// values are skipped.
+fn route_meta_from_value(vm: &mut ScriptVm, value: ScriptValue) -> RouteMeta {
+    let mut meta = RouteMeta::new();
+    let Some(obj) = value.as_object() else {
+        return meta;
+    };
+    vm.vec_with(obj, |vm, vec| {
+        for kv in vec {
+            let Some(key) = kv.key.as_id() else {
+                continue;
+            };
+            let value = match kv.value.as_object() {
+                Some(list) => {
+                    let mut items = Vec::new();
+                    vm.vec_with(list, |vm, vec| {
+                        items.extend(vec.iter().filter_map(|kv| route_meta_scalar(vm, kv.value)));
+                    });
+                    RouteMetaValue::List(items)
+                }
+                None => match route_meta_scalar(vm, kv.value) {
+                    Some(value) => value,
+                    None => continue,
+                },
+            };
+            meta.insert(key, value);
+        }
+    });
+    meta
+}
+
 pub(super) fn route_definition_from_template(
     vm: &mut ScriptVm,
     template_obj: ScriptObject,
@@ -22,6 +102,23 @@ pub(super) fn route_definition_from_template(
             .value(template_obj, id!(route_pattern).into(), NoTrap),
     );
 
+    let redirect = script_value_to_string(
+        vm,
+        vm.bx
+            .heap
+            .value(template_obj, id!(route_redirect).into(), NoTrap),
+    )
+    .filter(|v| !v.trim().is_empty());
+
+    let aliases = script_value_to_string(
+        vm,
+        vm.bx
+            .heap
+            .value(template_obj, id!(route_aliases).into(), NoTrap),
+    )
+    .map(|v| split_pattern_list(&v))
+    .unwrap_or_default();
+
     let route_transition_value =
         vm.bx
             .heap
@@ -36,9 +133,56 @@ pub(super) fn route_definition_from_template(
         .value(template_obj, id!(route_transition_duration).into(), NoTrap)
         .as_number();
 
+    let meta_value = vm
+        .bx
+        .heap
+        .value(template_obj, id!(route_meta).into(), NoTrap);
+    let meta = route_meta_from_value(vm, meta_value);
+
+    let overlay = vm
+        .bx
+        .heap
+        .value(template_obj, id!(route_overlay).into(), NoTrap)
+        .as_bool()
+        .unwrap_or(false);
+
+    let keep_alive = vm
+        .bx
+        .heap
+        .value(template_obj, id!(route_keep_alive).into(), NoTrap)
+        .as_id()
+        .and_then(RouterKeepAlive::from_live_id);
+
+    let preload = vm
+        .bx
+        .heap
+        .value(template_obj, id!(route_preload).into(), NoTrap)
+        .as_id()
+        .and_then(RouterPreload::from_live_id);
+
     RouteDefinition {
         pattern,
+        redirect,
+        aliases,
         transition,
         transition_duration,
+        meta,
+        overlay,
+        keep_alive,
+        preload,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_lists_split_on_top_level_commas() {
+        assert_eq!(
+            split_pattern_list(" /me, /profile/:id<[0-9]{2,4}> ,,/u/**"),
+            vec!["/me", "/profile/:id<[0-9]{2,4}>", "/u/**"]
+        );
+        assert!(split_pattern_list("  ").is_empty());
     }
 }
@@ -9,31 +9,62 @@ impl RouterWidget {
         scope: &mut Scope,
         rect: Rect,
     ) {
-        if let Some(state) = self.transition_rt.state.clone() {
-            let from_effect =
-                Self::compute_effect(state.preset, state.direction, state.progress, false, rect);
-            Self::draw_route_into_draw_list(
-                cx,
-                scope,
-                &mut self.draw_lists.from,
-                &mut self.routes.widgets,
-                state.from_route,
-                from_effect,
+        // With an overlay involved, the page beneath stays still and only overlays animate.
+        let base_route = self.overlay_base_route();
+        if let Some(base_route) = base_route {
+            self.ensure_route_widget(cx, base_route);
+            let effect = Self::compute_effect(
+                RouterTransitionPreset::None,
+                RouterTransitionDirection::Forward,
+                0.0,
                 true,
+                rect,
             );
-
-            let to_effect =
-                Self::compute_effect(state.preset, state.direction, state.progress, true, rect);
             Self::draw_route_into_draw_list(
                 cx,
                 scope,
-                &mut self.draw_lists.to,
+                &mut self.draw_lists.underlay,
                 &mut self.routes.widgets,
-                state.to_route,
-                to_effect,
-                true,
+                base_route,
+                effect,
+                false,
             );
-        } else {
+        }
+
+        if let Some(state) = self.transition_rt.state.clone() {
+            if base_route != Some(state.from_route) {
+                let from_effect = Self::compute_effect(
+                    state.preset,
+                    state.direction,
+                    state.progress,
+                    false,
+                    rect,
+                );
+                Self::draw_route_into_draw_list(
+                    cx,
+                    scope,
+                    &mut self.draw_lists.from,
+                    &mut self.routes.widgets,
+                    state.from_route,
+                    from_effect,
+                    true,
+                );
+            }
+
+            if base_route != Some(state.to_route) {
+                let to_effect =
+                    Self::compute_effect(state.preset, state.direction, state.progress, true, rect);
+                Self::draw_route_into_draw_list(
+                    cx,
+                    scope,
+                    &mut self.draw_lists.to,
+                    &mut self.routes.widgets,
+                    state.to_route,
+                    to_effect,
+                    true,
+                );
+            }
+        } else if base_route != Some(self.active_route) {
             let effect = Self::compute_effect(
                 RouterTransitionPreset::None,
                 RouterTransitionDirection::Forward,
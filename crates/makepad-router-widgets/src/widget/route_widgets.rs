@@ -12,6 +12,7 @@ impl RouterWidget {
     }
 
     pub(super) fn ensure_route_widget(&mut self, cx: &mut Cx, route_id: LiveId) {
+        self.touch_route_widget(route_id);
         if self.routes.widgets.contains_key(&route_id) {
             return;
         }
@@ -0,0 +1,77 @@
+//! Named history stacks (e.g. one back stack per bottom tab).
+
+use crate::navigation::NavigationHistory;
+use crate::route::Route;
+use crate::router::RouterAction;
+use makepad_widgets::*;
+
+use super::{RouterBlockReason, RouterWidget};
+
+impl RouterWidget {
+    /// Add a history stack rooted at `root_route_id` without switching to it.
+    ///
+    /// Returns `false` if the stack exists or the route has no template.
+    pub fn add_stack(&mut self, stack_id: LiveId, root_route_id: LiveId) -> bool {
+        if !self.routes.templates.contains_key(&root_route_id) {
+            log!("Router: Route template not found for {:?}", root_route_id);
+            return false;
+        }
+        self.router.add_stack(stack_id, Route::new(root_route_id))
+    }
+
+    /// Make `stack_id` the active history stack, keeping the previous one's entries.
+    ///
+    /// Selecting the active stack again pops it to its root. An unknown `stack_id` that names a
+    /// route is created rooted at that route. A pending navigation is cancelled.
+    ///
+    /// Guards and transitions are not run: the stack's current entry already passed the guards
+    /// when it was pushed, as with restoring persisted state. Reset the stack instead when access
+    /// must be checked again (e.g. after logout).
+    pub fn switch_stack(&mut self, cx: &mut Cx, stack_id: LiveId) -> bool {
+        if self.router.active_stack() == stack_id {
+            return self.pop_to_root(cx);
+        }
+        if !self.router.has_stack(stack_id) && !self.add_stack(stack_id, stack_id) {
+            self.last_blocked_reason = Some(RouterBlockReason::RouteMissing);
+            return false;
+        }
+        self.cancel_pending_navigation(cx);
+
+        let old_route = self.router.current_route().cloned();
+        self.router.switch_stack(stack_id);
+        let Some(new_route) = self.router.current_route().cloned() else {
+            return false;
+        };
+        self.clear_url_extras();
+        self.active_route = new_route.id;
+        self.interrupt_transition(cx);
+        self.ensure_route_widget(cx, new_route.id);
+
+        self.dispatch_route_change(cx, old_route.clone(), new_route.clone());
+        self.queue_route_actions(
+            Some(RouterAction::StackSwitched(stack_id)),
+            old_route.as_ref().map(|r| r.id),
+            &new_route,
+        );
+        self.redraw(cx);
+        true
+    }
+
+    /// Remove an inactive stack (its route widgets stay cached).
+    pub fn remove_stack(&mut self, stack_id: LiveId) -> bool {
+        self.router.remove_stack(stack_id).is_some()
+    }
+
+    pub fn active_stack(&self) -> LiveId {
+        self.router.active_stack()
+    }
+
+    /// Ids of all history stacks, active first.
+    pub fn stack_ids(&self) -> Vec<LiveId> {
+        self.router.stack_ids()
+    }
+
+    pub fn stack_history(&self, stack_id: LiveId) -> Option<&NavigationHistory> {
+        self.router.stack_history(stack_id)
+    }
+}
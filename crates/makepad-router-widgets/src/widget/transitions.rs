@@ -3,7 +3,7 @@ use makepad_widgets::*;
 
 // Route transition presets and runtime state.
 
-use super::RouterWidget;
+use super::{RouterNavEvent, RouterWidget};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RouterTransitionPreset {
@@ -72,9 +72,23 @@ pub(super) struct RouterTransitionState {
     pub(super) start_time: Option<f64>,
     pub(super) duration: f64,
     pub(super) progress: f64,
+    /// Whether `TransitionStarted` was emitted (it follows the commit that started the transition).
+    pub(super) announced: bool,
 }
 
 impl RouterTransitionState {
+    /// `TransitionStarted` for this transition, the first time it is asked for.
+    pub(super) fn take_started_event(&mut self) -> Option<RouterNavEvent> {
+        if std::mem::replace(&mut self.announced, true) {
+            return None;
+        }
+        Some(RouterNavEvent::TransitionStarted {
+            from: self.from_route,
+            to: self.to_route,
+            preset: self.preset,
+        })
+    }
+
     fn tick(&mut self, time: f64) -> bool {
         let start = self.start_time.get_or_insert(time);
         let elapsed = (time - *start).max(0.0);
@@ -131,6 +145,7 @@ impl RouterWidget {
         direction: RouterTransitionDirection,
         override_spec: Option<RouterTransitionSpec>,
     ) {
+        self.interrupt_transition(cx);
         if !self.transitions_enabled() {
             self.transition_rt.state = None;
             return;
@@ -170,23 +185,56 @@ impl RouterWidget {
             start_time: None,
             duration: spec.duration,
             progress: 0.0,
+            announced: false,
         });
         self.transition_rt.next_frame = cx.new_next_frame();
         self.redraw(cx);
     }
 
+    /// Emit `TransitionStarted` for the running transition if it has not been reported yet.
+    ///
+    /// Called after `NavigationCommitted`, so a transition is reported as part of the commit.
+    pub(super) fn announce_transition(&mut self, cx: &mut Cx) {
+        if !self.nav_events_enabled() {
+            return;
+        }
+        let event = self
+            .transition_rt
+            .state
+            .as_mut()
+            .and_then(RouterTransitionState::take_started_event);
+        if let Some(event) = event {
+            self.emit_nav_event(cx, event);
+        }
+    }
+
     pub(super) fn update_transition(&mut self, cx: &mut Cx, time: f64) {
         let Some(state) = &mut self.transition_rt.state else {
             return;
         };
         if !state.tick(time) {
             self.transition_rt.next_frame = cx.new_next_frame();
-        } else {
-            self.transition_rt.state = None;
+        } else if let Some(finished) = self.transition_rt.state.take() {
+            self.emit_transition_finished(cx, &finished);
+            self.evict_route_widgets(cx);
         }
         self.redraw(cx);
     }
 
+    /// Stop a running transition where it is, reporting it as finished.
+    pub(super) fn interrupt_transition(&mut self, cx: &mut Cx) {
+        if let Some(interrupted) = self.transition_rt.state.take() {
+            self.emit_transition_finished(cx, &interrupted);
+        }
+    }
+
+    fn emit_transition_finished(&mut self, cx: &mut Cx, state: &RouterTransitionState) {
+        if state.announced && self.nav_events_enabled() {
+            let (from, to) = (state.from_route, state.to_route);
+            self.emit_nav_event(cx, RouterNavEvent::TransitionFinished { from, to });
+        }
+    }
+
     pub(super) fn ease_in_out(t: f64) -> f64 {
         let t = t.clamp(0.0, 1.0);
         t * t * (3.0 - 2.0 * t)
@@ -349,6 +397,7 @@ mod tests {
             start_time: None,
             duration: 1.0,
             progress: 0.0,
+            announced: false,
         };
 
         assert!(!state.tick(10.0));
@@ -1,4 +1,8 @@
 use super::RouterWidget;
+use crate::error::UrlForError;
+use crate::pattern::{RouteParams, RoutePattern};
+use crate::route::RouteQuery;
+use makepad_widgets::*;
 
 impl RouterWidget {
     pub(super) fn clear_url_extras(&mut self) {
@@ -26,7 +30,7 @@ impl RouterWidget {
         }
     }
 
-    fn current_path_for_route(&self, route: &crate::route::Route) -> String {
+    pub(super) fn current_path_for_route(&self, route: &crate::route::Route) -> String {
         // Keep unknown path visible while showing the configured not-found route.
         if self.not_found_route.0 != 0 && route.id == self.not_found_route {
             if let Some(path) = &self.url_path_override {
@@ -45,10 +49,20 @@ impl RouterWidget {
             .as_ref()
             .or_else(|| self.router.route_registry.get_pattern(route.id));
 
+        let child = self
+            .child_routers
+            .get(&route.id)
+            .and_then(|child_router| child_router.borrow());
+
         let base = if let Some(pattern) = pattern {
-            pattern
-                .format_path(&route.params)
-                .unwrap_or_else(|| pattern.format_base_path(&route.params))
+            // A child router owns the wildcard tail, so named wildcard captures are not re-emitted.
+            if child.is_some() {
+                pattern.format_base_path(&route.params)
+            } else {
+                pattern
+                    .format_path(&route.params)
+                    .unwrap_or_else(|| pattern.format_base_path(&route.params))
+            }
         } else {
             let s = route.id.to_string();
             if s.is_empty() {
@@ -58,10 +72,7 @@ impl RouterWidget {
             }
         };
 
-        let Some(child_router) = self.child_routers.get(&route.id) else {
-            return base;
-        };
-        let Some(child) = child_router.borrow() else {
+        let Some(child) = child else {
             return base;
         };
         let tail = child.current_path();
@@ -75,6 +86,51 @@ impl RouterWidget {
         self.current_path_for_route(route)
     }
 
+    /// Build the path for `route_id`, searching this router and then its nested child routers.
+    ///
+    /// For a route owned by a child router, the parent route's base path is prefixed. Parent params
+    /// come from `params`, falling back to the parent route's current params when it is active.
+    fn path_for(&self, route_id: LiveId, params: &RouteParams) -> Result<String, UrlForError> {
+        match self.router.route_registry.path_for(route_id, params) {
+            Err(UrlForError::UnknownRoute { .. }) => {}
+            result => return result,
+        }
+
+        for (parent_id, child_router) in self.child_routers.iter() {
+            let Some(child) = child_router.borrow() else {
+                continue;
+            };
+            let tail = match child.path_for(route_id, params) {
+                Ok(tail) => tail,
+                Err(UrlForError::UnknownRoute { .. }) => continue,
+                Err(err) => return Err(err),
+            };
+            let Some(pattern) = self.router.route_registry.get_pattern(*parent_id) else {
+                return Ok(tail);
+            };
+            let current_params = self
+                .router
+                .current_route()
+                .filter(|current| current.id == *parent_id)
+                .map(|current| &current.params);
+            return Ok(nested_route_path(pattern, params, current_params, &tail));
+        }
+
+        Err(UrlForError::UnknownRoute { route_id })
+    }
+
+    /// Build a full URL for a route in this router or a nested child router (reverse routing).
+    pub fn url_for(
+        &self,
+        route_id: LiveId,
+        params: &RouteParams,
+        query: &RouteQuery,
+        hash: &str,
+    ) -> Result<String, UrlForError> {
+        let path = self.path_for(route_id, params)?;
+        Ok(crate::url::build_url(&path, &query.data, hash))
+    }
+
     pub fn current_url(&self) -> String {
         let Some(route) = self.router.current_route() else {
             return self.current_path();
@@ -87,3 +143,50 @@ impl RouterWidget {
         )
     }
 }
+
+/// Path of a child router's route: the parent route's base path (params from `params`, then the
+/// parent's `current_params` if it is active) followed by the child's `tail`.
+fn nested_route_path(
+    parent_pattern: &RoutePattern,
+    params: &RouteParams,
+    current_params: Option<&RouteParams>,
+    tail: &str,
+) -> String {
+    let mut parent_params = params.clone();
+    for (key, value) in current_params.into_iter().flat_map(RouteParams::iter) {
+        if parent_params.get_value(*key).is_none() {
+            parent_params.insert(*key, value.clone());
+        }
+    }
+    let base = parent_pattern.format_base_path(&parent_params);
+    RouterWidget::join_paths(&base, tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use makepad_live_id::live_id;
+
+    #[test]
+    fn nested_route_path_prefixes_the_parent_base_path() {
+        let parent = RoutePattern::parse("/org/:org/**").unwrap();
+        let mut params = RouteParams::new();
+        params.add_string(live_id!(org), "acme");
+        assert_eq!(
+            nested_route_path(&parent, &params, None, "/settings"),
+            "/org/acme/settings"
+        );
+
+        // Explicit params win over the active parent route's params.
+        let mut current = RouteParams::new();
+        current.add_string(live_id!(org), "current");
+        assert_eq!(
+            nested_route_path(&parent, &params, Some(&current), "/settings"),
+            "/org/acme/settings"
+        );
+        assert_eq!(
+            nested_route_path(&parent, &RouteParams::new(), Some(&current), "/"),
+            "/org/current"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use makepad_router_perf::{
-    build_mixed_route_table, router_from_table, run_dispatch_go_to_path_samples,
-    run_lookup_samples, run_stack_ops_samples, stats_to_json, summarize_us,
+    build_dynamic_first_route_table, build_mixed_route_table, router_from_table,
+    run_dispatch_go_to_path_samples, run_lookup_samples, run_stack_ops_samples, stats_to_json,
+    summarize_us,
 };
 
 fn main() {
@@ -13,6 +14,7 @@ fn main() {
 
     for scale in [100usize, 500usize, 1_000usize] {
         let table = build_mixed_route_table(scale);
+        let dynamic_first = build_dynamic_first_route_table(scale);
         let router = router_from_table(&table);
 
         all.push(summarize_us(
@@ -23,6 +25,18 @@ fn main() {
             &format!("lookup_dynamic_{}", scale),
             &run_lookup_samples(&table.registry, &table.dynamic_paths, iterations),
         ));
+        all.push(summarize_us(
+            &format!("lookup_dynamic_first_{}", scale),
+            &run_lookup_samples(
+                &dynamic_first.registry,
+                &dynamic_first.dynamic_paths,
+                iterations,
+            ),
+        ));
+        all.push(summarize_us(
+            &format!("lookup_miss_{}", scale),
+            &run_lookup_samples(&table.registry, &table.miss_paths, iterations),
+        ));
         all.push(summarize_us(
             &format!("lookup_mixed_{}", scale),
             &run_lookup_samples(&table.registry, &table.mixed_paths, iterations),